@@ -0,0 +1,24 @@
+use std::path::Path;
+
+use crate::services::dj_collection::{
+    export_collection, DjCollectionFormat, DjExportResult, DjPlaylist,
+};
+
+/// Export downloaded playlists as a Rekordbox XML or Traktor NML collection.
+///
+/// Several playlists can be merged into one file; each becomes a playlist
+/// node named after its title, listing its files in order. Files that can
+/// no longer be read are left out and reported in `skipped`.
+#[tauri::command]
+#[specta::specta]
+pub fn export_dj_collection(
+    playlists: Vec<DjPlaylist>,
+    output_path: String,
+    format: DjCollectionFormat,
+) -> Result<DjExportResult, String> {
+    if playlists.is_empty() {
+        return Err("No playlists to export".to_string());
+    }
+
+    export_collection(&playlists, Path::new(&output_path), format)
+}
@@ -5,12 +5,17 @@ use std::sync::Arc;
 use tauri::{Emitter, State};
 
 use crate::models::{ErrorResponse, HasErrorCode};
+use crate::services::artwork::{ArtworkCache, FolderArtName};
 use crate::services::auth_choice::{AuthChoice, AuthChoiceState};
 use crate::services::cancellation::CancellationState;
-use crate::services::metadata::TrackMetadata;
+use crate::services::metadata::{TagOptions, TrackMetadata};
+use crate::services::mix_splitter::MixSplitOptions;
 use crate::services::paths::get_downloads_dir;
 use crate::services::pipeline::{download_and_convert, PipelineConfig};
-use crate::services::queue::{DownloadQueue, QueueItem, QueueProcessContext};
+use crate::services::playlist::AlbumMetadata;
+use crate::services::queue::{DownloadQueue, QueueItem, QueueProcessContext, SubfolderNaming};
+use crate::services::title_rules::{TitleRuleEngine, TitleRules};
+use crate::services::url_validator::with_secret_token;
 use crate::services::ytdlp::DownloadProgressEvent;
 
 #[derive(Debug, Deserialize, Type)]
@@ -25,6 +30,24 @@ pub struct DownloadRequest {
     pub total_tracks: Option<u32>,
     pub artwork_url: Option<String>,
     pub output_dir: Option<String>,
+    pub album_artist: Option<String>,
+    pub genre: Option<String>,
+    pub release_date: Option<String>,
+    pub label: Option<String>,
+    pub isrc: Option<String>,
+    pub description: Option<String>,
+    pub permalink_url: Option<String>,
+    pub bpm: Option<f64>,
+    /// Duration in milliseconds.
+    pub duration: Option<u64>,
+    /// Secret token of a private track, added to `track_url` for yt-dlp.
+    pub secret_token: Option<String>,
+    #[serde(default)]
+    pub tag_options: TagOptions,
+    /// Title cleanup rules (None disables cleanup).
+    pub title_rules: Option<TitleRules>,
+    /// Split mixes into songs after download (None keeps them whole).
+    pub split_mix: Option<MixSplitOptions>,
 }
 
 /// Download and convert a track to MP3 with metadata embedding.
@@ -32,39 +55,66 @@ pub struct DownloadRequest {
 /// This command orchestrates the full download pipeline:
 /// 1. Downloads audio using yt-dlp with OAuth authentication
 /// 2. Converts to high-quality MP3 using yt-dlp native conversion
-/// 3. Embeds ID3 metadata (title, artist, album, track number, artwork)
+/// 3. Embeds ID3 metadata (title, artist, album, track number, genre, date,
+///    label, ISRC, comment, source URL, BPM, artwork)
 /// 4. Emits progress events throughout the process
 #[tauri::command]
 #[specta::specta]
 pub async fn download_track_full(
     request: DownloadRequest,
     app: tauri::AppHandle,
+    artwork_cache: State<'_, Arc<ArtworkCache>>,
 ) -> Result<String, ErrorResponse> {
     let output_path = match request.output_dir {
         Some(dir) => PathBuf::from(dir),
         None => get_download_path(&app)?,
     };
 
+    let (title, artist) = match request.title_rules {
+        Some(rules) => {
+            let engine = TitleRuleEngine::new(rules).map_err(|message| ErrorResponse {
+                code: "INVALID_TITLE_RULES".to_string(),
+                message,
+            })?;
+            let cleaned = engine.apply(&request.title, &request.artist);
+            (cleaned.title, cleaned.artist)
+        }
+        None => (request.title, request.artist),
+    };
+
     let metadata = TrackMetadata {
-        title: request.title,
-        artist: request.artist,
+        title,
+        artist,
         album: request.album,
         track_number: request.track_number,
         total_tracks: request.total_tracks,
         artwork_url: request.artwork_url,
+        album_artist: request.album_artist,
+        genre: request.genre,
+        release_date: request.release_date,
+        label: request.label,
+        isrc: request.isrc,
+        description: request.description,
+        permalink_url: request.permalink_url,
+        bpm: request.bpm,
+        duration: request.duration,
+        ..Default::default()
     };
 
     let track_id = request.track_id.clone();
 
     let config = PipelineConfig {
-        track_url: request.track_url,
+        track_url: with_secret_token(&request.track_url, request.secret_token.as_deref()),
         track_id: track_id.clone(),
         output_dir: output_path,
         metadata,
         playlist_context: None,
+        tag_options: request.tag_options,
+        artwork_cache: Arc::clone(&artwork_cache),
+        split_mix: request.split_mix,
     };
 
-    let result_path = download_and_convert(&app, config, None, None, None, false)
+    let result_paths = download_and_convert(&app, config, None, None, None, false)
         .await
         .map_err(|e| {
             let _ = app.emit(
@@ -96,7 +146,12 @@ pub async fn download_track_full(
         },
     );
 
-    Ok(result_path.to_str().unwrap_or_default().to_string())
+    // A split mix reports its first piece
+    Ok(result_paths
+        .first()
+        .and_then(|path| path.to_str())
+        .unwrap_or_default()
+        .to_string())
 }
 
 #[derive(Debug, Deserialize, Type)]
@@ -105,6 +160,23 @@ pub struct StartQueueRequest {
     pub tracks: Vec<QueueItemRequest>,
     pub album_name: Option<String>,
     pub output_dir: Option<String>,
+    /// Album artist written to every track (the playlist owner).
+    pub album_artist: Option<String>,
+    /// Album mode: playlist-level metadata for album/EP sets.
+    pub album: Option<AlbumMetadata>,
+    #[serde(default)]
+    pub tag_options: TagOptions,
+    /// Save the cover as `cover.jpg`/`folder.jpg` in the output directory.
+    pub folder_art: Option<FolderArtName>,
+    /// Title cleanup rules (None disables cleanup).
+    pub title_rules: Option<TitleRules>,
+    /// Split mixes into songs after download (None keeps them whole).
+    pub split_mix: Option<MixSplitOptions>,
+    /// Save tracks to an artist subfolder (None saves to the output directory).
+    pub subfolder: Option<SubfolderNaming>,
+    /// Tracks in the source playlist including unavailable ones
+    /// (`PlaylistInfo.track_count`). Defaults to the number of queued tracks.
+    pub total_tracks: Option<u32>,
 }
 
 #[derive(Debug, Deserialize, Type)]
@@ -115,6 +187,20 @@ pub struct QueueItemRequest {
     pub title: String,
     pub artist: String,
     pub artwork_url: Option<String>,
+    pub genre: Option<String>,
+    pub release_date: Option<String>,
+    pub label: Option<String>,
+    pub isrc: Option<String>,
+    pub description: Option<String>,
+    pub permalink_url: Option<String>,
+    pub bpm: Option<f64>,
+    /// Duration in milliseconds.
+    pub duration: Option<u64>,
+    /// Position in the source playlist, used as track number. Defaults to
+    /// the position in the queue.
+    pub track_number: Option<u32>,
+    /// Secret token of a private track, added to `track_url` for yt-dlp.
+    pub secret_token: Option<String>,
 }
 
 /// Start processing a download queue.
@@ -132,6 +218,7 @@ pub async fn start_download_queue(
     app: tauri::AppHandle,
     cancel_state: State<'_, CancellationState>,
     auth_choice_state: State<'_, Arc<AuthChoiceState>>,
+    artwork_cache: State<'_, Arc<ArtworkCache>>,
 ) -> Result<(), String> {
     cancel_state.reset();
     auth_choice_state.reset();
@@ -146,16 +233,30 @@ pub async fn start_download_queue(
         .into_iter()
         .enumerate()
         .map(|(i, t)| QueueItem {
-            track_url: t.track_url,
+            track_url: with_secret_token(&t.track_url, t.secret_token.as_deref()),
             track_id: t.track_id,
             title: t.title,
             artist: t.artist,
             artwork_url: t.artwork_url,
-            track_number: Some((i + 1) as u32),
+            track_number: t.track_number.or(Some((i + 1) as u32)),
+            genre: t.genre,
+            release_date: t.release_date,
+            label: t.label,
+            isrc: t.isrc,
+            description: t.description,
+            permalink_url: t.permalink_url,
+            bpm: t.bpm,
+            duration: t.duration,
         })
         .collect();
 
-    let mut queue = DownloadQueue::new(items, request.album_name);
+    let title_rules = request.title_rules.map(TitleRuleEngine::new).transpose()?;
+
+    let mut queue = DownloadQueue::new(items, request.album_name)
+        .with_title_rules(title_rules.as_ref())
+        .with_album_artist(request.album_artist)
+        .with_album(request.album)
+        .with_playlist_total(request.total_tracks);
 
     let ctx = QueueProcessContext {
         output_dir,
@@ -163,6 +264,11 @@ pub async fn start_download_queue(
         active_child: cancel_state.active_child(),
         active_pid: cancel_state.active_pid(),
         auth_choice_state: Arc::clone(&auth_choice_state),
+        tag_options: request.tag_options,
+        artwork_cache: Arc::clone(&artwork_cache),
+        folder_art: request.folder_art,
+        split_mix: request.split_mix,
+        subfolder: request.subfolder,
     };
 
     tokio::spawn(async move {
@@ -211,6 +317,7 @@ fn get_download_path(app: &tauri::AppHandle) -> Result<PathBuf, ErrorResponse> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::artwork::ArtworkSize;
 
     #[test]
     fn test_download_request_deserialize() {
@@ -289,6 +396,42 @@ mod tests {
         let item: QueueItemRequest = serde_json::from_str(json).unwrap();
         assert_eq!(item.track_url, "https://soundcloud.com/test/track");
         assert!(item.artwork_url.is_none());
+        assert!(item.genre.is_none());
+        assert!(item.bpm.is_none());
+        assert!(item.track_number.is_none());
+    }
+
+    #[test]
+    fn test_queue_item_request_deserialize_extended_metadata() {
+        let json = r#"{
+            "trackUrl": "https://soundcloud.com/test/track",
+            "trackId": "123456",
+            "title": "Test Track",
+            "artist": "Test Artist",
+            "genre": "Techno",
+            "releaseDate": "2023-06-02T00:00:00Z",
+            "label": "Some Label",
+            "isrc": "GBXXX2300001",
+            "description": "Out now",
+            "permalinkUrl": "https://soundcloud.com/test/track",
+            "bpm": 128.0,
+            "trackNumber": 4,
+            "secretToken": "s-AbC12"
+        }"#;
+
+        let item: QueueItemRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(item.genre, Some("Techno".to_string()));
+        assert_eq!(item.release_date, Some("2023-06-02T00:00:00Z".to_string()));
+        assert_eq!(item.label, Some("Some Label".to_string()));
+        assert_eq!(item.isrc, Some("GBXXX2300001".to_string()));
+        assert_eq!(item.description, Some("Out now".to_string()));
+        assert_eq!(
+            item.permalink_url,
+            Some("https://soundcloud.com/test/track".to_string())
+        );
+        assert_eq!(item.bpm, Some(128.0));
+        assert_eq!(item.track_number, Some(4));
+        assert_eq!(item.secret_token, Some("s-AbC12".to_string()));
     }
 
     #[test]
@@ -309,12 +452,15 @@ mod tests {
                     "artworkUrl": "https://example.com/art.jpg"
                 }
             ],
-            "albumName": "Test Album"
+            "albumName": "Test Album",
+            "albumArtist": "Playlist Owner"
         }"#;
 
         let request: StartQueueRequest = serde_json::from_str(json).unwrap();
         assert_eq!(request.tracks.len(), 2);
         assert_eq!(request.album_name, Some("Test Album".to_string()));
+        assert_eq!(request.album_artist, Some("Playlist Owner".to_string()));
+        assert!(request.album.is_none());
         assert_eq!(request.tracks[0].track_id, "1");
         assert_eq!(request.tracks[1].track_id, "2");
         assert!(request.tracks[0].artwork_url.is_none());
@@ -338,4 +484,51 @@ mod tests {
         assert_eq!(request.tracks.len(), 1);
         assert!(request.album_name.is_none());
     }
+
+    #[test]
+    fn test_start_queue_request_deserialize_album_mode() {
+        let json = r#"{
+            "tracks": [],
+            "albumName": "Debut EP",
+            "album": {
+                "artist": "Owner",
+                "artwork_url": "https://example.com/ep.jpg",
+                "release_date": "2024-02-01T00:00:00Z",
+                "label": null,
+                "genre": "House"
+            }
+        }"#;
+
+        let request: StartQueueRequest = serde_json::from_str(json).unwrap();
+        let album = request.album.unwrap();
+        assert_eq!(album.artist, "Owner");
+        assert_eq!(
+            album.artwork_url,
+            Some("https://example.com/ep.jpg".to_string())
+        );
+        assert_eq!(album.genre, Some("House".to_string()));
+    }
+
+    #[test]
+    fn test_start_queue_request_deserialize_artwork_options() {
+        let json = r#"{
+            "tracks": [],
+            "tagOptions": { "artworkSize": { "kind": "resize", "px": 1000 } },
+            "folderArt": "cover"
+        }"#;
+
+        let request: StartQueueRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            request.tag_options.artwork_size,
+            ArtworkSize::Resize { px: 1000 }
+        );
+        assert_eq!(request.folder_art, Some(FolderArtName::Cover));
+    }
+
+    #[test]
+    fn test_start_queue_request_default_artwork_options() {
+        let request: StartQueueRequest = serde_json::from_str(r#"{"tracks": []}"#).unwrap();
+        assert_eq!(request.tag_options.artwork_size, ArtworkSize::T500x500);
+        assert!(request.folder_art.is_none());
+    }
 }
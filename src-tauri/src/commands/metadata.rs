@@ -0,0 +1,72 @@
+use std::path::Path;
+use std::sync::Arc;
+use tauri::{AppHandle, State};
+
+use crate::models::ErrorResponse;
+use crate::services::artwork::ArtworkCache;
+use crate::services::history::{history_path, load_entries};
+use crate::services::retag::{retag_directory, RetagOptions, RetagSummary};
+use crate::services::tag_editor::{self, EditableTags, TagPatch, TagWriteResult};
+use crate::services::title_rules::{CleanedTitle, TitleRuleEngine, TitleRules};
+
+/// Preview the artist/title produced by the title cleanup rules.
+///
+/// Fails if one of the junk patterns is not a valid regular expression.
+#[tauri::command]
+#[specta::specta]
+pub fn preview_title_rules(
+    title: String,
+    artist: String,
+    rules: TitleRules,
+) -> Result<CleanedTitle, String> {
+    let engine = TitleRuleEngine::new(rules)?;
+    Ok(engine.apply(&title, &artist))
+}
+
+/// Re-tag previously downloaded files in a directory from SoundCloud metadata.
+///
+/// Files are matched to SoundCloud tracks by their embedded track ID or URL,
+/// or by file name via the download history. With `dryRun` set, no file is
+/// written and the result lists the proposed changes. Progress is emitted
+/// via `retag-progress`.
+#[tauri::command]
+#[specta::specta]
+pub async fn retag_library(
+    dir: String,
+    options: RetagOptions,
+    app: AppHandle,
+    artwork_cache: State<'_, Arc<ArtworkCache>>,
+) -> Result<RetagSummary, String> {
+    let dir_path = Path::new(&dir);
+    if !dir_path.is_dir() {
+        return Err("Directory does not exist".to_string());
+    }
+
+    let history = history_path(&app)
+        .map(|path| load_entries(&path))
+        .unwrap_or_default();
+
+    retag_directory(&app, dir_path, &options, &history, &artwork_cache).await
+}
+
+/// Read the editable tags of a downloaded file (MP3, AIFF or WAV).
+#[tauri::command]
+#[specta::specta]
+pub fn read_tags(path: String) -> Result<EditableTags, ErrorResponse> {
+    tag_editor::read_tags(Path::new(&path)).map_err(ErrorResponse::from)
+}
+
+/// Apply the same tag changes to one or more files.
+///
+/// Use a single path to edit one file, or all files of a playlist for batch
+/// edits such as setting the album. Returns a result per file.
+#[tauri::command]
+#[specta::specta]
+pub async fn write_tags(
+    paths: Vec<String>,
+    patch: TagPatch,
+) -> Result<Vec<TagWriteResult>, ErrorResponse> {
+    tag_editor::write_tags(&paths, &patch)
+        .await
+        .map_err(ErrorResponse::from)
+}
@@ -1,17 +1,27 @@
 pub mod auth;
+pub mod collection;
 pub mod download;
 pub mod ffmpeg;
+pub mod metadata;
+pub mod network;
 pub mod playlist;
 pub mod settings;
 pub mod updater;
 pub mod ytdlp;
 
 pub use auth::{check_auth_state, complete_oauth, sign_out, start_oauth, OAuthState};
+pub use collection::export_dj_collection;
 pub use download::{
     cancel_download_queue, download_track_full, respond_to_auth_choice, start_download_queue,
 };
 pub use ffmpeg::test_ffmpeg;
-pub use playlist::{get_playlist_info, get_track_info, validate_soundcloud_url};
+pub use metadata::{preview_title_rules, read_tags, retag_library, write_tags};
+pub use network::{set_proxy, test_proxy};
+pub use playlist::{
+    get_collection_info, get_my_likes, get_my_playlists, get_my_uploads, get_playlist_info,
+    get_profile_info, get_track_info, normalize_and_extract_urls, resolve_share_link,
+    search_soundcloud, validate_soundcloud_url,
+};
 pub use settings::{check_write_permission, get_default_download_path, validate_download_path};
 pub use updater::{check_for_updates, install_update};
 pub use ytdlp::test_ytdlp;
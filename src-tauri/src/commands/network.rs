@@ -0,0 +1,28 @@
+use crate::models::ErrorResponse;
+use crate::services::proxy::{self, ProxySettings};
+use crate::services::{http, storage};
+
+/// Route API requests and yt-dlp through the configured proxy.
+///
+/// The setting is saved and applied again on startup; until it is first
+/// set, the system proxy environment variables are used.
+#[tauri::command]
+#[specta::specta]
+pub fn set_proxy(settings: ProxySettings) -> Result<(), ErrorResponse> {
+    log::info!("[proxy] Using {:?} proxy", settings.mode);
+    http::client().set_proxy(settings.clone())?;
+
+    if let Err(e) = storage::store_proxy(&settings) {
+        log::warn!("[proxy] Failed to save proxy settings: {}", e);
+    }
+    Ok(())
+}
+
+/// Check that SoundCloud is reachable through a proxy before saving it.
+///
+/// Returns the round trip time in milliseconds.
+#[tauri::command]
+#[specta::specta]
+pub async fn test_proxy(settings: ProxySettings) -> Result<u64, ErrorResponse> {
+    proxy::check_proxy(&settings).await.map_err(|e| e.into())
+}
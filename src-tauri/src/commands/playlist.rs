@@ -1,11 +1,20 @@
 use std::future::Future;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
 
-use crate::models::url::ValidationResult;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::models::url::{ExtractedUrl, UrlType, ValidationResult};
+use crate::services::metadata_cache::MetadataCache;
 use crate::services::playlist::{
-    fetch_playlist_info, fetch_track_info, PlaylistError, PlaylistInfo, TrackInfo,
+    fetch_collection_info, fetch_my_likes, fetch_my_playlists, fetch_my_uploads,
+    fetch_playlist_info, fetch_profile_info, fetch_track_info, search, CollectionInfo,
+    FetchOptions, PlaylistError, PlaylistFetchProgressEvent, PlaylistInfo, SearchFilters,
+    SearchItems, SearchKind, SearchResults, TrackInfo,
 };
+use crate::services::share_link::{self, ResolvedLink};
 use crate::services::storage::{load_tokens, refresh_and_store_tokens};
-use crate::services::url_validator::validate_url;
+use crate::services::url_validator::{extract_urls, validate_url};
 
 #[tauri::command]
 #[specta::specta]
@@ -13,6 +22,29 @@ pub fn validate_soundcloud_url(url: String) -> ValidationResult {
     validate_url(&url)
 }
 
+/// Find every SoundCloud link in pasted text and normalize it.
+///
+/// Hosts are canonicalized and tracking parameters stripped; secret tokens
+/// are kept. With `expand_playlists`, `?in=user/sets/...` track links are
+/// replaced by the playlist they were opened from. Duplicates are removed.
+#[tauri::command]
+#[specta::specta]
+pub fn normalize_and_extract_urls(
+    text: String,
+    expand_playlists: Option<bool>,
+) -> Vec<ExtractedUrl> {
+    extract_urls(&text, expand_playlists.unwrap_or(false))
+}
+
+/// Resolve an `on.soundcloud.com` share link to its canonical URL and type.
+#[tauri::command]
+#[specta::specta]
+pub async fn resolve_share_link(url: String) -> Result<ResolvedLink, String> {
+    share_link::resolve_share_link(&url)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 async fn try_refresh_token() -> Result<(), String> {
     let tokens = load_tokens()
         .map_err(|e| e.to_string())?
@@ -53,24 +85,252 @@ where
     }
 }
 
+/// Emits `playlist-fetch-progress` for each batch of tracks fetched for `source`.
+fn progress_emitter(app: AppHandle, source: String) -> impl Fn(&[TrackInfo]) + Send + Sync {
+    let fetched = AtomicU32::new(0);
+
+    move |tracks: &[TrackInfo]| {
+        let count = tracks.len() as u32;
+        let _ = app.emit(
+            "playlist-fetch-progress",
+            PlaylistFetchProgressEvent {
+                source: source.clone(),
+                fetched: fetched.fetch_add(count, Ordering::Relaxed) + count,
+                tracks: tracks.to_vec(),
+            },
+        );
+    }
+}
+
+/// Fetch a playlist with all its tracks.
+///
+/// Large playlists are paged through; tracks are streamed via
+/// `playlist-fetch-progress` while they load. Playlists fetched recently
+/// are served from the metadata cache unless `force_refresh` is set.
+/// Share links are resolved first.
 #[tauri::command]
 #[specta::specta]
-pub async fn get_playlist_info(url: String) -> Result<PlaylistInfo, String> {
-    with_token_refresh(
-        || fetch_playlist_info(&url),
+pub async fn get_playlist_info(
+    url: String,
+    force_refresh: Option<bool>,
+    app: AppHandle,
+    cache: State<'_, Arc<MetadataCache>>,
+) -> Result<PlaylistInfo, String> {
+    let url = share_link::canonicalize(&url)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !force_refresh.unwrap_or(false) {
+        let cache = Arc::clone(&cache);
+        let key = url.clone();
+        let cached = tokio::task::spawn_blocking(move || cache.playlist_by_url(&key))
+            .await
+            .ok()
+            .flatten();
+        if let Some(playlist) = cached {
+            log::info!("[get_playlist_info] Cache hit for '{}'", playlist.title);
+            return Ok(playlist);
+        }
+    }
+
+    let progress = progress_emitter(app, url.clone());
+    let options = FetchOptions {
+        progress: Some(&progress),
+        cache: Some(cache.inner()),
+        ..Default::default()
+    };
+
+    let playlist = with_token_refresh(
+        || fetch_playlist_info(&url, &options),
         "get_playlist_info",
         |info| format!("got playlist '{}'", info.title),
     )
-    .await
+    .await?;
+
+    // One file per track; keep the writes off the async runtime
+    let cache = Arc::clone(&cache);
+    let cached = playlist.clone();
+    tokio::task::spawn_blocking(move || cache.put_playlist(&url, &cached));
+    Ok(playlist)
 }
 
+/// Fetch a single track, from the metadata cache unless `force_refresh`
+/// is set or the cached entry expired. Share links are resolved first.
 #[tauri::command]
 #[specta::specta]
-pub async fn get_track_info(url: String) -> Result<TrackInfo, String> {
-    with_token_refresh(
+pub async fn get_track_info(
+    url: String,
+    force_refresh: Option<bool>,
+    cache: State<'_, Arc<MetadataCache>>,
+) -> Result<TrackInfo, String> {
+    let url = share_link::canonicalize(&url)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !force_refresh.unwrap_or(false) {
+        let cache = Arc::clone(&cache);
+        let key = url.clone();
+        let cached = tokio::task::spawn_blocking(move || cache.track_by_url(&key))
+            .await
+            .ok()
+            .flatten();
+        if let Some(track) = cached {
+            log::info!("[get_track_info] Cache hit for '{}'", track.title);
+            return Ok(track);
+        }
+    }
+
+    let track = with_token_refresh(
         || fetch_track_info(&url),
         "get_track_info",
         |info| format!("got track '{}'", info.title),
     )
+    .await?;
+
+    let cache = Arc::clone(&cache);
+    let cached = track.clone();
+    tokio::task::spawn_blocking(move || cache.put_track(Some(&url), &cached));
+    Ok(track)
+}
+
+/// Fetch every track uploaded by an artist from their profile URL.
+///
+/// The result has the same shape as a playlist, titled after the artist,
+/// so it can be fed into `start_download_queue`.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_profile_info(url: String, app: AppHandle) -> Result<PlaylistInfo, String> {
+    let progress = progress_emitter(app, url.clone());
+    let options = FetchOptions {
+        progress: Some(&progress),
+        ..Default::default()
+    };
+
+    with_token_refresh(
+        || fetch_profile_info(&url, &options),
+        "get_profile_info",
+        |info| format!("got {} tracks of '{}'", info.tracks.len(), info.title),
+    )
+    .await
+}
+
+/// Fetch a profile tab: `/likes`, `/reposts`, `/popular-tracks`, `/albums`
+/// or `/sets`.
+///
+/// Albums and sets expand into one playlist each; queue them one at a time
+/// with the `playlist` subfolder naming so every set gets its own folder
+/// and track numbering.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_collection_info(url: String, app: AppHandle) -> Result<CollectionInfo, String> {
+    let kind = match validate_url(&url).url_type {
+        Some(UrlType::Collection(kind)) => kind,
+        _ => return Err("Not a SoundCloud collection URL".to_string()),
+    };
+
+    let progress = progress_emitter(app, url.clone());
+    let options = FetchOptions {
+        progress: Some(&progress),
+        ..Default::default()
+    };
+
+    with_token_refresh(
+        || fetch_collection_info(&url, kind, &options),
+        "get_collection_info",
+        |info| {
+            format!(
+                "got {} playlists of '{}'",
+                info.playlists.len(),
+                info.user.username
+            )
+        },
+    )
+    .await
+}
+
+/// Fetch the tracks liked by the signed-in user.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_my_likes(app: AppHandle) -> Result<PlaylistInfo, String> {
+    let progress = progress_emitter(app, "me/likes".to_string());
+    let options = FetchOptions {
+        progress: Some(&progress),
+        ..Default::default()
+    };
+
+    with_token_refresh(
+        || fetch_my_likes(&options),
+        "get_my_likes",
+        |info| format!("got {} liked tracks", info.tracks.len()),
+    )
+    .await
+}
+
+/// Fetch the signed-in user's playlists, private ones included.
+///
+/// Private playlists and tracks keep their `secret_token` so they can be
+/// downloaded.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_my_playlists(app: AppHandle) -> Result<Vec<PlaylistInfo>, String> {
+    let progress = progress_emitter(app, "me/playlists".to_string());
+    let options = FetchOptions {
+        progress: Some(&progress),
+        ..Default::default()
+    };
+
+    with_token_refresh(
+        || fetch_my_playlists(&options),
+        "get_my_playlists",
+        |playlists| format!("got {} playlists", playlists.len()),
+    )
+    .await
+}
+
+/// Fetch the signed-in user's uploads, private tracks included.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_my_uploads(app: AppHandle) -> Result<PlaylistInfo, String> {
+    let progress = progress_emitter(app, "me/tracks".to_string());
+    let options = FetchOptions {
+        progress: Some(&progress),
+        ..Default::default()
+    };
+
+    with_token_refresh(
+        || fetch_my_uploads(&options),
+        "get_my_uploads",
+        |info| format!("got {} uploads", info.tracks.len()),
+    )
+    .await
+}
+
+/// Search SoundCloud for tracks, playlists or users.
+///
+/// Pass `next_offset` of the results as `offset` to get the next page.
+/// `filters` only apply to track searches.
+#[tauri::command]
+#[specta::specta]
+pub async fn search_soundcloud(
+    query: String,
+    kind: SearchKind,
+    limit: u32,
+    offset: u32,
+    filters: Option<SearchFilters>,
+) -> Result<SearchResults, String> {
+    let filters = filters.unwrap_or_default();
+
+    with_token_refresh(
+        || search(&query, kind, limit, offset, &filters),
+        "search_soundcloud",
+        |page| {
+            let count = match &page.results {
+                SearchItems::Tracks(tracks) => tracks.len(),
+                SearchItems::Playlists(playlists) => playlists.len(),
+                SearchItems::Users(users) => users.len(),
+            };
+            format!("got {} results for '{}'", count, query)
+        },
+    )
     .await
 }
@@ -6,16 +6,22 @@ use std::sync::Arc;
 
 use commands::{
     cancel_download_queue, check_auth_state, check_for_updates, check_write_permission,
-    complete_oauth, download_track_full, get_default_download_path, get_playlist_info,
-    get_track_info, install_update, respond_to_auth_choice, sign_out, start_download_queue,
-    start_oauth, test_ffmpeg, test_ytdlp, validate_download_path, validate_soundcloud_url,
+    complete_oauth, download_track_full, export_dj_collection, get_collection_info,
+    get_default_download_path, get_my_likes, get_my_playlists, get_my_uploads, get_playlist_info,
+    get_profile_info, get_track_info, install_update, normalize_and_extract_urls,
+    preview_title_rules, read_tags, resolve_share_link, respond_to_auth_choice, retag_library,
+    search_soundcloud, set_proxy, sign_out, start_download_queue, start_oauth, test_ffmpeg,
+    test_proxy, test_ytdlp, validate_download_path, validate_soundcloud_url, write_tags,
     OAuthState,
 };
+use services::artwork::ArtworkCache;
 use services::auth_choice::AuthChoiceState;
 use services::cancellation::CancellationState;
 use services::deep_link::handle_deep_link;
+use services::http::HttpConfig;
+use services::metadata_cache::MetadataCache;
 use tauri::menu::{Menu, MenuItem, PredefinedMenuItem, Submenu};
-use tauri::Emitter;
+use tauri::{Emitter, Manager};
 use tauri_plugin_deep_link::DeepLinkExt;
 
 #[cfg(debug_assertions)]
@@ -30,14 +36,29 @@ pub fn run() {
         check_auth_state,
         sign_out,
         validate_soundcloud_url,
+        normalize_and_extract_urls,
+        resolve_share_link,
         get_playlist_info,
         get_track_info,
+        get_profile_info,
+        get_collection_info,
+        get_my_likes,
+        get_my_playlists,
+        get_my_uploads,
+        search_soundcloud,
         test_ytdlp,
         test_ffmpeg,
+        set_proxy,
+        test_proxy,
         download_track_full,
         start_download_queue,
         cancel_download_queue,
         respond_to_auth_choice,
+        preview_title_rules,
+        retag_library,
+        read_tags,
+        write_tags,
+        export_dj_collection,
         check_write_permission,
         get_default_download_path,
         validate_download_path,
@@ -56,6 +77,9 @@ pub fn run() {
         )
         .expect("Failed to export typescript bindings");
 
+    // The HTTP client is process-wide; services reach it via `http::client()`
+    services::http::init(HttpConfig::default());
+
     tauri::Builder::default()
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_deep_link::init())
@@ -70,6 +94,38 @@ pub fn run() {
         .setup(move |app| {
             builder.mount_events(app);
 
+            // Restore the saved proxy before anything goes over the network
+            match services::storage::load_proxy() {
+                Ok(Some(proxy)) => {
+                    log::info!("[proxy] Restoring {:?} proxy", proxy.mode);
+                    if let Err(e) = services::http::client().set_proxy(proxy) {
+                        log::warn!("[proxy] Failed to restore proxy: {}", e);
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => log::warn!("[proxy] Failed to load proxy settings: {}", e),
+            }
+
+            // Tell the UI when SoundCloud rate limits API requests
+            let handle = app.handle().clone();
+            services::rate_limit::api_limiter().set_listener(move |status| {
+                let _ = handle.emit("rate-limit-status", status);
+            });
+
+            // Artwork cache persisted under the app cache directory
+            let artwork_dir = app.path().app_cache_dir().ok().map(|d| d.join("artwork"));
+            app.manage(Arc::new(ArtworkCache::new(artwork_dir)));
+
+            // Track and playlist metadata cached under the app cache directory
+            let metadata_dir = app.path().app_cache_dir().ok().map(|d| d.join("metadata"));
+            let metadata_cache = Arc::new(MetadataCache::new(metadata_dir));
+            let pruned_cache = Arc::clone(&metadata_cache);
+            tauri::async_runtime::spawn_blocking(move || {
+                let removed = pruned_cache.prune_expired();
+                log::info!("[metadata-cache] Pruned {} expired entries", removed);
+            });
+            app.manage(metadata_cache);
+
             // Create settings menu item with Cmd+, shortcut
             let settings_item =
                 MenuItem::with_id(app, "settings", "Settings...", true, Some("CmdOrCtrl+,"))?;
@@ -25,12 +25,43 @@ impl<T: HasErrorCode + std::fmt::Display> From<T> for ErrorResponse {
 pub enum FfmpegError {
     #[error("FFmpeg binary not found")]
     BinaryNotFound,
+
+    #[error("Failed to split audio: {0}")]
+    SplitFailed(String),
 }
 
 impl HasErrorCode for FfmpegError {
     fn code(&self) -> &'static str {
         match self {
             FfmpegError::BinaryNotFound => "BINARY_NOT_FOUND",
+            FfmpegError::SplitFailed(_) => "SPLIT_FAILED",
+        }
+    }
+}
+
+/// Errors of the proxy configuration and its reachability check.
+#[derive(Debug, Error, Serialize)]
+pub enum ProxyError {
+    #[error("Invalid proxy: {0}")]
+    InvalidProxy(String),
+
+    #[error("Could not reach the proxy: {0}")]
+    Unreachable(String),
+
+    #[error("The proxy rejected the username or password")]
+    AuthFailed,
+
+    #[error("Request through the proxy failed: {0}")]
+    RequestFailed(String),
+}
+
+impl HasErrorCode for ProxyError {
+    fn code(&self) -> &'static str {
+        match self {
+            ProxyError::InvalidProxy(_) => "INVALID_PROXY",
+            ProxyError::Unreachable(_) => "PROXY_UNREACHABLE",
+            ProxyError::AuthFailed => "PROXY_AUTH_FAILED",
+            ProxyError::RequestFailed(_) => "PROXY_REQUEST_FAILED",
         }
     }
 }
@@ -42,6 +73,12 @@ pub enum MetadataError {
 
     #[error("Failed to download artwork: {0}")]
     ArtworkFailed(String),
+
+    #[error("Failed to read metadata: {0}")]
+    ReadFailed(String),
+
+    #[error("Unsupported file format: {0}")]
+    UnsupportedFormat(String),
 }
 
 impl HasErrorCode for MetadataError {
@@ -49,6 +86,8 @@ impl HasErrorCode for MetadataError {
         match self {
             MetadataError::WriteFailed(_) => "METADATA_WRITE_FAILED",
             MetadataError::ArtworkFailed(_) => "ARTWORK_DOWNLOAD_FAILED",
+            MetadataError::ReadFailed(_) => "METADATA_READ_FAILED",
+            MetadataError::UnsupportedFormat(_) => "UNSUPPORTED_FORMAT",
         }
     }
 }
@@ -338,6 +377,14 @@ mod tests {
         assert_eq!(response.code, "BINARY_NOT_FOUND");
     }
 
+    #[test]
+    fn test_error_response_from_ffmpeg_split_failed() {
+        let err = FfmpegError::SplitFailed("invalid data".to_string());
+        let response: ErrorResponse = err.into();
+        assert_eq!(response.code, "SPLIT_FAILED");
+        assert_eq!(response.message, "Failed to split audio: invalid data");
+    }
+
     #[test]
     fn test_pipeline_download_error_message() {
         let err = PipelineError::Download(YtDlpError::DownloadFailed("test error".to_string()));
@@ -382,6 +429,14 @@ mod tests {
             MetadataError::ArtworkFailed("test".to_string()).code(),
             "ARTWORK_DOWNLOAD_FAILED"
         );
+        assert_eq!(
+            MetadataError::ReadFailed("test".to_string()).code(),
+            "METADATA_READ_FAILED"
+        );
+        assert_eq!(
+            MetadataError::UnsupportedFormat("flac".to_string()).code(),
+            "UNSUPPORTED_FORMAT"
+        );
     }
 
     #[test]
@@ -399,4 +454,32 @@ mod tests {
         assert_eq!(response.code, "ARTWORK_DOWNLOAD_FAILED");
         assert!(response.message.contains("Failed to download artwork"));
     }
+
+    #[test]
+    fn test_proxy_error_codes() {
+        assert_eq!(
+            ProxyError::InvalidProxy("test".to_string()).code(),
+            "INVALID_PROXY"
+        );
+        assert_eq!(
+            ProxyError::Unreachable("test".to_string()).code(),
+            "PROXY_UNREACHABLE"
+        );
+        assert_eq!(ProxyError::AuthFailed.code(), "PROXY_AUTH_FAILED");
+        assert_eq!(
+            ProxyError::RequestFailed("test".to_string()).code(),
+            "PROXY_REQUEST_FAILED"
+        );
+    }
+
+    #[test]
+    fn test_error_response_from_proxy_unreachable() {
+        let err = ProxyError::Unreachable("connection refused".to_string());
+        let response: ErrorResponse = err.into();
+        assert_eq!(response.code, "PROXY_UNREACHABLE");
+        assert_eq!(
+            response.message,
+            "Could not reach the proxy: connection refused"
+        );
+    }
 }
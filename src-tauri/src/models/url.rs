@@ -6,6 +6,51 @@ use specta::Type;
 pub enum UrlType {
     Playlist,
     Track,
+    /// An artist profile; downloads every track of the user.
+    Profile,
+    /// A tab of a profile listing tracks or playlists.
+    Collection(CollectionKind),
+}
+
+/// Profile tabs that can be downloaded as a whole.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum CollectionKind {
+    /// `/likes`: tracks the user liked.
+    Likes,
+    /// `/reposts`: tracks the user reposted.
+    Reposts,
+    /// `/popular-tracks`: the user's tracks, most played first.
+    PopularTracks,
+    /// `/albums`: albums, EPs and singles, one playlist each.
+    Albums,
+    /// `/sets`: playlists, one playlist each.
+    Sets,
+}
+
+impl CollectionKind {
+    /// Parses the profile tab from its URL path segment.
+    pub fn from_path_segment(segment: &str) -> Option<Self> {
+        match segment {
+            "likes" => Some(Self::Likes),
+            "reposts" => Some(Self::Reposts),
+            "popular-tracks" => Some(Self::PopularTracks),
+            "albums" => Some(Self::Albums),
+            "sets" => Some(Self::Sets),
+            _ => None,
+        }
+    }
+
+    /// URL path segment of the tab.
+    pub fn path_segment(self) -> &'static str {
+        match self {
+            Self::Likes => "likes",
+            Self::Reposts => "reposts",
+            Self::PopularTracks => "popular-tracks",
+            Self::Albums => "albums",
+            Self::Sets => "sets",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
@@ -22,3 +67,11 @@ pub struct ValidationError {
     pub message: String,
     pub hint: Option<String>,
 }
+
+/// A SoundCloud URL found in pasted text, normalized, with its validation.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct ExtractedUrl {
+    pub url: String,
+    #[serde(flatten)]
+    pub result: ValidationResult,
+}
@@ -0,0 +1,567 @@
+//! Cover art download, format detection and caching.
+//!
+//! SoundCloud serves artwork in several resolutions selected by a URL suffix
+//! (`-large`, `-t500x500`, `-original`, ...) and in either JPEG or PNG.
+//! Downloaded images are cached by URL so that a playlist cover shared by
+//! every track is only fetched once per queue.
+
+use std::collections::VecDeque;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use specta::Type;
+
+use crate::models::error::MetadataError;
+use crate::services::http;
+
+/// JPEG quality used when re-encoding resized or converted artwork.
+const JPEG_QUALITY: u8 = 90;
+
+/// Images kept in memory; the least recently used one is dropped first.
+/// Originals can weigh several MB, so only a few are kept.
+const MAX_MEMORY_ENTRIES: usize = 16;
+
+/// Size of the on-disk cache above which the oldest entries are removed.
+const MAX_DISK_BYTES: u64 = 200 * 1024 * 1024;
+
+static SIZE_SUFFIX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"-(?:large|original|crop|t\d+x\d+|badge|small|tiny|mini)(\.\w+)?$")
+        .expect("artwork size regex is valid")
+});
+
+/// Resolution of the artwork embedded in downloaded files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, Type)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ArtworkSize {
+    /// 500x500, a good balance between quality and file size.
+    #[default]
+    T500x500,
+    /// The image as uploaded (may be several megabytes).
+    Original,
+    /// The original image scaled down to fit within `px` x `px`.
+    Resize { px: u32 },
+}
+
+impl ArtworkSize {
+    fn url_suffix(&self) -> &'static str {
+        match self {
+            ArtworkSize::T500x500 => "t500x500",
+            ArtworkSize::Original | ArtworkSize::Resize { .. } => "original",
+        }
+    }
+
+    fn cache_key(&self) -> String {
+        match self {
+            ArtworkSize::T500x500 => "t500x500".to_string(),
+            ArtworkSize::Original => "original".to_string(),
+            ArtworkSize::Resize { px } => format!("resize-{}", px),
+        }
+    }
+}
+
+/// File name used when saving the cover next to the downloaded tracks.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum FolderArtName {
+    /// `cover.jpg`
+    Cover,
+    /// `folder.jpg`
+    Folder,
+}
+
+impl FolderArtName {
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            FolderArtName::Cover => "cover.jpg",
+            FolderArtName::Folder => "folder.jpg",
+        }
+    }
+}
+
+/// Downloaded artwork with its detected MIME type.
+#[derive(Debug, Clone)]
+pub struct Artwork {
+    pub data: Vec<u8>,
+    pub mime_type: &'static str,
+}
+
+impl Artwork {
+    /// Wraps raw image bytes, failing if the format is not recognised.
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self, MetadataError> {
+        let mime_type = detect_mime_type(&data)
+            .ok_or_else(|| MetadataError::ArtworkFailed("Unsupported image format".to_string()))?;
+        Ok(Self { data, mime_type })
+    }
+}
+
+/// Detects the image MIME type from its magic bytes.
+pub fn detect_mime_type(data: &[u8]) -> Option<&'static str> {
+    if data.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("image/jpeg")
+    } else if data.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]) {
+        Some("image/png")
+    } else if data.starts_with(b"GIF87a") || data.starts_with(b"GIF89a") {
+        Some("image/gif")
+    } else if data.len() >= 12 && &data[..4] == b"RIFF" && &data[8..12] == b"WEBP" {
+        Some("image/webp")
+    } else {
+        None
+    }
+}
+
+/// Rewrites a SoundCloud artwork URL to the requested resolution.
+///
+/// SoundCloud artwork URLs support resolution suffixes:
+/// - `-large`: 100x100 (default)
+/// - `-t300x300`: 300x300
+/// - `-t500x500`: 500x500
+/// - `-original`: Original size (may be huge)
+///
+/// URLs without a recognised suffix are returned unchanged.
+pub fn artwork_url_for_size(url: &str, size: ArtworkSize) -> String {
+    SIZE_SUFFIX
+        .replace(url, |caps: &regex::Captures| {
+            let ext = caps.get(1).map(|m| m.as_str()).unwrap_or("");
+            format!("-{}{}", size.url_suffix(), ext)
+        })
+        .into_owned()
+}
+
+/// Scales an image down to fit within `max_px` x `max_px`, re-encoding as JPEG.
+///
+/// Images already within bounds are returned unchanged.
+fn resize_artwork(artwork: Artwork, max_px: u32) -> Result<Artwork, MetadataError> {
+    let img = image::load_from_memory(&artwork.data)
+        .map_err(|e| MetadataError::ArtworkFailed(e.to_string()))?;
+
+    if img.width() <= max_px && img.height() <= max_px {
+        return Ok(artwork);
+    }
+
+    let resized = img.resize(max_px, max_px, FilterType::Lanczos3);
+    encode_jpeg(&resized)
+}
+
+/// Converts artwork to JPEG if it is in another format.
+fn to_jpeg(artwork: &Artwork) -> Result<Vec<u8>, MetadataError> {
+    if artwork.mime_type == "image/jpeg" {
+        return Ok(artwork.data.clone());
+    }
+
+    let img = image::load_from_memory(&artwork.data)
+        .map_err(|e| MetadataError::ArtworkFailed(e.to_string()))?;
+    Ok(encode_jpeg(&img)?.data)
+}
+
+fn encode_jpeg(img: &image::DynamicImage) -> Result<Artwork, MetadataError> {
+    let mut buffer = Cursor::new(Vec::new());
+    JpegEncoder::new_with_quality(&mut buffer, JPEG_QUALITY)
+        .encode_image(&img.to_rgb8())
+        .map_err(|e| MetadataError::ArtworkFailed(e.to_string()))?;
+
+    Ok(Artwork {
+        data: buffer.into_inner(),
+        mime_type: "image/jpeg",
+    })
+}
+
+/// Downloads raw artwork bytes from a URL.
+async fn download_artwork(url: &str) -> Result<Vec<u8>, MetadataError> {
+    let http = http::client();
+    let response = http
+        .send(http.get(url))
+        .await
+        .map_err(|e| MetadataError::ArtworkFailed(e.to_string()))?;
+
+    if !response.status().is_success() {
+        return Err(MetadataError::ArtworkFailed(format!(
+            "HTTP {}",
+            response.status()
+        )));
+    }
+
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| MetadataError::ArtworkFailed(e.to_string()))?;
+
+    Ok(bytes.to_vec())
+}
+
+/// Downloads artwork from an arbitrary URL without resizing.
+pub async fn download_artwork_from_url(url: &str) -> Result<Artwork, MetadataError> {
+    Artwork::from_bytes(download_artwork(url).await?)
+}
+
+/// Downloads artwork at the requested size.
+///
+/// Not every upload has an `-original` variant, so original/resize requests
+/// fall back to 500x500 when the original cannot be fetched.
+async fn fetch_artwork(url: &str, size: ArtworkSize) -> Result<Artwork, MetadataError> {
+    let sized_url = artwork_url_for_size(url, size);
+
+    let data = match download_artwork(&sized_url).await {
+        Ok(data) => data,
+        Err(e) if size != ArtworkSize::T500x500 => {
+            log::warn!(
+                "[artwork] Failed to fetch {}: {}, falling back to 500x500",
+                sized_url,
+                e
+            );
+            download_artwork(&artwork_url_for_size(url, ArtworkSize::T500x500)).await?
+        }
+        Err(e) => return Err(e),
+    };
+
+    let artwork = Artwork::from_bytes(data)?;
+
+    match size {
+        ArtworkSize::Resize { px } if px > 0 => {
+            tokio::task::spawn_blocking(move || resize_artwork(artwork, px))
+                .await
+                .map_err(|e| MetadataError::ArtworkFailed(e.to_string()))?
+        }
+        _ => Ok(artwork),
+    }
+}
+
+/// In-memory artwork cache with an optional on-disk layer.
+///
+/// Entries are keyed by source URL and requested size. The disk layer
+/// persists across queues, so re-downloading a playlist does not fetch
+/// its covers again. Both layers are bounded: the memory layer keeps the
+/// [`MAX_MEMORY_ENTRIES`] most recently used images, and the disk layer is
+/// trimmed to [`MAX_DISK_BYTES`] after each write.
+#[derive(Default)]
+pub struct ArtworkCache {
+    /// Most recently used entries last.
+    memory: Mutex<VecDeque<(String, Arc<Artwork>)>>,
+    disk_dir: Option<PathBuf>,
+}
+
+impl ArtworkCache {
+    pub fn new(disk_dir: Option<PathBuf>) -> Self {
+        Self {
+            memory: Mutex::new(VecDeque::new()),
+            disk_dir,
+        }
+    }
+
+    /// Returns the artwork for a URL, downloading it on a cache miss.
+    pub async fn get(&self, url: &str, size: ArtworkSize) -> Result<Arc<Artwork>, MetadataError> {
+        let key = format!("{}|{}", size.cache_key(), url);
+
+        if let Some(artwork) = self.memory_get(&key) {
+            return Ok(artwork);
+        }
+
+        let path = self.disk_path(&key);
+        let cached = match path.clone() {
+            Some(path) => tokio::task::spawn_blocking(move || read_from_disk(&path))
+                .await
+                .ok()
+                .flatten(),
+            None => None,
+        };
+
+        let artwork = match cached {
+            Some(artwork) => Arc::new(artwork),
+            None => {
+                let artwork = Arc::new(fetch_artwork(url, size).await?);
+                if let Some(path) = path {
+                    let data = artwork.clone();
+                    let _ = tokio::task::spawn_blocking(move || write_to_disk(&path, &data)).await;
+                }
+                artwork
+            }
+        };
+
+        self.memory_put(key, artwork.clone());
+        Ok(artwork)
+    }
+
+    fn memory_get(&self, key: &str) -> Option<Arc<Artwork>> {
+        let mut memory = self.memory.lock().unwrap();
+        let index = memory.iter().position(|(k, _)| k == key)?;
+        let entry = memory.remove(index)?;
+        let artwork = entry.1.clone();
+        memory.push_back(entry);
+        Some(artwork)
+    }
+
+    fn memory_put(&self, key: String, artwork: Arc<Artwork>) {
+        let mut memory = self.memory.lock().unwrap();
+        memory.retain(|(k, _)| *k != key);
+        memory.push_back((key, artwork));
+        while memory.len() > MAX_MEMORY_ENTRIES {
+            memory.pop_front();
+        }
+    }
+
+    fn disk_path(&self, key: &str) -> Option<PathBuf> {
+        let dir = self.disk_dir.as_ref()?;
+        let hash = Sha256::digest(key.as_bytes());
+        let name: String = hash.iter().map(|b| format!("{:02x}", b)).collect();
+        Some(dir.join(name))
+    }
+}
+
+fn read_from_disk(path: &Path) -> Option<Artwork> {
+    let data = std::fs::read(path).ok()?;
+    Artwork::from_bytes(data).ok()
+}
+
+/// Writes a cache entry and trims the cache directory. Blocking.
+fn write_to_disk(path: &Path, artwork: &Artwork) {
+    if let Some(parent) = path.parent() {
+        if let Err(e) = std::fs::create_dir_all(parent) {
+            log::warn!("[artwork] Failed to create cache dir: {}", e);
+            return;
+        }
+    }
+
+    if let Err(e) = std::fs::write(path, &artwork.data) {
+        log::warn!("[artwork] Failed to write cache entry {:?}: {}", path, e);
+        return;
+    }
+
+    if let Some(dir) = path.parent() {
+        prune_disk_cache(dir, MAX_DISK_BYTES);
+    }
+}
+
+/// Removes the least recently written files of `dir` until the rest fit
+/// in `max_bytes`.
+fn prune_disk_cache(dir: &Path, max_bytes: u64) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+
+    let mut files: Vec<(std::time::SystemTime, u64, PathBuf)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            let modified = metadata.modified().ok()?;
+            metadata
+                .is_file()
+                .then(|| (modified, metadata.len(), entry.path()))
+        })
+        .collect();
+
+    let mut total: u64 = files.iter().map(|(_, len, _)| len).sum();
+    if total <= max_bytes {
+        return;
+    }
+
+    files.sort_by_key(|(modified, _, _)| *modified);
+    for (_, len, path) in files {
+        if total <= max_bytes {
+            break;
+        }
+        match std::fs::remove_file(&path) {
+            Ok(()) => total -= len,
+            Err(e) => log::warn!("[artwork] Failed to prune cache entry {:?}: {}", path, e),
+        }
+    }
+}
+
+/// Saves artwork as `cover.jpg`/`folder.jpg` in the given directory.
+///
+/// Non-JPEG artwork is converted so the file extension matches its content.
+pub fn save_folder_art(
+    dir: &Path,
+    artwork: &Artwork,
+    name: FolderArtName,
+) -> Result<PathBuf, MetadataError> {
+    let path = dir.join(name.file_name());
+    let data = to_jpeg(artwork)?;
+
+    std::fs::create_dir_all(dir).map_err(|e| MetadataError::WriteFailed(e.to_string()))?;
+    std::fs::write(&path, data).map_err(|e| MetadataError::WriteFailed(e.to_string()))?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn png_bytes(width: u32, height: u32) -> Vec<u8> {
+        let img = image::DynamicImage::new_rgb8(width, height);
+        let mut buffer = Cursor::new(Vec::new());
+        img.write_to(&mut buffer, image::ImageFormat::Png).unwrap();
+        buffer.into_inner()
+    }
+
+    #[test]
+    fn test_detect_mime_type_jpeg() {
+        assert_eq!(
+            detect_mime_type(&[0xFF, 0xD8, 0xFF, 0xE0, 0x00]),
+            Some("image/jpeg")
+        );
+    }
+
+    #[test]
+    fn test_detect_mime_type_png() {
+        assert_eq!(detect_mime_type(&png_bytes(1, 1)), Some("image/png"));
+    }
+
+    #[test]
+    fn test_detect_mime_type_gif_and_webp() {
+        assert_eq!(detect_mime_type(b"GIF89a...."), Some("image/gif"));
+        assert_eq!(
+            detect_mime_type(b"RIFF\0\0\0\0WEBPVP8 "),
+            Some("image/webp")
+        );
+    }
+
+    #[test]
+    fn test_detect_mime_type_unknown() {
+        assert_eq!(detect_mime_type(b"<html>"), None);
+        assert_eq!(detect_mime_type(&[]), None);
+    }
+
+    #[test]
+    fn test_artwork_url_for_size_large_to_t500() {
+        assert_eq!(
+            artwork_url_for_size(
+                "https://i1.sndcdn.com/artworks-000123-abc-large.jpg",
+                ArtworkSize::T500x500
+            ),
+            "https://i1.sndcdn.com/artworks-000123-abc-t500x500.jpg"
+        );
+    }
+
+    #[test]
+    fn test_artwork_url_for_size_original() {
+        assert_eq!(
+            artwork_url_for_size(
+                "https://i1.sndcdn.com/artworks-000123-abc-t500x500.png",
+                ArtworkSize::Original
+            ),
+            "https://i1.sndcdn.com/artworks-000123-abc-original.png"
+        );
+    }
+
+    #[test]
+    fn test_artwork_url_for_size_resize_uses_original() {
+        assert_eq!(
+            artwork_url_for_size(
+                "https://i1.sndcdn.com/avatars-000123-abc-large.jpg",
+                ArtworkSize::Resize { px: 1000 }
+            ),
+            "https://i1.sndcdn.com/avatars-000123-abc-original.jpg"
+        );
+    }
+
+    #[test]
+    fn test_artwork_url_for_size_unknown_suffix_unchanged() {
+        let url = "https://example.com/cover.jpg";
+        assert_eq!(artwork_url_for_size(url, ArtworkSize::Original), url);
+    }
+
+    #[test]
+    fn test_artwork_size_deserialize() {
+        let size: ArtworkSize = serde_json::from_str(r#"{"kind": "t500x500"}"#).unwrap();
+        assert_eq!(size, ArtworkSize::T500x500);
+        let size: ArtworkSize = serde_json::from_str(r#"{"kind": "original"}"#).unwrap();
+        assert_eq!(size, ArtworkSize::Original);
+        let size: ArtworkSize = serde_json::from_str(r#"{"kind": "resize", "px": 800}"#).unwrap();
+        assert_eq!(size, ArtworkSize::Resize { px: 800 });
+    }
+
+    #[test]
+    fn test_resize_artwork_scales_down() {
+        let artwork = Artwork::from_bytes(png_bytes(1000, 500)).unwrap();
+        let resized = resize_artwork(artwork, 200).unwrap();
+        assert_eq!(resized.mime_type, "image/jpeg");
+        let img = image::load_from_memory(&resized.data).unwrap();
+        assert_eq!((img.width(), img.height()), (200, 100));
+    }
+
+    #[test]
+    fn test_resize_artwork_keeps_small_images() {
+        let artwork = Artwork::from_bytes(png_bytes(100, 100)).unwrap();
+        let resized = resize_artwork(artwork, 200).unwrap();
+        assert_eq!(resized.mime_type, "image/png");
+    }
+
+    #[tokio::test]
+    async fn test_artwork_cache_reads_from_disk() {
+        let dir = tempdir().unwrap();
+        let cache = ArtworkCache::new(Some(dir.path().to_path_buf()));
+        let url = "https://example.invalid/artworks-1-large.jpg";
+        let key = format!("{}|{}", ArtworkSize::T500x500.cache_key(), url);
+
+        let artwork = Artwork::from_bytes(png_bytes(2, 2)).unwrap();
+        write_to_disk(&cache.disk_path(&key).unwrap(), &artwork);
+
+        // Served from disk without touching the (unresolvable) network
+        let cached = cache.get(url, ArtworkSize::T500x500).await.unwrap();
+        assert_eq!(cached.mime_type, "image/png");
+        assert_eq!(cached.data, artwork.data);
+    }
+
+    #[test]
+    fn test_artwork_cache_without_disk_dir() {
+        let cache = ArtworkCache::default();
+        assert!(cache.disk_path("key").is_none());
+    }
+
+    #[test]
+    fn test_artwork_cache_memory_is_bounded() {
+        let cache = ArtworkCache::default();
+        let artwork = Arc::new(Artwork::from_bytes(png_bytes(1, 1)).unwrap());
+
+        for i in 0..MAX_MEMORY_ENTRIES {
+            cache.memory_put(format!("key{}", i), artwork.clone());
+        }
+        // Using the oldest entry keeps it when a new one pushes another out
+        assert!(cache.memory_get("key0").is_some());
+        cache.memory_put("new".to_string(), artwork);
+
+        assert_eq!(cache.memory.lock().unwrap().len(), MAX_MEMORY_ENTRIES);
+        assert!(cache.memory_get("key0").is_some());
+        assert!(cache.memory_get("key1").is_none());
+        assert!(cache.memory_get("new").is_some());
+    }
+
+    #[test]
+    fn test_prune_disk_cache_removes_oldest_files() {
+        let dir = tempdir().unwrap();
+        let now = std::time::SystemTime::now();
+        for (i, name) in ["old", "mid", "new"].iter().enumerate() {
+            let path = dir.path().join(name);
+            std::fs::write(&path, [0u8; 10]).unwrap();
+            let file = std::fs::File::options().write(true).open(&path).unwrap();
+            file.set_modified(now - std::time::Duration::from_secs(60 * (3 - i as u64)))
+                .unwrap();
+        }
+
+        prune_disk_cache(dir.path(), 20);
+
+        assert!(!dir.path().join("old").exists());
+        assert!(dir.path().join("mid").exists());
+        assert!(dir.path().join("new").exists());
+    }
+
+    #[test]
+    fn test_save_folder_art_converts_to_jpeg() {
+        let dir = tempdir().unwrap();
+        let artwork = Artwork::from_bytes(png_bytes(4, 4)).unwrap();
+
+        let path = save_folder_art(dir.path(), &artwork, FolderArtName::Folder).unwrap();
+
+        assert_eq!(path, dir.path().join("folder.jpg"));
+        let data = std::fs::read(path).unwrap();
+        assert_eq!(detect_mime_type(&data), Some("image/jpeg"));
+    }
+}
@@ -0,0 +1,651 @@
+//! Rekordbox XML and Traktor NML export of downloaded playlists.
+//!
+//! Track details are read from the tags of the downloaded files, so the
+//! export reflects any later edits. Neither format can reference artwork;
+//! both applications show the cover embedded in the file instead.
+
+use id3::TagLike;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use crate::models::error::MetadataError;
+use crate::services::tag_editor::read_tag;
+
+/// A playlist to export: its name and the files in playlist order.
+#[derive(Debug, Clone, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DjPlaylist {
+    pub title: String,
+    pub paths: Vec<String>,
+}
+
+/// Collection file format.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum DjCollectionFormat {
+    /// Rekordbox XML (`File > Import > rekordbox xml`).
+    #[default]
+    Rekordbox,
+    /// Traktor NML collection.
+    Traktor,
+}
+
+/// Result of an export.
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct DjExportResult {
+    pub path: String,
+    pub tracks: u32,
+    pub playlists: u32,
+    /// Files that could not be read and were left out.
+    pub skipped: Vec<String>,
+}
+
+/// A file of the collection with the details read from its tags.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CollectionTrack {
+    pub path: PathBuf,
+    pub title: String,
+    pub artist: String,
+    pub album: Option<String>,
+    pub genre: Option<String>,
+    pub label: Option<String>,
+    pub bpm: Option<f64>,
+    pub year: Option<i32>,
+    pub track_number: Option<u32>,
+    /// Length in seconds, from the TLEN frame.
+    pub duration_secs: Option<u64>,
+    pub size_bytes: u64,
+}
+
+impl CollectionTrack {
+    pub fn from_file(path: &Path) -> Result<Self, MetadataError> {
+        let size_bytes = std::fs::metadata(path)
+            .map_err(|e| MetadataError::ReadFailed(format!("{}: {}", path.display(), e)))?
+            .len();
+        let tag = read_tag(path)?;
+        let text = |id: &str| {
+            tag.get(id)
+                .and_then(|f| f.content().text())
+                .map(|t| t.trim().to_string())
+                .filter(|t| !t.is_empty())
+        };
+
+        let file_stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            title: tag.title().map(str::to_string).unwrap_or(file_stem),
+            artist: tag.artist().map(str::to_string).unwrap_or_default(),
+            album: tag.album().map(str::to_string),
+            genre: tag.genre().map(str::to_string),
+            label: text("TPUB"),
+            bpm: text("TBPM").and_then(|b| b.parse().ok()),
+            year: tag.date_recorded().map(|d| d.year),
+            track_number: tag.track(),
+            duration_secs: text("TLEN")
+                .and_then(|ms| ms.parse::<u64>().ok())
+                .map(|ms| ms / 1000),
+            size_bytes,
+        })
+    }
+
+    fn kind(&self) -> &'static str {
+        match self
+            .path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("aif" | "aiff") => "AIFF File",
+            Some("wav") => "WAV File",
+            _ => "MP3 File",
+        }
+    }
+}
+
+/// Tracks and playlists to export; playlists refer to tracks by index.
+#[derive(Debug, Default)]
+pub struct DjCollection {
+    pub tracks: Vec<CollectionTrack>,
+    pub playlists: Vec<(String, Vec<usize>)>,
+}
+
+impl DjCollection {
+    /// Reads every file of the playlists. A file shared by several
+    /// playlists appears once in the collection; unreadable files are
+    /// skipped and returned.
+    pub fn from_playlists(playlists: &[DjPlaylist]) -> (Self, Vec<String>) {
+        let mut collection = DjCollection::default();
+        let mut indices: HashMap<String, usize> = HashMap::new();
+        let mut skipped = Vec::new();
+
+        for playlist in playlists {
+            let mut entries = Vec::with_capacity(playlist.paths.len());
+
+            for path in &playlist.paths {
+                if let Some(&index) = indices.get(path) {
+                    entries.push(index);
+                    continue;
+                }
+
+                match CollectionTrack::from_file(Path::new(path)) {
+                    Ok(track) => {
+                        let index = collection.tracks.len();
+                        collection.tracks.push(track);
+                        indices.insert(path.clone(), index);
+                        entries.push(index);
+                    }
+                    Err(e) => {
+                        log::warn!("[collection] Skipping {}: {}", path, e);
+                        if !skipped.contains(path) {
+                            skipped.push(path.clone());
+                        }
+                    }
+                }
+            }
+
+            collection.playlists.push((playlist.title.clone(), entries));
+        }
+
+        (collection, skipped)
+    }
+}
+
+/// Escapes text for use in an XML attribute.
+fn xml_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c if c.is_control() && c != '\t' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Splits a path into an optional Windows drive (`C:`) and its `/`-separated
+/// components.
+fn path_parts(path: &Path) -> (Option<String>, Vec<String>) {
+    let normalized = path.to_string_lossy().replace('\\', "/");
+    let (drive, rest) = match normalized.as_bytes() {
+        [letter, b':', ..] if letter.is_ascii_alphabetic() => {
+            (Some(normalized[..2].to_string()), &normalized[2..])
+        }
+        _ => (None, normalized.as_str()),
+    };
+
+    let parts = rest
+        .split('/')
+        .filter(|p| !p.is_empty())
+        .map(str::to_string)
+        .collect();
+    (drive, parts)
+}
+
+/// Rekordbox location URL: `file://localhost/` followed by the
+/// percent-encoded absolute path.
+pub fn rekordbox_location(path: &Path) -> String {
+    let (drive, parts) = path_parts(path);
+    let mut location = "file://localhost".to_string();
+
+    if let Some(drive) = drive {
+        location.push('/');
+        location.push_str(&drive);
+    }
+    for part in parts {
+        location.push('/');
+        location.push_str(&urlencoding::encode(&part));
+    }
+
+    location
+}
+
+/// Volume name Traktor uses for the macOS startup disk.
+const TRAKTOR_SYSTEM_VOLUME: &str = "Macintosh HD";
+
+/// Traktor location: volume, `/:`-separated directory and file name.
+///
+/// The volume is the drive letter on Windows. Elsewhere it is the volume
+/// name for paths under `/Volumes/<name>/`, and the startup disk otherwise;
+/// Traktor does not find files whose volume is empty.
+pub fn traktor_location(path: &Path) -> (String, String, String) {
+    let (drive, mut parts) = path_parts(path);
+    let file = parts.pop().unwrap_or_default();
+
+    let volume = match drive {
+        Some(drive) => drive,
+        None if parts.len() >= 2 && parts[0] == "Volumes" => {
+            let name = parts[1].clone();
+            parts.drain(..2);
+            name
+        }
+        None => TRAKTOR_SYSTEM_VOLUME.to_string(),
+    };
+
+    let mut dir = String::from("/:");
+    for part in parts {
+        dir.push_str(&part);
+        dir.push_str("/:");
+    }
+
+    (volume, dir, file)
+}
+
+/// Renders a Rekordbox XML collection.
+pub fn rekordbox_xml(collection: &DjCollection) -> String {
+    let mut xml = String::new();
+    let _ = writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = writeln!(xml, r#"<DJ_PLAYLISTS Version="1.0.0">"#);
+    let _ = writeln!(
+        xml,
+        r#"  <PRODUCT Name="SC Downloader" Version="{}" Company=""/>"#,
+        env!("CARGO_PKG_VERSION")
+    );
+    let _ = writeln!(
+        xml,
+        r#"  <COLLECTION Entries="{}">"#,
+        collection.tracks.len()
+    );
+
+    for (i, track) in collection.tracks.iter().enumerate() {
+        let mut attrs = vec![
+            ("TrackID", (i + 1).to_string()),
+            ("Name", track.title.clone()),
+            ("Artist", track.artist.clone()),
+            ("Kind", track.kind().to_string()),
+            ("Size", track.size_bytes.to_string()),
+        ];
+        let optional = [
+            ("Album", track.album.clone()),
+            ("Genre", track.genre.clone()),
+            ("Label", track.label.clone()),
+            ("TotalTime", track.duration_secs.map(|d| d.to_string())),
+            ("TrackNumber", track.track_number.map(|n| n.to_string())),
+            ("Year", track.year.map(|y| y.to_string())),
+            ("AverageBpm", track.bpm.map(|b| format!("{:.2}", b))),
+        ];
+        attrs.extend(
+            optional
+                .into_iter()
+                .filter_map(|(name, value)| value.map(|v| (name, v))),
+        );
+        attrs.push(("Location", rekordbox_location(&track.path)));
+
+        let attrs: Vec<String> = attrs
+            .iter()
+            .map(|(name, value)| format!(r#"{}="{}""#, name, xml_escape(value)))
+            .collect();
+        let _ = writeln!(xml, "    <TRACK {}/>", attrs.join(" "));
+    }
+
+    let _ = writeln!(xml, "  </COLLECTION>");
+    let _ = writeln!(xml, "  <PLAYLISTS>");
+    let _ = writeln!(
+        xml,
+        r#"    <NODE Type="0" Name="ROOT" Count="{}">"#,
+        collection.playlists.len()
+    );
+
+    for (name, entries) in &collection.playlists {
+        let _ = writeln!(
+            xml,
+            r#"      <NODE Name="{}" Type="1" KeyType="0" Entries="{}">"#,
+            xml_escape(name),
+            entries.len()
+        );
+        for index in entries {
+            let _ = writeln!(xml, r#"        <TRACK Key="{}"/>"#, index + 1);
+        }
+        let _ = writeln!(xml, "      </NODE>");
+    }
+
+    let _ = writeln!(xml, "    </NODE>");
+    let _ = writeln!(xml, "  </PLAYLISTS>");
+    let _ = writeln!(xml, "</DJ_PLAYLISTS>");
+    xml
+}
+
+/// Renders a Traktor NML collection.
+pub fn traktor_nml(collection: &DjCollection) -> String {
+    let mut nml = String::new();
+    let _ = writeln!(
+        nml,
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="no" ?>"#
+    );
+    let _ = writeln!(nml, r#"<NML VERSION="19">"#);
+    let _ = writeln!(
+        nml,
+        r#"  <HEAD COMPANY="www.native-instruments.com" PROGRAM="Traktor"></HEAD>"#
+    );
+    let _ = writeln!(
+        nml,
+        r#"  <COLLECTION ENTRIES="{}">"#,
+        collection.tracks.len()
+    );
+
+    let mut keys = Vec::with_capacity(collection.tracks.len());
+    for track in &collection.tracks {
+        let (volume, dir, file) = traktor_location(&track.path);
+        keys.push(format!("{}{}{}", volume, dir, file));
+
+        let _ = writeln!(
+            nml,
+            r#"    <ENTRY TITLE="{}" ARTIST="{}">"#,
+            xml_escape(&track.title),
+            xml_escape(&track.artist)
+        );
+        let _ = writeln!(
+            nml,
+            r#"      <LOCATION DIR="{}" FILE="{}" VOLUME="{}" VOLUMEID="{}"></LOCATION>"#,
+            xml_escape(&dir),
+            xml_escape(&file),
+            xml_escape(&volume),
+            xml_escape(&volume)
+        );
+        if track.album.is_some() || track.track_number.is_some() {
+            let _ = writeln!(
+                nml,
+                r#"      <ALBUM TITLE="{}" TRACK="{}"></ALBUM>"#,
+                xml_escape(track.album.as_deref().unwrap_or_default()),
+                track
+                    .track_number
+                    .map(|n| n.to_string())
+                    .unwrap_or_default()
+            );
+        }
+
+        let mut info = vec![format!("FILESIZE=\"{}\"", track.size_bytes / 1024)];
+        if let Some(genre) = &track.genre {
+            info.push(format!("GENRE=\"{}\"", xml_escape(genre)));
+        }
+        if let Some(label) = &track.label {
+            info.push(format!("LABEL=\"{}\"", xml_escape(label)));
+        }
+        if let Some(duration) = track.duration_secs {
+            info.push(format!("PLAYTIME=\"{}\"", duration));
+        }
+        if let Some(year) = track.year {
+            info.push(format!("RELEASE_DATE=\"{}/1/1\"", year));
+        }
+        let _ = writeln!(nml, "      <INFO {}></INFO>", info.join(" "));
+
+        if let Some(bpm) = track.bpm {
+            let _ = writeln!(
+                nml,
+                r#"      <TEMPO BPM="{:.6}" BPM_QUALITY="100.000000"></TEMPO>"#,
+                bpm
+            );
+        }
+        let _ = writeln!(nml, "    </ENTRY>");
+    }
+
+    let _ = writeln!(nml, "  </COLLECTION>");
+    let _ = writeln!(nml, "  <PLAYLISTS>");
+    let _ = writeln!(nml, r#"    <NODE TYPE="FOLDER" NAME="$ROOT">"#);
+    let _ = writeln!(
+        nml,
+        r#"      <SUBNODES COUNT="{}">"#,
+        collection.playlists.len()
+    );
+
+    for (name, entries) in &collection.playlists {
+        let _ = writeln!(
+            nml,
+            r#"        <NODE TYPE="PLAYLIST" NAME="{}">"#,
+            xml_escape(name)
+        );
+        let _ = writeln!(
+            nml,
+            r#"          <PLAYLIST ENTRIES="{}" TYPE="LIST" UUID="{}">"#,
+            entries.len(),
+            uuid::Uuid::new_v4().simple()
+        );
+        for index in entries {
+            let _ = writeln!(
+                nml,
+                r#"            <ENTRY><PRIMARYKEY TYPE="TRACK" KEY="{}"></PRIMARYKEY></ENTRY>"#,
+                xml_escape(&keys[*index])
+            );
+        }
+        let _ = writeln!(nml, "          </PLAYLIST>");
+        let _ = writeln!(nml, "        </NODE>");
+    }
+
+    let _ = writeln!(nml, "      </SUBNODES>");
+    let _ = writeln!(nml, "    </NODE>");
+    let _ = writeln!(nml, "  </PLAYLISTS>");
+    let _ = writeln!(nml, "</NML>");
+    nml
+}
+
+/// Exports playlists to a collection file.
+pub fn export_collection(
+    playlists: &[DjPlaylist],
+    output_path: &Path,
+    format: DjCollectionFormat,
+) -> Result<DjExportResult, String> {
+    let (collection, skipped) = DjCollection::from_playlists(playlists);
+
+    let content = match format {
+        DjCollectionFormat::Rekordbox => rekordbox_xml(&collection),
+        DjCollectionFormat::Traktor => traktor_nml(&collection),
+    };
+
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(output_path, content).map_err(|e| e.to_string())?;
+
+    log::info!(
+        "[collection] Exported {} tracks in {} playlists to {:?}",
+        collection.tracks.len(),
+        collection.playlists.len(),
+        output_path
+    );
+
+    Ok(DjExportResult {
+        path: output_path.to_string_lossy().to_string(),
+        tracks: collection.tracks.len() as u32,
+        playlists: collection.playlists.len() as u32,
+        skipped,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use id3::{Tag, Version};
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn create_track(dir: &Path, name: &str, title: &str, bpm: Option<&str>) -> String {
+        let path = dir.join(name);
+        fs::write(&path, [0xFF, 0xFB, 0x90, 0x00]).unwrap();
+
+        let mut tag = Tag::new();
+        tag.set_title(title);
+        tag.set_artist("Artist & Co");
+        tag.set_genre("House");
+        if let Some(bpm) = bpm {
+            tag.set_text("TBPM", bpm);
+        }
+        tag.write_to_path(&path, Version::Id3v24).unwrap();
+
+        path.to_string_lossy().to_string()
+    }
+
+    fn track(path: &str, title: &str) -> CollectionTrack {
+        CollectionTrack {
+            path: PathBuf::from(path),
+            title: title.to_string(),
+            artist: "Artist".to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_collection_track_from_file() {
+        let dir = tempdir().unwrap();
+        let path = create_track(dir.path(), "a.mp3", "Title", Some("124"));
+
+        let track = CollectionTrack::from_file(Path::new(&path)).unwrap();
+        assert_eq!(track.title, "Title");
+        assert_eq!(track.artist, "Artist & Co");
+        assert_eq!(track.genre.as_deref(), Some("House"));
+        assert_eq!(track.bpm, Some(124.0));
+        assert!(track.size_bytes > 0);
+    }
+
+    #[test]
+    fn test_from_playlists_dedupes_and_skips() {
+        let dir = tempdir().unwrap();
+        let a = create_track(dir.path(), "a.mp3", "A", None);
+        let b = create_track(dir.path(), "b.mp3", "B", None);
+        let missing = dir.path().join("gone.mp3").to_string_lossy().to_string();
+
+        let playlists = vec![
+            DjPlaylist {
+                title: "One".to_string(),
+                paths: vec![b.clone(), a.clone(), missing.clone()],
+            },
+            DjPlaylist {
+                title: "Two".to_string(),
+                paths: vec![a.clone()],
+            },
+        ];
+
+        let (collection, skipped) = DjCollection::from_playlists(&playlists);
+        assert_eq!(collection.tracks.len(), 2);
+        assert_eq!(collection.tracks[0].title, "B");
+        assert_eq!(
+            collection.playlists,
+            vec![
+                ("One".to_string(), vec![0, 1]),
+                ("Two".to_string(), vec![1])
+            ]
+        );
+        assert_eq!(skipped, vec![missing]);
+    }
+
+    #[test]
+    fn test_rekordbox_location() {
+        assert_eq!(
+            rekordbox_location(Path::new("/Users/me/Music/My Track #1.mp3")),
+            "file://localhost/Users/me/Music/My%20Track%20%231.mp3"
+        );
+        assert_eq!(
+            rekordbox_location(Path::new(r"C:\Music\a.mp3")),
+            "file://localhost/C:/Music/a.mp3"
+        );
+    }
+
+    #[test]
+    fn test_traktor_location() {
+        assert_eq!(
+            traktor_location(Path::new("/Users/me/Music/a.mp3")),
+            (
+                "Macintosh HD".to_string(),
+                "/:Users/:me/:Music/:".to_string(),
+                "a.mp3".to_string()
+            )
+        );
+        assert_eq!(
+            traktor_location(Path::new("/Volumes/DJ Drive/Sets/a.mp3")),
+            (
+                "DJ Drive".to_string(),
+                "/:Sets/:".to_string(),
+                "a.mp3".to_string()
+            )
+        );
+        assert_eq!(
+            traktor_location(Path::new(r"D:\DJ\a.mp3")),
+            ("D:".to_string(), "/:DJ/:".to_string(), "a.mp3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_rekordbox_xml() {
+        let mut first = track("/music/a.mp3", "A & B");
+        first.bpm = Some(128.0);
+        first.genre = Some("Techno".to_string());
+        let collection = DjCollection {
+            tracks: vec![first, track("/music/b.mp3", "C")],
+            playlists: vec![("My \"Set\"".to_string(), vec![1, 0])],
+        };
+
+        let xml = rekordbox_xml(&collection);
+        assert!(xml.contains(r#"<COLLECTION Entries="2">"#));
+        assert!(xml.contains(
+            r#"<TRACK TrackID="1" Name="A &amp; B" Artist="Artist" Kind="MP3 File" Size="0" Genre="Techno" AverageBpm="128.00" Location="file://localhost/music/a.mp3"/>"#
+        ));
+        assert!(xml.contains(r#"<NODE Type="0" Name="ROOT" Count="1">"#));
+        assert!(
+            xml.contains(r#"<NODE Name="My &quot;Set&quot;" Type="1" KeyType="0" Entries="2">"#)
+        );
+
+        let second = xml.find(r#"<TRACK Key="2"/>"#).unwrap();
+        let first = xml.find(r#"<TRACK Key="1"/>"#).unwrap();
+        assert!(second < first, "playlist order must be kept");
+    }
+
+    #[test]
+    fn test_traktor_nml() {
+        let mut first = track("/music/a.mp3", "A");
+        first.bpm = Some(128.0);
+        let collection = DjCollection {
+            tracks: vec![first],
+            playlists: vec![("Set".to_string(), vec![0])],
+        };
+
+        let nml = traktor_nml(&collection);
+        assert!(nml.contains(r#"<ENTRY TITLE="A" ARTIST="Artist">"#));
+        assert!(nml.contains(
+            r#"<LOCATION DIR="/:music/:" FILE="a.mp3" VOLUME="Macintosh HD" VOLUMEID="Macintosh HD">"#
+        ));
+        assert!(nml.contains(r#"<TEMPO BPM="128.000000""#));
+        assert!(nml.contains(r#"<NODE TYPE="PLAYLIST" NAME="Set">"#));
+        assert!(nml.contains(r#"KEY="Macintosh HD/:music/:a.mp3""#));
+    }
+
+    #[test]
+    fn test_export_collection_writes_file() {
+        let dir = tempdir().unwrap();
+        let a = create_track(dir.path(), "a.mp3", "A", None);
+        let output = dir.path().join("export").join("collection.xml");
+
+        let result = export_collection(
+            &[DjPlaylist {
+                title: "Set".to_string(),
+                paths: vec![a],
+            }],
+            &output,
+            DjCollectionFormat::Rekordbox,
+        )
+        .unwrap();
+
+        assert_eq!(result.tracks, 1);
+        assert_eq!(result.playlists, 1);
+        assert!(result.skipped.is_empty());
+        assert!(fs::read_to_string(output)
+            .unwrap()
+            .contains("<DJ_PLAYLISTS"));
+    }
+
+    #[test]
+    fn test_xml_escape() {
+        assert_eq!(xml_escape("a<b>'c'\u{1}"), "a&lt;b&gt;&apos;c&apos;");
+    }
+}
@@ -1,8 +1,282 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::Path;
+use tauri_plugin_shell::ShellExt;
+
 use crate::models::error::FfmpegError;
-use crate::services::sidecar::get_sidecar_version;
+use crate::services::sidecar::{bytes_to_string, get_sidecar_version};
+
+static SILENCE_START: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"silence_start:\s*(-?[\d.]+)").expect("silence regex is valid"));
+
+static SILENCE_END: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"silence_end:\s*([\d.]+)").expect("silence regex is valid"));
+
+static DURATION: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"Duration:\s*(\d+):(\d{2}):(\d{2}(?:\.\d+)?)").expect("duration regex is valid")
+});
 
 pub async fn get_version<R: tauri::Runtime>(
     app: &tauri::AppHandle<R>,
 ) -> Result<String, FfmpegError> {
     get_sidecar_version(app, "ffmpeg", "-version", || FfmpegError::BinaryNotFound).await
 }
+
+/// A stretch of silence reported by `silencedetect`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SilenceInterval {
+    pub start_ms: u64,
+    pub end_ms: u64,
+}
+
+/// Runs ffmpeg and returns its stderr, where it writes its log.
+async fn run<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    args: Vec<String>,
+) -> Result<String, FfmpegError> {
+    let output = app
+        .shell()
+        .sidecar("ffmpeg")
+        .map_err(|_| FfmpegError::BinaryNotFound)?
+        .args(args)
+        .output()
+        .await
+        .map_err(|e| FfmpegError::SplitFailed(e.to_string()))?;
+
+    let stderr = bytes_to_string(&output.stderr);
+    if !output.status.success() {
+        let message = stderr.lines().last().unwrap_or("ffmpeg failed").trim();
+        return Err(FfmpegError::SplitFailed(message.to_string()));
+    }
+
+    Ok(stderr)
+}
+
+/// Finds silent passages with ffmpeg's `silencedetect` filter.
+pub async fn detect_silence<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    input: &Path,
+    threshold_db: f64,
+    min_silence_secs: f64,
+) -> Result<Vec<SilenceInterval>, FfmpegError> {
+    let args = vec![
+        "-hide_banner".to_string(),
+        "-nostats".to_string(),
+        "-i".to_string(),
+        input.to_string_lossy().to_string(),
+        "-af".to_string(),
+        format!(
+            "silencedetect=noise={}dB:d={}",
+            threshold_db, min_silence_secs
+        ),
+        "-f".to_string(),
+        "null".to_string(),
+        "-".to_string(),
+    ];
+
+    let stderr = run(app, args).await?;
+    Ok(parse_silencedetect(&stderr))
+}
+
+/// Parses the `silence_start`/`silence_end` lines of `silencedetect`.
+///
+/// A trailing silence without an end (the file ends silent) is dropped.
+pub fn parse_silencedetect(log: &str) -> Vec<SilenceInterval> {
+    let mut intervals = Vec::new();
+    let mut start: Option<f64> = None;
+
+    for line in log.lines() {
+        if let Some(caps) = SILENCE_START.captures(line) {
+            start = caps[1].parse().ok();
+        } else if let Some(caps) = SILENCE_END.captures(line) {
+            if let (Some(s), Ok(e)) = (start.take(), caps[1].parse::<f64>()) {
+                intervals.push(SilenceInterval {
+                    start_ms: secs_to_ms(s),
+                    end_ms: secs_to_ms(e),
+                });
+            }
+        }
+    }
+
+    intervals
+}
+
+/// Reads the duration of an audio file in milliseconds, without decoding it.
+pub async fn probe_duration<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    input: &Path,
+) -> Result<u64, FfmpegError> {
+    let args = vec![
+        "-hide_banner".to_string(),
+        "-i".to_string(),
+        input.to_string_lossy().to_string(),
+        "-t".to_string(),
+        "0".to_string(),
+        "-f".to_string(),
+        "null".to_string(),
+        "-".to_string(),
+    ];
+
+    let stderr = run(app, args).await?;
+    parse_duration(&stderr)
+        .ok_or_else(|| FfmpegError::SplitFailed("No duration in ffmpeg output".to_string()))
+}
+
+/// Parses the `Duration: HH:MM:SS.ss` line ffmpeg logs for its input.
+pub fn parse_duration(log: &str) -> Option<u64> {
+    let caps = DURATION.captures(log)?;
+    let hours: f64 = caps[1].parse().ok()?;
+    let minutes: f64 = caps[2].parse().ok()?;
+    let seconds: f64 = caps[3].parse().ok()?;
+    Some(secs_to_ms(hours * 3600.0 + minutes * 60.0 + seconds))
+}
+
+fn secs_to_ms(secs: f64) -> u64 {
+    (secs.max(0.0) * 1000.0).round() as u64
+}
+
+fn ms_to_secs(ms: u64) -> String {
+    format!("{}.{:03}", ms / 1000, ms % 1000)
+}
+
+/// Builds the arguments cutting `[start_ms, end_ms)` out of `input`.
+///
+/// Tags and chapters of the input are dropped; the piece gets its own tags
+/// afterwards.
+pub fn cut_args(
+    input: &Path,
+    output: &Path,
+    start_ms: u64,
+    end_ms: Option<u64>,
+    stream_copy: bool,
+) -> Vec<String> {
+    let mut args = vec![
+        "-y".to_string(),
+        "-hide_banner".to_string(),
+        "-loglevel".to_string(),
+        "error".to_string(),
+        "-ss".to_string(),
+        ms_to_secs(start_ms),
+    ];
+
+    if let Some(end_ms) = end_ms {
+        args.push("-t".to_string());
+        args.push(ms_to_secs(end_ms.saturating_sub(start_ms)));
+    }
+
+    args.extend([
+        "-i".to_string(),
+        input.to_string_lossy().to_string(),
+        "-map".to_string(),
+        "0:a".to_string(),
+        "-map_metadata".to_string(),
+        "-1".to_string(),
+        "-map_chapters".to_string(),
+        "-1".to_string(),
+    ]);
+
+    if stream_copy {
+        args.extend(["-c".to_string(), "copy".to_string()]);
+    } else {
+        args.extend([
+            "-c:a".to_string(),
+            "libmp3lame".to_string(),
+            "-b:a".to_string(),
+            "320k".to_string(),
+        ]);
+    }
+
+    args.push(output.to_string_lossy().to_string());
+    args
+}
+
+/// Cuts a piece out of an audio file.
+///
+/// Tries a stream copy first (fast and lossless) and re-encodes to MP3 if
+/// that fails.
+pub async fn cut_segment<R: tauri::Runtime>(
+    app: &tauri::AppHandle<R>,
+    input: &Path,
+    output: &Path,
+    start_ms: u64,
+    end_ms: Option<u64>,
+) -> Result<(), FfmpegError> {
+    match run(app, cut_args(input, output, start_ms, end_ms, true)).await {
+        Ok(_) => Ok(()),
+        Err(FfmpegError::BinaryNotFound) => Err(FfmpegError::BinaryNotFound),
+        Err(e) => {
+            log::warn!("[ffmpeg] Stream copy failed, re-encoding: {}", e);
+            run(app, cut_args(input, output, start_ms, end_ms, false))
+                .await
+                .map(|_| ())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_silencedetect() {
+        let log = "\
+[silencedetect @ 0x1] silence_start: 181.5
+[silencedetect @ 0x1] silence_end: 184.25 | silence_duration: 2.75
+size=N/A time=00:05:00.00
+[silencedetect @ 0x1] silence_start: -0.01
+[silencedetect @ 0x1] silence_end: 2 | silence_duration: 2.01
+[silencedetect @ 0x1] silence_start: 598.2
+";
+
+        assert_eq!(
+            parse_silencedetect(log),
+            vec![
+                SilenceInterval {
+                    start_ms: 181_500,
+                    end_ms: 184_250,
+                },
+                SilenceInterval {
+                    start_ms: 0,
+                    end_ms: 2_000,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        let log = "\
+Input #0, mp3, from 'mix.mp3':
+  Duration: 01:02:03.45, start: 0.025057, bitrate: 320 kb/s
+  Stream #0:0: Audio: mp3, 44100 Hz, stereo, fltp, 320 kb/s
+";
+        assert_eq!(parse_duration(log), Some(3_723_450));
+        assert_eq!(parse_duration("  Duration: N/A, bitrate: N/A"), None);
+    }
+
+    #[test]
+    fn test_cut_args_stream_copy() {
+        let args = cut_args(
+            Path::new("/music/mix.mp3"),
+            Path::new("/music/mix/01.mp3"),
+            65_500,
+            Some(125_000),
+            true,
+        );
+
+        assert_eq!(
+            args.join(" "),
+            "-y -hide_banner -loglevel error -ss 65.500 -t 59.500 -i /music/mix.mp3 \
+             -map 0:a -map_metadata -1 -map_chapters -1 -c copy /music/mix/01.mp3"
+        );
+    }
+
+    #[test]
+    fn test_cut_args_reencode_until_end() {
+        let args = cut_args(Path::new("in.mp3"), Path::new("out.mp3"), 0, None, false);
+
+        assert!(!args.contains(&"-t".to_string()));
+        assert!(args.contains(&"libmp3lame".to_string()));
+        assert_eq!(args.last().unwrap(), "out.mp3");
+    }
+}
@@ -0,0 +1,158 @@
+//! Download history, used to identify previously downloaded files.
+//!
+//! Each completed download is appended as one JSON line to
+//! `download-history.jsonl` in the app data directory.
+
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Manager, Runtime};
+
+use crate::services::storage::current_timestamp;
+
+const HISTORY_FILE: &str = "download-history.jsonl";
+
+/// A completed download.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    /// SoundCloud track ID; empty for a piece split from a mix, which is
+    /// not a track of its own.
+    pub track_id: String,
+    pub track_url: String,
+    pub title: String,
+    pub artist: String,
+    /// File name of the downloaded file (without directory).
+    pub file_name: String,
+    /// Unix timestamp of the download.
+    pub downloaded_at: u64,
+}
+
+impl HistoryEntry {
+    pub fn new(track_id: &str, track_url: &str, title: &str, artist: &str, path: &Path) -> Self {
+        Self {
+            track_id: track_id.to_string(),
+            track_url: track_url.to_string(),
+            title: title.to_string(),
+            artist: artist.to_string(),
+            file_name: path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            downloaded_at: current_timestamp(),
+        }
+    }
+}
+
+/// Path of the history file in the app data directory.
+pub fn history_path<R: Runtime>(app: &AppHandle<R>) -> Option<PathBuf> {
+    app.path()
+        .app_data_dir()
+        .ok()
+        .map(|dir| dir.join(HISTORY_FILE))
+}
+
+/// Appends an entry to the history file, creating it if needed.
+pub fn append_entry(path: &Path, entry: &HistoryEntry) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let line = serde_json::to_string(entry).map_err(std::io::Error::other)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)
+}
+
+/// Records a completed download. Failures are logged and ignored.
+pub fn record_download<R: Runtime>(app: &AppHandle<R>, entry: &HistoryEntry) {
+    let Some(path) = history_path(app) else {
+        return;
+    };
+
+    if let Err(e) = append_entry(&path, entry) {
+        log::warn!("[history] Failed to record download: {}", e);
+    }
+}
+
+/// Loads all history entries, skipping malformed lines.
+pub fn load_entries(path: &Path) -> Vec<HistoryEntry> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return vec![];
+    };
+
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Finds the most recent entry for a file name.
+pub fn find_by_file_name<'a>(
+    entries: &'a [HistoryEntry],
+    file_name: &str,
+) -> Option<&'a HistoryEntry> {
+    entries.iter().rev().find(|e| e.file_name == file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn entry(id: &str, file_name: &str) -> HistoryEntry {
+        HistoryEntry {
+            track_id: id.to_string(),
+            track_url: format!("https://api.soundcloud.com/tracks/{}", id),
+            title: "Title".to_string(),
+            artist: "Artist".to_string(),
+            file_name: file_name.to_string(),
+            downloaded_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_append_and_load_entries() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("nested").join(HISTORY_FILE);
+
+        append_entry(&path, &entry("1", "a.mp3")).unwrap();
+        append_entry(&path, &entry("2", "b.mp3")).unwrap();
+
+        let entries = load_entries(&path);
+        assert_eq!(entries, vec![entry("1", "a.mp3"), entry("2", "b.mp3")]);
+    }
+
+    #[test]
+    fn test_load_entries_skips_malformed_lines() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join(HISTORY_FILE);
+        let valid = serde_json::to_string(&entry("1", "a.mp3")).unwrap();
+        fs::write(&path, format!("not json\n\n{}\n", valid)).unwrap();
+
+        assert_eq!(load_entries(&path).len(), 1);
+    }
+
+    #[test]
+    fn test_load_entries_missing_file() {
+        assert!(load_entries(Path::new("/nonexistent/history.jsonl")).is_empty());
+    }
+
+    #[test]
+    fn test_find_by_file_name_prefers_latest() {
+        let entries = vec![
+            entry("1", "a.mp3"),
+            entry("2", "a.mp3"),
+            entry("3", "b.mp3"),
+        ];
+
+        assert_eq!(find_by_file_name(&entries, "a.mp3").unwrap().track_id, "2");
+        assert!(find_by_file_name(&entries, "c.mp3").is_none());
+    }
+
+    #[test]
+    fn test_history_entry_new_uses_file_name() {
+        let e = HistoryEntry::new("1", "url", "T", "A", Path::new("/music/A - T.mp3"));
+        assert_eq!(e.file_name, "A - T.mp3");
+    }
+}
@@ -1,4 +1,279 @@
+//! Shared HTTP client used by every service.
+//!
+//! One `reqwest::Client` is built at startup so connections are pooled and
+//! every request gets the same timeouts and User-Agent. GET requests are
+//! retried with exponential backoff on connection errors, timeouts, 429 and
+//! 5xx responses, honouring `Retry-After` when the server sends one.
+//! SoundCloud API requests also go through the shared rate limiter.
+//!
+//! The client can be reconfigured at runtime (e.g. when the proxy setting
+//! changes); every handle to it picks up the new configuration.
+
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use once_cell::sync::OnceCell;
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use reqwest::redirect::Policy;
+use reqwest::{IntoUrl, Method, RequestBuilder, Response, StatusCode};
 use serde::de::DeserializeOwned;
+use tokio::time::sleep;
+
+use crate::models::error::ProxyError;
+use crate::services::proxy::ProxySettings;
+use crate::services::rate_limit;
+
+/// User-Agent sent with every request. It stays browser-like because the
+/// SoundCloud web pages only include hydration data for browsers.
+pub const USER_AGENT: &str = concat!(
+    "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36 InfraBoothDownloader/",
+    env!("CARGO_PKG_VERSION")
+);
+
+/// Timeouts and retry policy of the shared client.
+#[derive(Debug, Clone)]
+pub struct HttpConfig {
+    /// Time allowed to establish a connection.
+    pub connect_timeout: Duration,
+    /// Time allowed for a whole request, body included.
+    pub request_timeout: Duration,
+    /// Retries of a failed GET after the first attempt.
+    pub max_retries: u32,
+    /// Delay before the first retry, doubled for each following one.
+    pub initial_backoff: Duration,
+    /// Longest delay between attempts. A `Retry-After` asking for more
+    /// is not waited for; the response is returned as is.
+    pub max_backoff: Duration,
+    pub proxy: ProxySettings,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(10),
+            request_timeout: Duration::from_secs(30),
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+            proxy: ProxySettings::default(),
+        }
+    }
+}
+
+/// Pooled HTTP client with retries for idempotent requests.
+///
+/// Cloning is cheap; clones share the connection pool and configuration.
+#[derive(Debug, Clone)]
+pub struct HttpClient {
+    inner: Arc<RwLock<Configured>>,
+}
+
+/// A built client together with the configuration it was built from.
+#[derive(Debug, Clone)]
+struct Configured {
+    client: reqwest::Client,
+    config: HttpConfig,
+}
+
+impl Configured {
+    fn build(config: HttpConfig) -> Result<Self, ProxyError> {
+        let client = build_client(&config, Policy::default())?;
+        Ok(Self { client, config })
+    }
+}
+
+/// Builds a standalone client with the User-Agent, timeouts and proxy of
+/// `config`, for requests that need their own redirect policy.
+pub fn build_client(config: &HttpConfig, redirect: Policy) -> Result<reqwest::Client, ProxyError> {
+    let builder = reqwest::Client::builder()
+        .user_agent(USER_AGENT)
+        .connect_timeout(config.connect_timeout)
+        .timeout(config.request_timeout)
+        .redirect(redirect);
+
+    config
+        .proxy
+        .apply(builder)?
+        .build()
+        .map_err(|e| ProxyError::InvalidProxy(e.to_string()))
+}
+
+impl HttpClient {
+    pub fn new(config: HttpConfig) -> Result<Self, ProxyError> {
+        Ok(Self {
+            inner: Arc::new(RwLock::new(Configured::build(config)?)),
+        })
+    }
+
+    fn current(&self) -> Configured {
+        self.inner.read().unwrap().clone()
+    }
+
+    /// Rebuilds the client with a new configuration. On error the previous
+    /// configuration stays in use.
+    pub fn configure(&self, config: HttpConfig) -> Result<(), ProxyError> {
+        let configured = Configured::build(config)?;
+        *self.inner.write().unwrap() = configured;
+        Ok(())
+    }
+
+    pub fn config(&self) -> HttpConfig {
+        self.inner.read().unwrap().config.clone()
+    }
+
+    /// Routes every following request through `proxy`.
+    pub fn set_proxy(&self, proxy: ProxySettings) -> Result<(), ProxyError> {
+        self.configure(HttpConfig {
+            proxy,
+            ..self.config()
+        })
+    }
+
+    pub fn get<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.current().client.get(url)
+    }
+
+    pub fn post<U: IntoUrl>(&self, url: U) -> RequestBuilder {
+        self.current().client.post(url)
+    }
+
+    /// Sends a request, retrying GETs that fail transiently.
+    ///
+    /// Requests to the SoundCloud API wait for the shared rate limiter, and
+    /// a 429 from it pauses every API request rather than just this one.
+    /// Other methods are sent once. After the last attempt the final
+    /// response is returned whatever its status, so callers keep mapping
+    /// statuses to their own errors.
+    pub async fn send(&self, request: RequestBuilder) -> Result<Response, reqwest::Error> {
+        let Configured { client, config } = self.current();
+        let request = request.build()?;
+        let limiter = rate_limit::limiter_for(request.url());
+
+        if let Some(limiter) = limiter {
+            limiter.acquire().await;
+        }
+        if request.method() != Method::GET {
+            return client.execute(request).await;
+        }
+
+        let mut attempt = 0;
+        loop {
+            let Some(this_try) = request.try_clone() else {
+                return client.execute(request).await;
+            };
+            let retries_left = attempt < config.max_retries;
+
+            let delay = match client.execute(this_try).await {
+                Ok(response) if is_retryable_status(response.status()) => {
+                    let delay = retry_after(response.headers())
+                        .unwrap_or_else(|| backoff_delay(&config, attempt));
+                    let rate_limited = response.status() == StatusCode::TOO_MANY_REQUESTS;
+
+                    if let (Some(limiter), true) = (limiter, rate_limited) {
+                        limiter.back_off(delay.min(config.max_backoff));
+                    }
+                    if !retries_left {
+                        return Ok(response);
+                    }
+                    if delay > config.max_backoff {
+                        log::warn!(
+                            "[http] {} asked to retry in {:?}, giving up",
+                            request.url(),
+                            delay
+                        );
+                        return Ok(response);
+                    }
+                    log::warn!(
+                        "[http] {} returned {}, retrying in {:?}",
+                        request.url(),
+                        response.status(),
+                        delay
+                    );
+                    // The limiter already holds every API request back
+                    if limiter.is_some() && rate_limited {
+                        Duration::ZERO
+                    } else {
+                        delay
+                    }
+                }
+                Err(e) if retries_left && (e.is_timeout() || e.is_connect()) => {
+                    let delay = backoff_delay(&config, attempt);
+                    log::warn!(
+                        "[http] {} failed: {}, retrying in {:?}",
+                        request.url(),
+                        e,
+                        delay
+                    );
+                    delay
+                }
+                result => return result,
+            };
+
+            sleep(delay).await;
+            if let Some(limiter) = limiter {
+                limiter.acquire().await;
+            }
+            attempt += 1;
+        }
+    }
+}
+
+static SHARED: OnceCell<HttpClient> = OnceCell::new();
+
+/// Builds the shared client from `config`, or returns the existing one if
+/// it was already built.
+pub fn init(config: HttpConfig) -> &'static HttpClient {
+    SHARED.get_or_init(|| {
+        HttpClient::new(config).unwrap_or_else(|e| {
+            log::error!("[http] Failed to configure HTTP client: {}", e);
+            HttpClient {
+                inner: Arc::new(RwLock::new(Configured {
+                    client: reqwest::Client::new(),
+                    config: HttpConfig::default(),
+                })),
+            }
+        })
+    })
+}
+
+/// The shared client, built with the default configuration on first use
+/// if [`init`] was not called.
+pub fn client() -> &'static HttpClient {
+    init(HttpConfig::default())
+}
+
+/// Statuses worth retrying: rate limiting and temporary server failures.
+fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::REQUEST_TIMEOUT
+            | StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Delay requested by a `Retry-After` header given in seconds.
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let seconds = headers
+        .get(RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()?;
+    Some(Duration::from_secs(seconds))
+}
+
+/// Exponential backoff: the initial delay doubled for each previous retry.
+fn backoff_delay(config: &HttpConfig, attempt: u32) -> Duration {
+    config
+        .initial_backoff
+        .saturating_mul(2u32.saturating_pow(attempt))
+        .min(config.max_backoff)
+}
 
 pub async fn handle_json_response<T, E>(
     response: reqwest::Response,
@@ -16,3 +291,55 @@ where
         Err(error_constructor(format!("HTTP {}: {}", status, body)))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_retryable_statuses() {
+        assert!(is_retryable_status(StatusCode::TOO_MANY_REQUESTS));
+        assert!(is_retryable_status(StatusCode::SERVICE_UNAVAILABLE));
+        assert!(!is_retryable_status(StatusCode::NOT_FOUND));
+        assert!(!is_retryable_status(StatusCode::UNAUTHORIZED));
+        assert!(!is_retryable_status(StatusCode::OK));
+    }
+
+    #[test]
+    fn test_retry_after_seconds() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("7"));
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(7)));
+
+        // HTTP dates fall back to the regular backoff
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(retry_after(&headers), None);
+    }
+
+    #[test]
+    fn test_backoff_delay_doubles_up_to_max() {
+        let config = HttpConfig {
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(3),
+            ..Default::default()
+        };
+
+        assert_eq!(backoff_delay(&config, 0), Duration::from_millis(500));
+        assert_eq!(backoff_delay(&config, 1), Duration::from_secs(1));
+        assert_eq!(backoff_delay(&config, 2), Duration::from_secs(2));
+        assert_eq!(backoff_delay(&config, 3), Duration::from_secs(3));
+        assert_eq!(backoff_delay(&config, 40), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn test_user_agent_identifies_app() {
+        assert!(USER_AGENT.starts_with("Mozilla/5.0"));
+        assert!(USER_AGENT.contains("InfraBoothDownloader/"));
+    }
+}
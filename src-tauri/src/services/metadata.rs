@@ -1,10 +1,38 @@
-use id3::{frame::Picture, Tag, TagLike, Version};
+use id3::frame::{Comment, ExtendedText, Picture};
+use id3::{Frame, Tag, TagLike, Timestamp};
+use serde::Deserialize;
+use specta::Type;
 use std::path::Path;
 
 use crate::models::error::MetadataError;
+use crate::services::artwork::{Artwork, ArtworkCache, ArtworkSize};
+use crate::services::tag_profile::{self, TagProfile};
+use crate::services::tracklist::{apply_chapters, parse_tracklist, write_cue_sheet};
+
+/// How our tags are combined with the tag already in the file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum TagWriteMode {
+    /// Keep frames written by yt-dlp/ffmpeg and overwrite only the fields we set.
+    #[default]
+    Merge,
+    /// Discard the existing tag and write ours from scratch.
+    Replace,
+}
+
+/// User-configurable tagging options sent with download requests.
+#[derive(Debug, Clone, Default, Deserialize, Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TagOptions {
+    /// Resolution of the embedded cover art.
+    pub artwork_size: ArtworkSize,
+    pub write_mode: TagWriteMode,
+    /// ID3 versions written to the file.
+    pub profile: TagProfile,
+}
 
 /// Metadata to embed in a downloaded track.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct TrackMetadata {
     pub title: String,
     pub artist: String,
@@ -12,16 +40,56 @@ pub struct TrackMetadata {
     pub track_number: Option<u32>,
     pub total_tracks: Option<u32>,
     pub artwork_url: Option<String>,
+    /// Album artist (TPE2), usually the playlist owner.
+    pub album_artist: Option<String>,
+    pub genre: Option<String>,
+    /// Release or upload date as returned by the API.
+    pub release_date: Option<String>,
+    /// Record label, written as publisher (TPUB).
+    pub label: Option<String>,
+    pub isrc: Option<String>,
+    /// Track description, written as a comment (COMM).
+    pub description: Option<String>,
+    /// SoundCloud permalink, written as WOAS and WOAF.
+    pub permalink_url: Option<String>,
+    pub bpm: Option<f64>,
+    /// Marks the track as part of a various-artists release (TCMP).
+    pub compilation: bool,
+    /// SoundCloud track ID, written as a TXXX frame so the file can be
+    /// identified again later (e.g. when re-tagging).
+    pub track_id: Option<String>,
+    /// Duration in milliseconds, written as TLEN and used to end the last
+    /// tracklist chapter.
+    pub duration: Option<u64>,
+    /// Page of the mix a piece was split from. Written as its own TXXX
+    /// frame rather than WOAS, so the piece is not taken for the mix.
+    pub mix_url: Option<String>,
 }
 
+/// Description of the TXXX frame holding the SoundCloud track ID.
+pub const TRACK_ID_FRAME: &str = "SOUNDCLOUD_TRACK_ID";
+
+/// Description of the TXXX frame holding the URL of the source mix.
+pub const MIX_URL_FRAME: &str = "SOUNDCLOUD_MIX_URL";
+
 /// Embed metadata (ID3 tags) into an MP3 file.
 ///
-/// Writes ID3v2.4 tags including title, artist, album, track number,
-/// and artwork (downloaded from URL if provided).
+/// Writes ID3 tags in the configured profile (ID3v2.4 by default)
+/// including title, artist, album, album artist, track number, genre,
+/// date, label, ISRC, comment, source URL, BPM and artwork (downloaded
+/// from URL if provided, or served from the cache). When the description
+/// contains a timestamped tracklist, chapters are added and a `.cue` file
+/// is written next to the MP3.
+///
+/// In merge mode the tag written by yt-dlp is read first and our fields are
+/// applied on top of it (see [`apply_metadata`] for the precedence rules).
+/// In replace mode the file gets a fresh tag.
 ///
 /// # Arguments
 /// * `file_path` - Path to the MP3 file
 /// * `metadata` - Track metadata to embed
+/// * `options` - Tagging options (artwork size, merge or replace, profile)
+/// * `artwork_cache` - Cache shared by all tracks of a queue
 ///
 /// # Returns
 /// Ok(()) on success, or MetadataError on failure.
@@ -32,9 +100,71 @@ pub struct TrackMetadata {
 pub async fn embed_metadata(
     file_path: &Path,
     metadata: TrackMetadata,
+    options: &TagOptions,
+    artwork_cache: &ArtworkCache,
 ) -> Result<(), MetadataError> {
-    let mut tag = Tag::new();
+    let mut tag = match options.write_mode {
+        TagWriteMode::Merge => read_existing_tag(file_path),
+        TagWriteMode::Replace => Tag::new(),
+    };
+
+    // Download artwork
+    let artwork = match &metadata.artwork_url {
+        Some(artwork_url) => match artwork_cache.get(artwork_url, options.artwork_size).await {
+            Ok(artwork) => Some(artwork),
+            Err(e) => {
+                // Log but continue - artwork is optional
+                log::warn!("Failed to download artwork: {}", e);
+                None
+            }
+        },
+        None => None,
+    };
+
+    apply_metadata(&mut tag, &metadata, artwork.as_deref());
 
+    // Write tag to file
+    tag_profile::write_tag(file_path, &tag, options.profile)
+        .map_err(|e| MetadataError::WriteFailed(e.to_string()))?;
+
+    // Mixes with a tracklist also get a CUE sheet
+    let tracklist = parse_tracklist(metadata.description.as_deref().unwrap_or_default());
+    if !tracklist.is_empty() {
+        if let Err(e) = write_cue_sheet(file_path, &tracklist, &metadata.artist, &metadata.title) {
+            log::warn!("Failed to write CUE sheet: {}", e);
+        }
+    }
+
+    Ok(())
+}
+
+/// Read the tag already present in a file, or an empty tag if there is none.
+///
+/// ID3v2.3 date frames are upgraded so they are replaced rather than
+/// duplicated by ours.
+fn read_existing_tag(file_path: &Path) -> Tag {
+    match Tag::read_from_path(file_path) {
+        Ok(mut tag) => {
+            tag_profile::upgrade_frames(&mut tag);
+            tag
+        }
+        Err(e) if matches!(e.kind, id3::ErrorKind::NoTag) => Tag::new(),
+        Err(e) => {
+            log::warn!("Failed to read existing tag from {:?}: {}", file_path, e);
+            Tag::new()
+        }
+    }
+}
+
+/// Apply track metadata to a tag.
+///
+/// Precedence: every field we have a value for overwrites the matching
+/// frame, fields we don't have leave the existing frame untouched, and
+/// frames we never write (encoder settings, extra TXXX frames, ...) are
+/// kept as-is. The comment replaces any existing untitled comment and the
+/// artwork replaces the existing front cover. A timestamped tracklist in
+/// the description replaces any existing chapters.
+pub fn apply_metadata(tag: &mut Tag, metadata: &TrackMetadata, artwork: Option<&Artwork>) {
     // Set basic metadata
     tag.set_title(&metadata.title);
     tag.set_artist(&metadata.artist);
@@ -51,65 +181,119 @@ pub async fn embed_metadata(
         tag.set_total_tracks(total);
     }
 
-    // Download and embed artwork
-    if let Some(artwork_url) = &metadata.artwork_url {
-        match download_artwork(artwork_url).await {
-            Ok(artwork_data) => {
-                let picture = Picture {
-                    mime_type: "image/jpeg".to_string(),
-                    picture_type: id3::frame::PictureType::CoverFront,
-                    description: "Cover".to_string(),
-                    data: artwork_data,
-                };
-                tag.add_frame(picture);
-            }
-            Err(e) => {
-                // Log but continue - artwork is optional
-                log::warn!("Failed to download artwork: {}", e);
-            }
-        }
+    if let Some(album_artist) = &metadata.album_artist {
+        tag.set_album_artist(album_artist);
     }
 
-    // Write tag to file
-    tag.write_to_path(file_path, Version::Id3v24)
-        .map_err(|e| MetadataError::WriteFailed(e.to_string()))?;
+    if let Some(genre) = &metadata.genre {
+        tag.set_genre(genre);
+    }
 
-    Ok(())
+    if let Some(date) = metadata
+        .release_date
+        .as_deref()
+        .and_then(parse_release_date)
+    {
+        tag.set_date_recorded(date);
+    }
+
+    if let Some(label) = &metadata.label {
+        tag.set_text("TPUB", label);
+    }
+
+    if let Some(isrc) = &metadata.isrc {
+        tag.set_text("TSRC", isrc);
+    }
+
+    if let Some(description) = &metadata.description {
+        tag.remove_comment(Some(""), None);
+        tag.add_frame(Comment {
+            lang: "eng".to_string(),
+            description: String::new(),
+            text: description.clone(),
+        });
+    }
+
+    if let Some(permalink) = &metadata.permalink_url {
+        tag.add_frame(Frame::link("WOAS", permalink));
+        tag.add_frame(Frame::link("WOAF", permalink));
+    }
+
+    if let Some(bpm) = metadata.bpm.filter(|bpm| *bpm > 0.0) {
+        tag.set_text("TBPM", (bpm.round() as u32).to_string());
+    }
+
+    if let Some(duration) = metadata.duration.filter(|d| *d > 0) {
+        tag.set_text("TLEN", duration.to_string());
+    }
+
+    if metadata.compilation {
+        tag.set_text("TCMP", "1");
+    }
+
+    if let Some(track_id) = &metadata.track_id {
+        tag.add_frame(ExtendedText {
+            description: TRACK_ID_FRAME.to_string(),
+            value: track_id.clone(),
+        });
+    }
+
+    if let Some(mix_url) = &metadata.mix_url {
+        tag.add_frame(ExtendedText {
+            description: MIX_URL_FRAME.to_string(),
+            value: mix_url.clone(),
+        });
+    }
+
+    if let Some(description) = &metadata.description {
+        let tracklist = parse_tracklist(description);
+        if !tracklist.is_empty() {
+            apply_chapters(tag, &tracklist, metadata.duration);
+        }
+    }
+
+    if let Some(artwork) = artwork {
+        tag.add_frame(Picture {
+            mime_type: artwork.mime_type.to_string(),
+            picture_type: id3::frame::PictureType::CoverFront,
+            description: "Cover".to_string(),
+            data: artwork.data.clone(),
+        });
+    }
 }
 
-/// Download artwork from URL, using higher resolution variant.
+/// Parse a SoundCloud date into an ID3 timestamp (date precision only).
 ///
-/// SoundCloud artwork URLs support resolution suffixes:
-/// - `-large`: 100x100 (default)
-/// - `-t300x300`: 300x300
-/// - `-t500x500`: 500x500 (used here)
-/// - `-original`: Original size (may be huge)
-async fn download_artwork(url: &str) -> Result<Vec<u8>, MetadataError> {
-    // Use higher resolution artwork (500x500 for quality without excessive size)
-    let hq_url = url.replace("-large", "-t500x500");
-
-    let response = reqwest::get(&hq_url)
-        .await
-        .map_err(|e| MetadataError::ArtworkFailed(e.to_string()))?;
+/// Accepts both the ISO 8601 form (`2023-05-01T12:00:00Z`) and the
+/// legacy API form (`2023/05/01 12:00:00 +0000`).
+pub fn parse_release_date(value: &str) -> Option<Timestamp> {
+    let mut parts = value
+        .get(..10)
+        .unwrap_or(value)
+        .split(['-', '/'])
+        .map(|p| p.trim());
 
-    if !response.status().is_success() {
-        return Err(MetadataError::ArtworkFailed(format!(
-            "HTTP {}",
-            response.status()
-        )));
+    let year: i32 = parts.next()?.parse().ok()?;
+    if !(1000..=9999).contains(&year) {
+        return None;
     }
+    let month: Option<u8> = parts.next().and_then(|m| m.parse().ok());
+    let day: Option<u8> = month.and(parts.next().and_then(|d| d.parse().ok()));
 
-    let bytes = response
-        .bytes()
-        .await
-        .map_err(|e| MetadataError::ArtworkFailed(e.to_string()))?;
-
-    Ok(bytes.to_vec())
+    Some(Timestamp {
+        year,
+        month,
+        day,
+        hour: None,
+        minute: None,
+        second: None,
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use id3::Version;
     use std::fs;
     use tempfile::tempdir;
 
@@ -138,9 +322,16 @@ mod tests {
             track_number: Some(5),
             total_tracks: Some(10),
             artwork_url: None,
+            ..Default::default()
         };
 
-        let result = embed_metadata(&file_path, metadata).await;
+        let result = embed_metadata(
+            &file_path,
+            metadata,
+            &TagOptions::default(),
+            &ArtworkCache::default(),
+        )
+        .await;
         assert!(result.is_ok());
 
         // Verify tags were written
@@ -166,9 +357,16 @@ mod tests {
             track_number: None,
             total_tracks: None,
             artwork_url: None,
+            ..Default::default()
         };
 
-        let result = embed_metadata(&file_path, metadata).await;
+        let result = embed_metadata(
+            &file_path,
+            metadata,
+            &TagOptions::default(),
+            &ArtworkCache::default(),
+        )
+        .await;
         assert!(result.is_ok());
 
         let tag = Tag::read_from_path(&file_path).unwrap();
@@ -178,6 +376,355 @@ mod tests {
         assert_eq!(tag.track(), None);
     }
 
+    #[tokio::test]
+    async fn test_embed_metadata_extended_fields() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("test.mp3");
+
+        fs::write(&file_path, create_minimal_mp3()).unwrap();
+
+        let metadata = TrackMetadata {
+            title: "Test Track".to_string(),
+            artist: "Test Artist".to_string(),
+            album_artist: Some("Playlist Owner".to_string()),
+            genre: Some("Techno".to_string()),
+            release_date: Some("2023/05/01 12:00:00 +0000".to_string()),
+            label: Some("Some Label".to_string()),
+            isrc: Some("GBXXX2300001".to_string()),
+            description: Some("Out now".to_string()),
+            permalink_url: Some("https://soundcloud.com/artist/test-track".to_string()),
+            bpm: Some(127.6),
+            ..Default::default()
+        };
+
+        let result = embed_metadata(
+            &file_path,
+            metadata,
+            &TagOptions::default(),
+            &ArtworkCache::default(),
+        )
+        .await;
+        assert!(result.is_ok());
+
+        let tag = Tag::read_from_path(&file_path).unwrap();
+        assert_eq!(tag.album_artist(), Some("Playlist Owner"));
+        assert_eq!(tag.genre(), Some("Techno"));
+        assert_eq!(tag.date_recorded().map(|d| d.year), Some(2023));
+        assert_eq!(
+            tag.get("TPUB").and_then(|f| f.content().text()),
+            Some("Some Label")
+        );
+        assert_eq!(
+            tag.get("TSRC").and_then(|f| f.content().text()),
+            Some("GBXXX2300001")
+        );
+        assert_eq!(
+            tag.get("TBPM").and_then(|f| f.content().text()),
+            Some("128")
+        );
+        assert_eq!(
+            tag.get("WOAS").and_then(|f| f.content().link()),
+            Some("https://soundcloud.com/artist/test-track")
+        );
+        assert!(tag.get("WOAF").is_some());
+        assert_eq!(
+            tag.comments().next().map(|c| c.text.as_str()),
+            Some("Out now")
+        );
+        assert!(tag.get("TCMP").is_none());
+    }
+
+    #[test]
+    fn test_apply_metadata_writes_length() {
+        let mut tag = Tag::new();
+        let metadata = TrackMetadata {
+            duration: Some(215_000),
+            ..Default::default()
+        };
+
+        apply_metadata(&mut tag, &metadata, None);
+        assert_eq!(
+            tag.get("TLEN").and_then(|f| f.content().text()),
+            Some("215000")
+        );
+    }
+
+    #[test]
+    fn test_apply_metadata_writes_track_id() {
+        let mut tag = Tag::new();
+        let metadata = TrackMetadata {
+            track_id: Some("123456".to_string()),
+            ..Default::default()
+        };
+
+        apply_metadata(&mut tag, &metadata, None);
+        apply_metadata(&mut tag, &metadata, None);
+
+        let ids: Vec<_> = tag
+            .extended_texts()
+            .filter(|t| t.description == TRACK_ID_FRAME)
+            .map(|t| t.value.as_str())
+            .collect();
+        assert_eq!(ids, vec!["123456"]);
+    }
+
+    #[tokio::test]
+    async fn test_embed_metadata_compilation_flag() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("test.mp3");
+
+        fs::write(&file_path, create_minimal_mp3()).unwrap();
+
+        let metadata = TrackMetadata {
+            title: "Test Track".to_string(),
+            artist: "Test Artist".to_string(),
+            compilation: true,
+            ..Default::default()
+        };
+
+        embed_metadata(
+            &file_path,
+            metadata,
+            &TagOptions::default(),
+            &ArtworkCache::default(),
+        )
+        .await
+        .unwrap();
+
+        let tag = Tag::read_from_path(&file_path).unwrap();
+        assert_eq!(tag.get("TCMP").and_then(|f| f.content().text()), Some("1"));
+    }
+
+    fn write_existing_tag(file_path: &Path) {
+        let mut tag = Tag::new();
+        tag.set_title("yt-dlp title");
+        tag.set_album("yt-dlp album");
+        tag.set_text("TSSE", "Lavf60.3.100");
+        tag.add_frame(Comment {
+            lang: "XXX".to_string(),
+            description: String::new(),
+            text: "yt-dlp comment".to_string(),
+        });
+        tag.write_to_path(file_path, Version::Id3v24).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_embed_metadata_merge_keeps_existing_frames() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("test.mp3");
+
+        fs::write(&file_path, create_minimal_mp3()).unwrap();
+        write_existing_tag(&file_path);
+
+        let metadata = TrackMetadata {
+            title: "Our Title".to_string(),
+            artist: "Our Artist".to_string(),
+            description: Some("Our description".to_string()),
+            ..Default::default()
+        };
+
+        embed_metadata(
+            &file_path,
+            metadata,
+            &TagOptions::default(),
+            &ArtworkCache::default(),
+        )
+        .await
+        .unwrap();
+
+        let tag = Tag::read_from_path(&file_path).unwrap();
+        // Our fields win
+        assert_eq!(tag.title(), Some("Our Title"));
+        assert_eq!(tag.artist(), Some("Our Artist"));
+        assert_eq!(
+            tag.comments().map(|c| c.text.as_str()).collect::<Vec<_>>(),
+            vec!["Our description"]
+        );
+        // Fields we don't set and extra frames are kept
+        assert_eq!(tag.album(), Some("yt-dlp album"));
+        assert_eq!(
+            tag.get("TSSE").and_then(|f| f.content().text()),
+            Some("Lavf60.3.100")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_embed_metadata_replace_discards_existing_frames() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("test.mp3");
+
+        fs::write(&file_path, create_minimal_mp3()).unwrap();
+        write_existing_tag(&file_path);
+
+        let metadata = TrackMetadata {
+            title: "Our Title".to_string(),
+            artist: "Our Artist".to_string(),
+            ..Default::default()
+        };
+        let options = TagOptions {
+            write_mode: TagWriteMode::Replace,
+            ..Default::default()
+        };
+
+        embed_metadata(&file_path, metadata, &options, &ArtworkCache::default())
+            .await
+            .unwrap();
+
+        let tag = Tag::read_from_path(&file_path).unwrap();
+        assert_eq!(tag.title(), Some("Our Title"));
+        assert_eq!(tag.album(), None);
+        assert!(tag.get("TSSE").is_none());
+        assert_eq!(tag.comments().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_embed_metadata_v23_profile_replaces_existing_year() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("test.mp3");
+
+        fs::write(&file_path, create_minimal_mp3()).unwrap();
+        let mut existing = Tag::new();
+        existing.set_text("TYER", "1999");
+        existing.write_to_path(&file_path, Version::Id3v23).unwrap();
+
+        let metadata = TrackMetadata {
+            title: "Title".to_string(),
+            artist: "Artist".to_string(),
+            release_date: Some("2021-03-04T00:00:00Z".to_string()),
+            ..Default::default()
+        };
+        let options = TagOptions {
+            profile: TagProfile::Id3v23,
+            ..Default::default()
+        };
+
+        embed_metadata(&file_path, metadata, &options, &ArtworkCache::default())
+            .await
+            .unwrap();
+
+        let tag = Tag::read_from_path(&file_path).unwrap();
+        assert_eq!(tag.version(), Version::Id3v23);
+        assert_eq!(tag.year(), Some(2021));
+        assert_eq!(tag.get("TDAT").unwrap().content().text(), Some("0403"));
+        assert!(tag.get("TDRC").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_embed_metadata_tracklist_writes_chapters_and_cue() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("Mix.mp3");
+        fs::write(&file_path, create_minimal_mp3()).unwrap();
+
+        let metadata = TrackMetadata {
+            title: "Mix".to_string(),
+            artist: "DJ".to_string(),
+            description: Some("00:00 A - One\n05:00 B - Two".to_string()),
+            duration: Some(600_000),
+            ..Default::default()
+        };
+
+        embed_metadata(
+            &file_path,
+            metadata,
+            &TagOptions::default(),
+            &ArtworkCache::default(),
+        )
+        .await
+        .unwrap();
+
+        let tag = Tag::read_from_path(&file_path).unwrap();
+        assert_eq!(tag.chapters().count(), 2);
+        assert_eq!(tag.tables_of_contents().count(), 1);
+        assert!(dir.path().join("Mix.cue").exists());
+    }
+
+    #[tokio::test]
+    async fn test_embed_metadata_without_tracklist_adds_no_chapters() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("Track.mp3");
+        fs::write(&file_path, create_minimal_mp3()).unwrap();
+
+        let metadata = TrackMetadata {
+            title: "Track".to_string(),
+            artist: "Artist".to_string(),
+            description: Some("Out now, 12:00 premiere".to_string()),
+            ..Default::default()
+        };
+
+        embed_metadata(
+            &file_path,
+            metadata,
+            &TagOptions::default(),
+            &ArtworkCache::default(),
+        )
+        .await
+        .unwrap();
+
+        let tag = Tag::read_from_path(&file_path).unwrap();
+        assert_eq!(tag.chapters().count(), 0);
+        assert!(!dir.path().join("Track.cue").exists());
+    }
+
+    #[test]
+    fn test_apply_metadata_replaces_front_cover() {
+        let mut tag = Tag::new();
+        tag.add_frame(Picture {
+            mime_type: "image/png".to_string(),
+            picture_type: id3::frame::PictureType::CoverFront,
+            description: "Old".to_string(),
+            data: vec![1, 2, 3],
+        });
+        let artwork = Artwork {
+            data: vec![0xFF, 0xD8, 0xFF],
+            mime_type: "image/jpeg",
+        };
+
+        apply_metadata(&mut tag, &TrackMetadata::default(), Some(&artwork));
+
+        let pictures: Vec<_> = tag.pictures().collect();
+        assert_eq!(pictures.len(), 1);
+        assert_eq!(pictures[0].mime_type, "image/jpeg");
+    }
+
+    #[test]
+    fn test_tag_options_deserialize() {
+        let options: TagOptions = serde_json::from_str(r#"{"writeMode": "replace"}"#).unwrap();
+        assert_eq!(options.write_mode, TagWriteMode::Replace);
+        assert_eq!(options.artwork_size, ArtworkSize::T500x500);
+
+        let options: TagOptions = serde_json::from_str("{}").unwrap();
+        assert_eq!(options.write_mode, TagWriteMode::Merge);
+    }
+
+    #[test]
+    fn test_parse_release_date_iso() {
+        let date = parse_release_date("2023-06-02T00:00:00Z").unwrap();
+        assert_eq!(date.year, 2023);
+        assert_eq!(date.month, Some(6));
+        assert_eq!(date.day, Some(2));
+    }
+
+    #[test]
+    fn test_parse_release_date_legacy_format() {
+        let date = parse_release_date("2013/03/23 14:58:27 +0000").unwrap();
+        assert_eq!(date.year, 2013);
+        assert_eq!(date.month, Some(3));
+        assert_eq!(date.day, Some(23));
+    }
+
+    #[test]
+    fn test_parse_release_date_year_only() {
+        let date = parse_release_date("1999").unwrap();
+        assert_eq!(date.year, 1999);
+        assert_eq!(date.month, None);
+    }
+
+    #[test]
+    fn test_parse_release_date_invalid() {
+        assert!(parse_release_date("").is_none());
+        assert!(parse_release_date("not a date").is_none());
+    }
+
     #[tokio::test]
     async fn test_embed_metadata_nonexistent_file() {
         let metadata = TrackMetadata {
@@ -187,9 +734,16 @@ mod tests {
             track_number: None,
             total_tracks: None,
             artwork_url: None,
+            ..Default::default()
         };
 
-        let result = embed_metadata(Path::new("/nonexistent/path.mp3"), metadata).await;
+        let result = embed_metadata(
+            Path::new("/nonexistent/path.mp3"),
+            metadata,
+            &TagOptions::default(),
+            &ArtworkCache::default(),
+        )
+        .await;
         assert!(result.is_err());
         match result {
             Err(MetadataError::WriteFailed(_)) => (),
@@ -206,6 +760,7 @@ mod tests {
             track_number: Some(1),
             total_tracks: Some(10),
             artwork_url: Some("https://example.com/art.jpg".to_string()),
+            ..Default::default()
         };
 
         let cloned = metadata.clone();
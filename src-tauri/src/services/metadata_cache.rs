@@ -0,0 +1,303 @@
+//! On-disk cache of track and playlist metadata.
+//!
+//! Entries are JSON files under the app cache directory, keyed by SoundCloud
+//! ID (`tracks/<id>.json`, `playlists/<id>.json`). Page URLs point to an ID
+//! through `urls/<hash>.json`, so reopening a pasted URL neither resolves it
+//! nor fetches its tracks again. Track entries also spare playlist fetches
+//! and retagging a request for every track already seen. Entries older than
+//! the TTL are ignored and replaced by the next fetch, and removed by
+//! [`MetadataCache::prune_expired`].
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::services::playlist::{PlaylistInfo, TrackInfo};
+use crate::services::storage::current_timestamp;
+use crate::services::url_validator::normalize_url;
+
+/// How long cached metadata is served before it is fetched again.
+pub const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A cached value and when it was stored (Unix seconds).
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry<T> {
+    cached_at: u64,
+    value: T,
+}
+
+/// Cache of `TrackInfo` and `PlaylistInfo`, disabled without a directory.
+pub struct MetadataCache {
+    dir: Option<PathBuf>,
+    ttl: Duration,
+}
+
+impl Default for MetadataCache {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl MetadataCache {
+    pub fn new(dir: Option<PathBuf>) -> Self {
+        Self {
+            dir,
+            ttl: DEFAULT_TTL,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn with_ttl(self, ttl: Duration) -> Self {
+        Self { ttl, ..self }
+    }
+
+    pub fn track(&self, id: u64) -> Option<TrackInfo> {
+        self.read(self.entry_path("tracks", id)?)
+    }
+
+    pub fn track_by_url(&self, url: &str) -> Option<TrackInfo> {
+        self.track(self.resolve_alias("track", url)?)
+    }
+
+    pub fn playlist_by_url(&self, url: &str) -> Option<PlaylistInfo> {
+        let id = self.resolve_alias("playlist", url)?;
+        self.read(self.entry_path("playlists", id)?)
+    }
+
+    /// Stores a track, reachable by ID and by `url` when given.
+    pub fn put_track(&self, url: Option<&str>, track: &TrackInfo) {
+        if let Some(path) = self.entry_path("tracks", track.id) {
+            self.write(path, track);
+        }
+        if let Some(url) = url {
+            self.put_alias("track", url, track.id);
+        }
+    }
+
+    /// Stores a playlist under its ID and `url`, and each of its tracks.
+    pub fn put_playlist(&self, url: &str, playlist: &PlaylistInfo) {
+        if let Some(path) = self.entry_path("playlists", playlist.id) {
+            self.write(path, playlist);
+        }
+        self.put_alias("playlist", url, playlist.id);
+
+        for track in &playlist.tracks {
+            self.put_track(None, track);
+        }
+    }
+
+    /// Removes entries written longer than the TTL ago, returning how many
+    /// were removed. Expired entries are never read, so they would only
+    /// take up space.
+    pub fn prune_expired(&self) -> usize {
+        let Some(dir) = &self.dir else {
+            return 0;
+        };
+
+        let mut removed = 0;
+        for kind in ["tracks", "playlists", "urls"] {
+            let Ok(entries) = std::fs::read_dir(dir.join(kind)) else {
+                continue;
+            };
+            for entry in entries.flatten() {
+                let expired = entry
+                    .metadata()
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .and_then(|modified| modified.elapsed().ok())
+                    .is_some_and(|age| age >= self.ttl);
+                if !expired {
+                    continue;
+                }
+                match std::fs::remove_file(entry.path()) {
+                    Ok(()) => removed += 1,
+                    Err(e) => log::warn!(
+                        "[metadata-cache] Failed to remove {:?}: {}",
+                        entry.path(),
+                        e
+                    ),
+                }
+            }
+        }
+        removed
+    }
+
+    fn entry_path(&self, kind: &str, id: u64) -> Option<PathBuf> {
+        Some(self.dir.as_ref()?.join(kind).join(format!("{}.json", id)))
+    }
+
+    fn alias_path(&self, kind: &str, url: &str) -> Option<PathBuf> {
+        // Variants of the same link (host, tracking query, trailing slash)
+        // share an entry
+        let url = normalize_url(url, false).unwrap_or_else(|| url.trim().to_string());
+        let key = format!("{}|{}", kind, url);
+        let hash = Sha256::digest(key.as_bytes());
+        let name: String = hash.iter().map(|b| format!("{:02x}", b)).collect();
+        Some(
+            self.dir
+                .as_ref()?
+                .join("urls")
+                .join(format!("{}.json", name)),
+        )
+    }
+
+    fn resolve_alias(&self, kind: &str, url: &str) -> Option<u64> {
+        self.read(self.alias_path(kind, url)?)
+    }
+
+    fn put_alias(&self, kind: &str, url: &str, id: u64) {
+        if let Some(path) = self.alias_path(kind, url) {
+            self.write(path, &id);
+        }
+    }
+
+    /// Reads an entry, treating expired or unreadable ones as missing.
+    fn read<T: DeserializeOwned>(&self, path: PathBuf) -> Option<T> {
+        let data = std::fs::read(&path).ok()?;
+        let entry: CacheEntry<T> = match serde_json::from_slice(&data) {
+            Ok(entry) => entry,
+            Err(e) => {
+                log::warn!(
+                    "[metadata-cache] Ignoring unreadable entry {:?}: {}",
+                    path,
+                    e
+                );
+                return None;
+            }
+        };
+
+        let age = current_timestamp().saturating_sub(entry.cached_at);
+        (age < self.ttl.as_secs()).then_some(entry.value)
+    }
+
+    fn write<T: Serialize>(&self, path: PathBuf, value: &T) {
+        if let Some(parent) = path.parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                log::warn!("[metadata-cache] Failed to create cache dir: {}", e);
+                return;
+            }
+        }
+
+        let entry = CacheEntry {
+            cached_at: current_timestamp(),
+            value,
+        };
+        let result = serde_json::to_vec(&entry)
+            .map_err(|e| e.to_string())
+            .and_then(|data| std::fs::write(&path, data).map_err(|e| e.to_string()));
+
+        if let Err(e) = result {
+            log::warn!("[metadata-cache] Failed to write {:?}: {}", path, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::playlist::UserInfo;
+    use tempfile::tempdir;
+
+    fn track(id: u64, title: &str) -> TrackInfo {
+        TrackInfo {
+            id,
+            title: title.to_string(),
+            user: UserInfo {
+                username: "artist".to_string(),
+            },
+            duration: 1000,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_track_by_id_and_url() {
+        let dir = tempdir().unwrap();
+        let cache = MetadataCache::new(Some(dir.path().to_path_buf()));
+        let url = "https://soundcloud.com/artist/one";
+
+        assert!(cache.track_by_url(url).is_none());
+        cache.put_track(Some(url), &track(1, "One"));
+
+        assert_eq!(cache.track(1).unwrap().title, "One");
+        assert_eq!(
+            cache
+                .track_by_url("https://www.soundcloud.com/artist/one/")
+                .unwrap()
+                .id,
+            1
+        );
+        // A track URL is not a playlist URL
+        assert!(cache.playlist_by_url(url).is_none());
+    }
+
+    #[test]
+    fn test_playlist_caches_its_tracks() {
+        let dir = tempdir().unwrap();
+        let cache = MetadataCache::new(Some(dir.path().to_path_buf()));
+        let url = "https://soundcloud.com/artist/sets/mix";
+        let playlist = PlaylistInfo {
+            id: 9,
+            title: "Mix".to_string(),
+            track_count: 2,
+            tracks: vec![track(1, "One"), track(2, "Two")],
+            ..Default::default()
+        };
+
+        cache.put_playlist(url, &playlist);
+
+        let cached = cache.playlist_by_url(url).unwrap();
+        assert_eq!(cached.title, "Mix");
+        assert_eq!(cached.tracks.len(), 2);
+        assert_eq!(cache.track(2).unwrap().title, "Two");
+    }
+
+    #[test]
+    fn test_expired_entries_are_ignored() {
+        let dir = tempdir().unwrap();
+        let cache = MetadataCache::new(Some(dir.path().to_path_buf())).with_ttl(Duration::ZERO);
+
+        cache.put_track(Some("https://soundcloud.com/artist/one"), &track(1, "One"));
+        assert!(cache.track(1).is_none());
+        assert!(cache
+            .track_by_url("https://soundcloud.com/artist/one")
+            .is_none());
+    }
+
+    #[test]
+    fn test_prune_expired_removes_old_entries() {
+        let dir = tempdir().unwrap();
+        let url = "https://soundcloud.com/artist/one";
+
+        let cache = MetadataCache::new(Some(dir.path().to_path_buf()));
+        cache.put_track(Some(url), &track(1, "One"));
+        assert_eq!(cache.prune_expired(), 0);
+        assert!(cache.track(1).is_some());
+
+        let cache = cache.with_ttl(Duration::ZERO);
+        assert_eq!(cache.prune_expired(), 2);
+        assert!(!cache.entry_path("tracks", 1).unwrap().exists());
+        assert!(!cache.alias_path("track", url).unwrap().exists());
+    }
+
+    #[test]
+    fn test_unreadable_entries_are_ignored() {
+        let dir = tempdir().unwrap();
+        let cache = MetadataCache::new(Some(dir.path().to_path_buf()));
+        let path = cache.entry_path("tracks", 1).unwrap();
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(&path, "not json").unwrap();
+
+        assert!(cache.track(1).is_none());
+    }
+
+    #[test]
+    fn test_cache_without_dir() {
+        let cache = MetadataCache::default();
+        cache.put_track(Some("https://soundcloud.com/artist/one"), &track(1, "One"));
+        assert!(cache.track(1).is_none());
+    }
+}
@@ -0,0 +1,394 @@
+//! Splitting of long mixes into one file per song.
+//!
+//! Cut points come from the description tracklist or, when there is none,
+//! from silence detection. Pieces are written to a folder named after the
+//! mix and tagged with their own artist/title, the mix as album and their
+//! position as track number.
+
+use serde::Deserialize;
+use specta::Type;
+use std::path::{Path, PathBuf};
+use tauri::{AppHandle, Runtime};
+
+use crate::models::error::FfmpegError;
+use crate::services::artwork::ArtworkCache;
+use crate::services::ffmpeg::{cut_segment, detect_silence, SilenceInterval};
+use crate::services::metadata::{embed_metadata, TagOptions, TrackMetadata};
+use crate::services::tracklist::{parse_tracklist, TracklistEntry};
+use crate::services::ytdlp::sanitize_filename;
+
+/// Pieces shorter than this are merged into the previous one when
+/// splitting on silence, so short breaks inside a song don't cut it up.
+const MIN_SILENCE_SEGMENT_MS: u64 = 30_000;
+
+/// Options for splitting a mix after download.
+#[derive(Debug, Clone, Deserialize, Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct MixSplitOptions {
+    /// Fall back to silence detection when there is no tracklist.
+    pub detect_silence: bool,
+    /// Volume below which audio counts as silence, in dB.
+    pub silence_threshold_db: f64,
+    /// Minimum length of a silence to split on, in seconds.
+    pub min_silence_secs: f64,
+    /// Keep the full mix next to the pieces.
+    pub keep_original: bool,
+}
+
+impl Default for MixSplitOptions {
+    fn default() -> Self {
+        Self {
+            detect_silence: true,
+            silence_threshold_db: -50.0,
+            min_silence_secs: 2.0,
+            keep_original: true,
+        }
+    }
+}
+
+/// A piece of a mix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MixSegment {
+    pub start_ms: u64,
+    /// End of the piece, or None for the rest of the file.
+    pub end_ms: Option<u64>,
+    pub artist: Option<String>,
+    pub title: String,
+}
+
+/// Pieces following a tracklist; each ends where the next one starts.
+pub fn segments_from_tracklist(
+    entries: &[TracklistEntry],
+    duration_ms: Option<u64>,
+) -> Vec<MixSegment> {
+    let entries: Vec<&TracklistEntry> = entries
+        .iter()
+        .filter(|e| !matches!(duration_ms, Some(d) if e.start_ms >= d))
+        .collect();
+
+    entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| MixSegment {
+            start_ms: entry.start_ms,
+            end_ms: entries.get(i + 1).map(|next| next.start_ms),
+            artist: entry.artist.clone(),
+            title: entry.title.clone(),
+        })
+        .collect()
+}
+
+/// Pieces cut in the middle of each silence, named `Part N`.
+///
+/// Silences at the very start or end of the file and cuts that would leave
+/// a piece shorter than [`MIN_SILENCE_SEGMENT_MS`] are ignored.
+pub fn segments_from_silence(
+    silences: &[SilenceInterval],
+    duration_ms: Option<u64>,
+) -> Vec<MixSegment> {
+    let mut cuts: Vec<u64> = Vec::new();
+
+    for silence in silences {
+        let cut = (silence.start_ms + silence.end_ms) / 2;
+        let previous = cuts.last().copied().unwrap_or(0);
+        let near_end =
+            matches!(duration_ms, Some(d) if d.saturating_sub(cut) < MIN_SILENCE_SEGMENT_MS);
+
+        if cut.saturating_sub(previous) >= MIN_SILENCE_SEGMENT_MS && !near_end {
+            cuts.push(cut);
+        }
+    }
+
+    let starts = std::iter::once(0).chain(cuts.iter().copied());
+    let ends = cuts.iter().copied().map(Some).chain(std::iter::once(None));
+
+    starts
+        .zip(ends)
+        .enumerate()
+        .map(|(i, (start_ms, end_ms))| MixSegment {
+            start_ms,
+            end_ms,
+            artist: None,
+            title: format!("Part {}", i + 1),
+        })
+        .collect()
+}
+
+/// Tags for a piece of a mix.
+pub fn segment_metadata(
+    mix: &TrackMetadata,
+    segment: &MixSegment,
+    index: usize,
+    total: usize,
+) -> TrackMetadata {
+    TrackMetadata {
+        title: segment.title.clone(),
+        artist: segment.artist.clone().unwrap_or_else(|| mix.artist.clone()),
+        album: Some(mix.title.clone()),
+        album_artist: Some(mix.artist.clone()),
+        track_number: Some(index as u32 + 1),
+        total_tracks: Some(total as u32),
+        artwork_url: mix.artwork_url.clone(),
+        genre: mix.genre.clone(),
+        release_date: mix.release_date.clone(),
+        label: mix.label.clone(),
+        mix_url: mix.permalink_url.clone(),
+        duration: segment
+            .end_ms
+            .or(mix.duration)
+            .map(|end| end.saturating_sub(segment.start_ms)),
+        ..Default::default()
+    }
+}
+
+/// Path of a piece: `<mix folder>/<NN> - <Artist> - <Title>.mp3`.
+pub fn segment_path(mix_path: &Path, metadata: &TrackMetadata, total: usize) -> PathBuf {
+    let stem = mix_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let folder = mix_path.with_file_name(sanitize_filename(&stem));
+
+    let width = total.to_string().len().max(2);
+    let file_name = format!(
+        "{:0width$} - {} - {}.mp3",
+        metadata.track_number.unwrap_or(0),
+        sanitize_filename(&metadata.artist),
+        sanitize_filename(&metadata.title),
+        width = width
+    );
+
+    folder.join(file_name)
+}
+
+/// Finds the pieces of a mix: the tracklist if there is one, silence
+/// detection otherwise (when enabled).
+async fn find_segments<R: Runtime>(
+    app: &AppHandle<R>,
+    path: &Path,
+    metadata: &TrackMetadata,
+    options: &MixSplitOptions,
+) -> Result<Vec<MixSegment>, FfmpegError> {
+    let tracklist = parse_tracklist(metadata.description.as_deref().unwrap_or_default());
+    if !tracklist.is_empty() {
+        return Ok(segments_from_tracklist(&tracklist, metadata.duration));
+    }
+
+    if !options.detect_silence {
+        return Ok(vec![]);
+    }
+
+    let silences = detect_silence(
+        app,
+        path,
+        options.silence_threshold_db,
+        options.min_silence_secs,
+    )
+    .await?;
+    Ok(segments_from_silence(&silences, metadata.duration))
+}
+
+/// A song cut out of a mix.
+#[derive(Debug, Clone)]
+pub struct SplitPiece {
+    pub path: PathBuf,
+    /// Tags written to the piece.
+    pub metadata: TrackMetadata,
+}
+
+/// Splits a downloaded mix into tagged pieces.
+///
+/// Returns the pieces, or an empty list when no cut points were found (the
+/// file is left untouched).
+pub async fn split_mix<R: Runtime>(
+    app: &AppHandle<R>,
+    path: &Path,
+    metadata: &TrackMetadata,
+    options: &MixSplitOptions,
+    tag_options: &TagOptions,
+    artwork_cache: &ArtworkCache,
+) -> Result<Vec<SplitPiece>, FfmpegError> {
+    let segments = find_segments(app, path, metadata, options).await?;
+    if segments.len() < 2 {
+        log::info!("[split] No cut points found for {:?}", path);
+        return Ok(vec![]);
+    }
+
+    let total = segments.len();
+    let mut pieces = Vec::with_capacity(total);
+
+    for (i, segment) in segments.iter().enumerate() {
+        let piece_metadata = segment_metadata(metadata, segment, i, total);
+        let piece_path = segment_path(path, &piece_metadata, total);
+
+        if let Some(folder) = piece_path.parent() {
+            std::fs::create_dir_all(folder).map_err(|e| FfmpegError::SplitFailed(e.to_string()))?;
+        }
+
+        cut_segment(app, path, &piece_path, segment.start_ms, segment.end_ms).await?;
+
+        if let Err(e) = embed_metadata(
+            &piece_path,
+            piece_metadata.clone(),
+            tag_options,
+            artwork_cache,
+        )
+        .await
+        {
+            log::warn!("[split] Failed to tag {:?}: {}", piece_path, e);
+        }
+
+        pieces.push(SplitPiece {
+            path: piece_path,
+            metadata: piece_metadata,
+        });
+    }
+
+    if !options.keep_original {
+        for file in [path.to_path_buf(), path.with_extension("cue")] {
+            if file.exists() {
+                if let Err(e) = std::fs::remove_file(&file) {
+                    log::warn!("[split] Failed to remove {:?}: {}", file, e);
+                }
+            }
+        }
+    }
+
+    log::info!("[split] Split {:?} into {} pieces", path, total);
+    Ok(pieces)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(start_ms: u64, artist: Option<&str>, title: &str) -> TracklistEntry {
+        TracklistEntry {
+            start_ms,
+            artist: artist.map(str::to_string),
+            title: title.to_string(),
+        }
+    }
+
+    fn silence(start_ms: u64, end_ms: u64) -> SilenceInterval {
+        SilenceInterval { start_ms, end_ms }
+    }
+
+    #[test]
+    fn test_split_options_deserialize_defaults() {
+        let options: MixSplitOptions = serde_json::from_str(r#"{"keepOriginal": false}"#).unwrap();
+        assert!(!options.keep_original);
+        assert!(options.detect_silence);
+        assert_eq!(options.silence_threshold_db, -50.0);
+    }
+
+    #[test]
+    fn test_segments_from_tracklist() {
+        let entries = vec![
+            entry(0, None, "Intro"),
+            entry(60_000, Some("A"), "One"),
+            entry(900_000, Some("B"), "Late"),
+        ];
+
+        let segments = segments_from_tracklist(&entries, Some(600_000));
+        assert_eq!(
+            segments,
+            vec![
+                MixSegment {
+                    start_ms: 0,
+                    end_ms: Some(60_000),
+                    artist: None,
+                    title: "Intro".to_string(),
+                },
+                MixSegment {
+                    start_ms: 60_000,
+                    end_ms: None,
+                    artist: Some("A".to_string()),
+                    title: "One".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_segments_from_silence() {
+        let silences = vec![
+            silence(0, 1_000),         // leading silence
+            silence(180_000, 182_000), // cut at 181s
+            silence(190_000, 191_000), // too close to the previous cut
+            silence(400_000, 402_000), // cut at 401s
+            silence(590_000, 592_000), // too close to the end
+        ];
+
+        let segments = segments_from_silence(&silences, Some(600_000));
+        let bounds: Vec<(u64, Option<u64>)> =
+            segments.iter().map(|s| (s.start_ms, s.end_ms)).collect();
+        assert_eq!(
+            bounds,
+            vec![
+                (0, Some(181_000)),
+                (181_000, Some(401_000)),
+                (401_000, None)
+            ]
+        );
+        assert_eq!(segments[2].title, "Part 3");
+    }
+
+    #[test]
+    fn test_segments_from_silence_without_silence() {
+        assert_eq!(segments_from_silence(&[], Some(600_000)).len(), 1);
+    }
+
+    #[test]
+    fn test_segment_metadata() {
+        let mix = TrackMetadata {
+            title: "Summer Mix".to_string(),
+            artist: "DJ".to_string(),
+            genre: Some("House".to_string()),
+            description: Some("00:00 A - One".to_string()),
+            track_id: Some("1".to_string()),
+            permalink_url: Some("https://soundcloud.com/dj/summer-mix".to_string()),
+            duration: Some(600_000),
+            ..Default::default()
+        };
+        let segment = MixSegment {
+            start_ms: 120_000,
+            end_ms: None,
+            artist: None,
+            title: "Part 2".to_string(),
+        };
+
+        let metadata = segment_metadata(&mix, &segment, 1, 3);
+        assert_eq!(metadata.title, "Part 2");
+        assert_eq!(metadata.artist, "DJ");
+        assert_eq!(metadata.album.as_deref(), Some("Summer Mix"));
+        assert_eq!(metadata.album_artist.as_deref(), Some("DJ"));
+        assert_eq!(metadata.track_number, Some(2));
+        assert_eq!(metadata.total_tracks, Some(3));
+        assert_eq!(metadata.genre.as_deref(), Some("House"));
+        assert_eq!(metadata.duration, Some(480_000));
+        // The piece must not look like the full mix
+        assert!(metadata.description.is_none());
+        assert!(metadata.track_id.is_none());
+        assert!(metadata.permalink_url.is_none());
+        assert_eq!(
+            metadata.mix_url.as_deref(),
+            Some("https://soundcloud.com/dj/summer-mix")
+        );
+    }
+
+    #[test]
+    fn test_segment_path() {
+        let metadata = TrackMetadata {
+            title: "What?".to_string(),
+            artist: "A/B".to_string(),
+            track_number: Some(3),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            segment_path(Path::new("/music/DJ - Mix.mp3"), &metadata, 12),
+            PathBuf::from("/music/DJ - Mix/03 - A_B - What_.mp3")
+        );
+    }
+}
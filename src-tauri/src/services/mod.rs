@@ -1,19 +1,33 @@
+pub mod artwork;
 pub mod auth_choice;
 pub mod cancellation;
 pub mod constants;
 pub mod deep_link;
 #[cfg(debug_assertions)]
 pub mod dev_server;
+pub mod dj_collection;
 pub mod ffmpeg;
+pub mod history;
 pub mod http;
 pub mod metadata;
+pub mod metadata_cache;
+pub mod mix_splitter;
 pub mod oauth;
+pub mod pagination;
 pub mod paths;
 pub mod pipeline;
 pub mod playlist;
+pub mod proxy;
 pub mod queue;
+pub mod rate_limit;
+pub mod retag;
+pub mod share_link;
 pub mod sidecar;
 pub mod storage;
+pub mod tag_editor;
+pub mod tag_profile;
+pub mod title_rules;
+pub mod tracklist;
 pub mod url_validator;
 pub mod updater;
 pub mod ytdlp;
@@ -5,7 +5,7 @@ use sha2::{Digest, Sha256};
 use url::Url;
 
 use crate::models::AuthError;
-use crate::services::http::handle_json_response;
+use crate::services::http::{self, handle_json_response};
 
 /// OAuth configuration constants
 pub const CLIENT_ID: &str = "4CHDCUOhHIdSxBv4XN0msyZXuIXbB5wv";
@@ -85,8 +85,7 @@ pub async fn exchange_code(
     client_secret: &str,
     redirect_uri: &str,
 ) -> Result<TokenResponse, AuthError> {
-    let client = reqwest::Client::new();
-    let response = client
+    let response = http::client()
         .post(TOKEN_URL)
         .form(&[
             ("grant_type", "authorization_code"),
@@ -139,8 +138,7 @@ pub async fn refresh_tokens(
     refresh_token: &str,
     client_secret: &str,
 ) -> Result<TokenResponse, AuthError> {
-    let client = reqwest::Client::new();
-    let response = client
+    let response = http::client()
         .post(TOKEN_URL)
         .form(&[
             ("grant_type", "refresh_token"),
@@ -157,8 +155,7 @@ pub async fn refresh_tokens(
 /// Gets an app-level access token using Client Credentials flow.
 /// This token can be used for public API requests without user sign-in.
 pub async fn get_app_token(client_secret: &str) -> Result<AppTokenResponse, AuthError> {
-    let client = reqwest::Client::new();
-    let response = client
+    let response = http::client()
         .post(TOKEN_URL)
         .form(&[
             ("grant_type", "client_credentials"),
@@ -180,11 +177,12 @@ pub async fn get_app_token(client_secret: &str) -> Result<AppTokenResponse, Auth
 /// * `Ok(UserProfile)` - The user's profile data
 /// * `Err(AuthError)` - Error if fetch fails
 pub async fn fetch_user_profile(access_token: &str) -> Result<UserProfile, AuthError> {
-    let client = reqwest::Client::new();
-    let response = client
-        .get("https://api.soundcloud.com/me")
-        .header("Authorization", format!("OAuth {}", access_token))
-        .send()
+    let http = http::client();
+    let response = http
+        .send(
+            http.get("https://api.soundcloud.com/me")
+                .header("Authorization", format!("OAuth {}", access_token)),
+        )
         .await?;
 
     handle_json_response(response, AuthError::ProfileFetchFailed).await
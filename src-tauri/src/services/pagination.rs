@@ -0,0 +1,195 @@
+//! Paging through `linked_partitioning` collections of the SoundCloud API.
+//!
+//! Collection endpoints return at most one page of items together with a
+//! `next_href` pointing to the following page. [`Pages`] follows those links
+//! one page at a time so callers can report partial results as they arrive.
+
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::services::http::{self, HttpClient};
+use crate::services::playlist::PlaylistError;
+
+/// Default number of items per page (the API maximum).
+pub const DEFAULT_PAGE_SIZE: u32 = 200;
+
+/// Largest page size the API accepts.
+pub const MAX_PAGE_SIZE: u32 = 200;
+
+/// One page of a `linked_partitioning` collection.
+#[derive(Debug, Deserialize)]
+pub struct CollectionPage {
+    pub collection: Vec<Value>,
+    pub next_href: Option<String>,
+}
+
+/// Adds `linked_partitioning=1` and the page size to an endpoint URL,
+/// replacing any values already present.
+pub fn paginated_url(endpoint: &str, page_size: u32) -> String {
+    let Ok(mut url) = url::Url::parse(endpoint) else {
+        return endpoint.to_string();
+    };
+
+    let query: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(key, _)| key != "linked_partitioning" && key != "limit")
+        .map(|(key, value)| (key.into_owned(), value.into_owned()))
+        .collect();
+
+    url.query_pairs_mut()
+        .clear()
+        .extend_pairs(query)
+        .append_pair("linked_partitioning", "1")
+        .append_pair("limit", &page_size.clamp(1, MAX_PAGE_SIZE).to_string());
+
+    url.to_string()
+}
+
+/// Items of a page that match `T`; others (e.g. deleted tracks) are skipped.
+fn parse_items<T: DeserializeOwned>(values: Vec<Value>) -> Vec<T> {
+    values
+        .into_iter()
+        .filter_map(|value| match serde_json::from_value(value) {
+            Ok(item) => Some(item),
+            Err(e) => {
+                log::warn!("[soundcloud] Skipping collection item: {}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Walks the pages of a collection by following `next_href`.
+pub struct Pages<'a> {
+    client: &'static HttpClient,
+    access_token: &'a str,
+    next: Option<String>,
+}
+
+impl<'a> Pages<'a> {
+    pub fn new(endpoint: &str, access_token: &'a str, page_size: u32) -> Self {
+        Self {
+            client: http::client(),
+            access_token,
+            next: Some(paginated_url(endpoint, page_size)),
+        }
+    }
+
+    /// Fetches the next page, or returns None once the collection is exhausted.
+    pub async fn next_page<T: DeserializeOwned>(
+        &mut self,
+    ) -> Result<Option<Vec<T>>, PlaylistError> {
+        let Some(url) = self.next.take() else {
+            return Ok(None);
+        };
+
+        let response = self
+            .client
+            .send(
+                self.client
+                    .get(&url)
+                    .header("Authorization", format!("OAuth {}", self.access_token)),
+            )
+            .await?;
+
+        match response.status() {
+            reqwest::StatusCode::NOT_FOUND => return Err(PlaylistError::TrackNotFound),
+            reqwest::StatusCode::UNAUTHORIZED => return Err(PlaylistError::AuthRequired),
+            status if !status.is_success() => {
+                let body = response.text().await.unwrap_or_default();
+                return Err(PlaylistError::FetchFailed(format!(
+                    "HTTP {}: {}",
+                    status, body
+                )));
+            }
+            _ => {}
+        }
+
+        let page: CollectionPage = response
+            .json()
+            .await
+            .map_err(|_| PlaylistError::InvalidResponse)?;
+
+        if page.collection.is_empty() {
+            return Ok(None);
+        }
+
+        // Guard against an endpoint linking a page to itself
+        self.next = page.next_href.filter(|href| *href != url);
+        Ok(Some(parse_items(page.collection)))
+    }
+
+    /// Whether the last page fetched links to another one.
+    pub fn has_next(&self) -> bool {
+        self.next.is_some()
+    }
+
+    /// Fetches every remaining page.
+    pub async fn collect_all<T: DeserializeOwned>(mut self) -> Result<Vec<T>, PlaylistError> {
+        let mut items = Vec::new();
+        while let Some(page) = self.next_page().await? {
+            items.extend(page);
+        }
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paginated_url_adds_parameters() {
+        assert_eq!(
+            paginated_url("https://api.soundcloud.com/users/1/tracks", 50),
+            "https://api.soundcloud.com/users/1/tracks?linked_partitioning=1&limit=50"
+        );
+    }
+
+    #[test]
+    fn test_paginated_url_keeps_other_parameters() {
+        assert_eq!(
+            paginated_url(
+                "https://api.soundcloud.com/playlists/1/tracks?secret_token=s-abc&limit=10",
+                500
+            ),
+            "https://api.soundcloud.com/playlists/1/tracks?secret_token=s-abc&linked_partitioning=1&limit=200"
+        );
+    }
+
+    #[test]
+    fn test_collection_page_deserializes() {
+        let json = r#"{
+            "collection": [{"id": 1}, {"id": 2}],
+            "next_href": "https://api.soundcloud.com/users/1/tracks?cursor=abc"
+        }"#;
+        let page: CollectionPage = serde_json::from_str(json).unwrap();
+        assert_eq!(page.collection.len(), 2);
+        assert!(page.next_href.unwrap().contains("cursor=abc"));
+    }
+
+    #[test]
+    fn test_collection_page_last_page() {
+        let page: CollectionPage =
+            serde_json::from_str(r#"{"collection": [], "next_href": null}"#).unwrap();
+        assert!(page.collection.is_empty());
+        assert!(page.next_href.is_none());
+    }
+
+    #[test]
+    fn test_parse_items_skips_mismatches() {
+        #[derive(Deserialize)]
+        struct Item {
+            id: u64,
+        }
+
+        let items: Vec<Item> = parse_items(vec![
+            serde_json::json!({"id": 1}),
+            serde_json::json!({"title": "no id"}),
+            serde_json::json!({"id": 3}),
+        ]);
+        let ids: Vec<u64> = items.iter().map(|i| i.id).collect();
+        assert_eq!(ids, vec![1, 3]);
+    }
+}
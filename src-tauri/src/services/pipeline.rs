@@ -5,7 +5,10 @@ use tauri_plugin_shell::process::CommandChild;
 use tokio::sync::{watch, Mutex};
 
 use crate::models::error::PipelineError;
-use crate::services::metadata::{embed_metadata, TrackMetadata};
+use crate::services::artwork::ArtworkCache;
+use crate::services::history::{record_download, HistoryEntry};
+use crate::services::metadata::{embed_metadata, TagOptions, TrackMetadata};
+use crate::services::mix_splitter::{split_mix, MixSplitOptions};
 use crate::services::ytdlp::{download_track_to_mp3, PlaylistContext, TrackDownloadToMp3Config};
 
 /// Configuration for the full download pipeline.
@@ -16,6 +19,11 @@ pub struct PipelineConfig {
     pub metadata: TrackMetadata,
     /// Playlist context for track numbering (None for single tracks)
     pub playlist_context: Option<PlaylistContext>,
+    pub tag_options: TagOptions,
+    /// Artwork cache shared across the tracks of a queue
+    pub artwork_cache: Arc<ArtworkCache>,
+    /// Split the downloaded file into songs (None keeps it whole)
+    pub split_mix: Option<MixSplitOptions>,
 }
 
 /// Download a track and convert it to MP3.
@@ -32,7 +40,8 @@ pub struct PipelineConfig {
 /// * `skip_auth` - If true, skip OAuth authentication (download at 128kbps)
 ///
 /// # Returns
-/// The path to the final MP3 file on success.
+/// The downloaded files on success: the MP3 itself, or the pieces it was
+/// split into when the full mix was not kept.
 pub async fn download_and_convert<R: tauri::Runtime>(
     app: &AppHandle<R>,
     config: PipelineConfig,
@@ -40,14 +49,19 @@ pub async fn download_and_convert<R: tauri::Runtime>(
     cancel_rx: Option<watch::Receiver<bool>>,
     active_pid: Option<Arc<Mutex<Option<u32>>>>,
     skip_auth: bool,
-) -> Result<PathBuf, PipelineError> {
+) -> Result<Vec<PathBuf>, PipelineError> {
+    let mut metadata = config.metadata;
+    metadata
+        .track_id
+        .get_or_insert_with(|| config.track_id.clone());
+
     let download_config = TrackDownloadToMp3Config {
-        track_url: config.track_url,
-        track_id: config.track_id,
+        track_url: config.track_url.clone(),
+        track_id: config.track_id.clone(),
         output_dir: config.output_dir,
         playlist_context: config.playlist_context,
-        artist: config.metadata.artist.clone(),
-        title: config.metadata.title.clone(),
+        artist: metadata.artist.clone(),
+        title: metadata.title.clone(),
     };
 
     let output_path = download_track_to_mp3(
@@ -61,13 +75,68 @@ pub async fn download_and_convert<R: tauri::Runtime>(
     .await
     .map_err(PipelineError::Download)?;
 
+    let original = metadata.clone();
+
     // Embed metadata (graceful degradation - log errors but don't fail)
-    if let Err(e) = embed_metadata(&output_path, config.metadata).await {
+    if let Err(e) = embed_metadata(
+        &output_path,
+        metadata,
+        &config.tag_options,
+        &config.artwork_cache,
+    )
+    .await
+    {
         log::warn!("Metadata embedding failed: {}", e);
         // Continue - file without metadata is still playable
     }
 
-    Ok(output_path)
+    // Split mixes into songs (graceful degradation - the full mix is kept)
+    let mut pieces = vec![];
+    if let Some(options) = &config.split_mix {
+        match split_mix(
+            app,
+            &output_path,
+            &original,
+            options,
+            &config.tag_options,
+            &config.artwork_cache,
+        )
+        .await
+        {
+            // The mix was removed, so the pieces stand in for it
+            Ok(split) if !options.keep_original => pieces = split,
+            Ok(_) => {}
+            Err(e) => log::warn!("Mix splitting failed: {}", e),
+        }
+    }
+
+    if pieces.is_empty() {
+        record_download(
+            app,
+            &HistoryEntry::new(
+                &config.track_id,
+                &config.track_url,
+                &original.title,
+                &original.artist,
+                &output_path,
+            ),
+        );
+        return Ok(vec![output_path]);
+    }
+
+    for piece in &pieces {
+        record_download(
+            app,
+            &HistoryEntry::new(
+                "",
+                &config.track_url,
+                &piece.metadata.title,
+                &piece.metadata.artist,
+                &piece.path,
+            ),
+        );
+    }
+    Ok(pieces.into_iter().map(|piece| piece.path).collect())
 }
 
 #[cfg(test)]
@@ -83,6 +152,7 @@ mod tests {
             track_number: Some(1),
             total_tracks: Some(10),
             artwork_url: Some("https://example.com/art.jpg".to_string()),
+            ..Default::default()
         };
 
         let config = PipelineConfig {
@@ -91,6 +161,9 @@ mod tests {
             output_dir: PathBuf::from("/tmp/output"),
             metadata,
             playlist_context: None,
+            tag_options: TagOptions::default(),
+            artwork_cache: Arc::new(ArtworkCache::default()),
+            split_mix: None,
         };
 
         assert_eq!(config.track_url, "https://soundcloud.com/test/track");
@@ -110,6 +183,7 @@ mod tests {
             track_number: Some(5),
             total_tracks: Some(20),
             artwork_url: None,
+            ..Default::default()
         };
 
         let config = PipelineConfig {
@@ -121,6 +195,9 @@ mod tests {
                 track_position: 5,
                 total_tracks: 20,
             }),
+            tag_options: TagOptions::default(),
+            artwork_cache: Arc::new(ArtworkCache::default()),
+            split_mix: None,
         };
 
         assert!(config.playlist_context.is_some());
@@ -138,6 +215,7 @@ mod tests {
             track_number: None,
             total_tracks: None,
             artwork_url: None,
+            ..Default::default()
         };
 
         let config = PipelineConfig {
@@ -146,6 +224,9 @@ mod tests {
             output_dir: PathBuf::from("/tmp/output"),
             metadata,
             playlist_context: None,
+            tag_options: TagOptions::default(),
+            artwork_cache: Arc::new(ArtworkCache::default()),
+            split_mix: None,
         };
 
         assert!(config.metadata.album.is_none());
@@ -9,18 +9,22 @@
 //! 3. Parse playlist info with all track IDs
 //! 4. Batch-fetch full track details via API
 
-use std::sync::Mutex;
-use std::time::Duration;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 
-use futures::future::join_all;
+use futures::stream::{self, StreamExt};
 use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use specta::Type;
 use thiserror::Error;
-use tokio::time::sleep;
 
+use crate::models::url::CollectionKind;
+use crate::services::http;
+use crate::services::metadata_cache::MetadataCache;
 use crate::services::oauth::{get_app_token, get_client_secret};
+use crate::services::pagination::{Pages, DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE};
+use crate::services::rate_limit::MAX_CONCURRENT_REQUESTS;
 use crate::services::storage::{current_timestamp, is_token_expired_or_expiring, load_tokens};
 
 #[derive(Debug, Deserialize)]
@@ -54,7 +58,7 @@ pub enum PlaylistError {
 }
 
 /// User information from SoundCloud API (public).
-#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
 pub struct UserInfo {
     pub username: String,
 }
@@ -73,6 +77,8 @@ struct RawUserInfo {
 pub struct PublisherMetadata {
     /// The actual artist name (different from uploader username for label content)
     pub artist: Option<String>,
+    /// International Standard Recording Code, when provided by the distributor.
+    pub isrc: Option<String>,
 }
 
 /// Raw track information from SoundCloud API.
@@ -87,10 +93,69 @@ struct RawTrackInfo {
     pub duration: u64,
     /// Publisher metadata containing the actual artist name for label content.
     pub publisher_metadata: Option<PublisherMetadata>,
+    pub genre: Option<String>,
+    /// Upload date (`2023/05/01 12:00:00 +0000` or ISO 8601).
+    pub created_at: Option<String>,
+    /// Release date set by the uploader, preferred over `created_at` when present.
+    pub release_date: Option<String>,
+    pub label_name: Option<String>,
+    pub description: Option<String>,
+    pub permalink_url: Option<String>,
+    pub bpm: Option<f64>,
+    /// Number of plays, used to order popular tracks.
+    #[serde(default)]
+    pub playback_count: Option<u64>,
+    /// Secret token of a private track, only returned to its owner.
+    #[serde(default)]
+    pub secret_token: Option<String>,
+    /// API URL of a private track including its secret token
+    /// (`.../tracks/1?secret_token=s-...`), returned instead of `secret_token`
+    /// for tracks listed in a private playlist.
+    #[serde(default)]
+    pub secret_uri: Option<String>,
+    /// Whether the uploader enabled downloads of the original file.
+    #[serde(default)]
+    pub downloadable: Option<bool>,
+    #[serde(default)]
+    pub streamable: Option<bool>,
+    /// `ALLOW`, `MONETIZE`, `SNIP` or `BLOCK`.
+    #[serde(default)]
+    pub policy: Option<String>,
+    /// `playable`, `preview` or `blocked`.
+    #[serde(default)]
+    pub access: Option<String>,
+}
+
+/// Whether a track can be downloaded in full, judged from its metadata.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum Playability {
+    #[default]
+    Playable,
+    /// Only a 30 second preview is available (e.g. Go+ tracks).
+    PreviewOnly,
+    /// The track cannot be streamed, so its download will fail.
+    Blocked,
+}
+
+impl Playability {
+    fn from_raw(streamable: Option<bool>, policy: Option<&str>, access: Option<&str>) -> Self {
+        let is = |value: Option<&str>, expected: &str| {
+            value.is_some_and(|v| v.eq_ignore_ascii_case(expected))
+        };
+
+        if streamable == Some(false) || is(access, "blocked") || is(policy, "BLOCK") {
+            Playability::Blocked
+        } else if is(access, "preview") || is(policy, "SNIP") {
+            Playability::PreviewOnly
+        } else {
+            Playability::Playable
+        }
+    }
 }
 
 /// Track information from SoundCloud API.
-#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
 pub struct TrackInfo {
     pub id: u64,
     pub title: String,
@@ -98,20 +163,79 @@ pub struct TrackInfo {
     pub artwork_url: Option<String>,
     /// Duration in milliseconds.
     pub duration: u64,
+    #[serde(default)]
+    pub genre: Option<String>,
+    /// Release date, falling back to the upload date.
+    #[serde(default)]
+    pub release_date: Option<String>,
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub isrc: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub permalink_url: Option<String>,
+    #[serde(default)]
+    pub bpm: Option<f64>,
+    /// Secret token (`s-...`) of a private track, needed to download it.
+    #[serde(default)]
+    pub secret_token: Option<String>,
+    #[serde(default)]
+    pub downloadable: Option<bool>,
+    #[serde(default)]
+    pub streamable: Option<bool>,
+    /// `ALLOW`, `MONETIZE`, `SNIP` or `BLOCK`.
+    #[serde(default)]
+    pub policy: Option<String>,
+    /// `playable`, `preview` or `blocked`.
+    #[serde(default)]
+    pub access: Option<String>,
+    /// Summary of `streamable`, `policy` and `access`, so tracks that will
+    /// fail or only download as a preview can be left out of the queue.
+    #[serde(default)]
+    pub playability: Playability,
+    /// 1-based position in the playlist the track was loaded from. Tracks
+    /// that could not be fetched keep their slot, so it can skip numbers.
+    #[serde(default)]
+    pub playlist_position: Option<u32>,
+}
+
+/// Treats empty strings from the API the same as missing values.
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.trim().is_empty())
+}
+
+/// Reads the `secret_token` query parameter of a track's `secret_uri`.
+fn secret_token_from_uri(uri: &str) -> Option<String> {
+    let url = url::Url::parse(uri).ok()?;
+    let token = url
+        .query_pairs()
+        .find(|(key, _)| key == "secret_token")
+        .map(|(_, value)| value.into_owned());
+    non_empty(token)
 }
 
 impl From<RawTrackInfo> for TrackInfo {
     fn from(raw: RawTrackInfo) -> Self {
+        let (publisher_artist, isrc) = match raw.publisher_metadata {
+            Some(pm) => (pm.artist, pm.isrc),
+            None => (None, None),
+        };
+
         // Use publisher_metadata.artist if available, otherwise fall back to user.username
-        let artist_name = raw
-            .publisher_metadata
-            .and_then(|pm| pm.artist)
+        let artist_name = publisher_artist
             .filter(|a| !a.is_empty())
             .unwrap_or_else(|| raw.user.username.clone());
 
         // Use track artwork if available, otherwise fall back to user avatar
         let artwork = raw.artwork_url.or(raw.user.avatar_url);
 
+        let policy = non_empty(raw.policy).map(|p| p.to_uppercase());
+        let access = non_empty(raw.access).map(|a| a.to_lowercase());
+        let playability =
+            Playability::from_raw(raw.streamable, policy.as_deref(), access.as_deref());
+
         TrackInfo {
             id: raw.id,
             title: raw.title,
@@ -120,6 +244,21 @@ impl From<RawTrackInfo> for TrackInfo {
             },
             artwork_url: artwork,
             duration: raw.duration,
+            genre: non_empty(raw.genre),
+            release_date: non_empty(raw.release_date).or(non_empty(raw.created_at)),
+            label: non_empty(raw.label_name),
+            isrc: non_empty(isrc),
+            description: non_empty(raw.description),
+            permalink_url: non_empty(raw.permalink_url),
+            bpm: raw.bpm.filter(|bpm| *bpm > 0.0),
+            secret_token: non_empty(raw.secret_token)
+                .or_else(|| raw.secret_uri.as_deref().and_then(secret_token_from_uri)),
+            downloadable: raw.downloadable,
+            streamable: raw.streamable,
+            policy,
+            access,
+            playability,
+            playlist_position: None,
         }
     }
 }
@@ -134,6 +273,44 @@ struct RawPlaylistInfo {
     pub artwork_url: Option<String>,
     pub track_count: u32,
     pub tracks: Vec<RawTrackInfo>,
+    #[serde(default)]
+    pub secret_token: Option<String>,
+    #[serde(default)]
+    pub permalink_url: Option<String>,
+    #[serde(flatten)]
+    pub set: RawSetMetadata,
+}
+
+/// Album-related fields shared by API and hydration playlist payloads.
+#[derive(Debug, Clone, Default, Deserialize)]
+struct RawSetMetadata {
+    /// `playlist`, `album`, `ep`, `single` or `compilation`.
+    pub set_type: Option<String>,
+    pub release_date: Option<String>,
+    pub label_name: Option<String>,
+    pub genre: Option<String>,
+}
+
+impl RawSetMetadata {
+    /// Builds album metadata when the set is an album or EP.
+    fn into_album(self, owner: &str, artwork_url: Option<&String>) -> Option<AlbumMetadata> {
+        let is_album = self
+            .set_type
+            .as_deref()
+            .is_some_and(|t| t.eq_ignore_ascii_case("album") || t.eq_ignore_ascii_case("ep"));
+
+        if !is_album {
+            return None;
+        }
+
+        Some(AlbumMetadata {
+            artist: owner.to_string(),
+            artwork_url: artwork_url.cloned(),
+            release_date: non_empty(self.release_date),
+            label: non_empty(self.label_name),
+            genre: non_empty(self.genre),
+        })
+    }
 }
 
 /// Hydration data structure from SoundCloud web page.
@@ -152,10 +329,66 @@ struct HydrationPlaylist {
     artwork_url: Option<String>,
     track_count: u32,
     tracks: Vec<Value>,
+    #[serde(default)]
+    secret_token: Option<String>,
+    #[serde(default)]
+    permalink_url: Option<String>,
+    #[serde(flatten)]
+    set: RawSetMetadata,
+}
+
+/// Playlist-level metadata used as album tags when downloading an album/EP set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct AlbumMetadata {
+    /// Album artist (the playlist owner).
+    pub artist: String,
+    /// Playlist artwork, used as cover for every track.
+    pub artwork_url: Option<String>,
+    pub release_date: Option<String>,
+    pub label: Option<String>,
+    pub genre: Option<String>,
+}
+
+/// Why a playlist track could not be fetched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum UnavailableReason {
+    /// Not available in the user's country (HTTP 403).
+    GeoBlocked,
+    /// Deleted by the uploader or taken down (HTTP 404).
+    Removed,
+    /// Made private after being added to the playlist (HTTP 401).
+    Private,
+    /// Left out by the API for another reason, e.g. a blocked uploader.
+    ApiFiltered,
+    /// The request for the track kept failing (server or network error).
+    Unknown,
+}
+
+impl UnavailableReason {
+    /// Maps the error of a failed [`request_track`] to a reason, or `None`
+    /// when the request itself failed and says nothing about the track.
+    fn from_error(error: &PlaylistError) -> Option<Self> {
+        match error {
+            PlaylistError::GeoBlocked => Some(UnavailableReason::GeoBlocked),
+            PlaylistError::TrackNotFound => Some(UnavailableReason::Removed),
+            PlaylistError::AuthRequired => Some(UnavailableReason::Private),
+            _ => None,
+        }
+    }
+}
+
+/// A playlist track that could not be fetched.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct UnavailableTrack {
+    pub id: u64,
+    /// 1-based position in the playlist.
+    pub position: u32,
+    pub reason: UnavailableReason,
 }
 
 /// Playlist information from SoundCloud API.
-#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
 pub struct PlaylistInfo {
     pub id: u64,
     pub title: String,
@@ -163,10 +396,29 @@ pub struct PlaylistInfo {
     pub artwork_url: Option<String>,
     pub track_count: u32,
     pub tracks: Vec<TrackInfo>,
+    #[serde(default)]
+    pub set_type: Option<String>,
+    /// Album metadata, present when the set is an album or EP.
+    #[serde(default)]
+    pub album: Option<AlbumMetadata>,
+    /// Secret token (`s-...`) of a private playlist.
+    #[serde(default)]
+    pub secret_token: Option<String>,
+    /// Playlist page, used to fetch the full track list of search results.
+    #[serde(default)]
+    pub permalink_url: Option<String>,
+    /// Tracks listed in the playlist that could not be fetched.
+    #[serde(default)]
+    pub unavailable: Vec<UnavailableTrack>,
 }
 
 impl From<RawPlaylistInfo> for PlaylistInfo {
     fn from(raw: RawPlaylistInfo) -> Self {
+        let set_type = non_empty(raw.set.set_type.clone());
+        let album = raw
+            .set
+            .into_album(&raw.user.username, raw.artwork_url.as_ref());
+
         PlaylistInfo {
             id: raw.id,
             title: raw.title,
@@ -175,11 +427,72 @@ impl From<RawPlaylistInfo> for PlaylistInfo {
             },
             artwork_url: raw.artwork_url,
             track_count: raw.track_count,
-            tracks: raw.tracks.into_iter().map(TrackInfo::from).collect(),
+            tracks: numbered(raw.tracks.into_iter().map(TrackInfo::from).collect()),
+            set_type,
+            album,
+            secret_token: non_empty(raw.secret_token),
+            permalink_url: non_empty(raw.permalink_url),
+            unavailable: vec![],
+        }
+    }
+}
+
+/// Receives each batch of tracks as it is fetched.
+pub type FetchProgress<'a> = &'a (dyn Fn(&[TrackInfo]) + Send + Sync);
+
+/// Options for fetching playlists and collections.
+#[derive(Clone, Copy)]
+pub struct FetchOptions<'a> {
+    /// Items per page for paginated endpoints.
+    pub page_size: u32,
+    /// Called with partial results while large collections load.
+    pub progress: Option<FetchProgress<'a>>,
+    /// Tracks found here are not requested from the API again.
+    pub cache: Option<&'a Arc<MetadataCache>>,
+}
+
+impl Default for FetchOptions<'_> {
+    fn default() -> Self {
+        Self {
+            page_size: DEFAULT_PAGE_SIZE,
+            progress: None,
+            cache: None,
         }
     }
 }
 
+impl FetchOptions<'_> {
+    fn report(&self, tracks: &[TrackInfo]) {
+        if let (Some(progress), false) = (self.progress, tracks.is_empty()) {
+            progress(tracks);
+        }
+    }
+
+    /// Looks up `ids` in the metadata cache, off the async runtime.
+    async fn cached_tracks(&self, ids: &[u64]) -> Vec<TrackInfo> {
+        let Some(cache) = self.cache else {
+            return vec![];
+        };
+        let cache = Arc::clone(cache);
+        let ids = ids.to_vec();
+        tokio::task::spawn_blocking(move || ids.iter().filter_map(|id| cache.track(*id)).collect())
+            .await
+            .unwrap_or_default()
+    }
+}
+
+/// Event payload for tracks arriving while a playlist or collection loads.
+#[derive(Clone, Debug, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistFetchProgressEvent {
+    /// The URL (or `me/...` collection) being fetched.
+    pub source: String,
+    /// Tracks fetched so far.
+    pub fetched: u32,
+    /// Tracks of this batch, not necessarily in playlist order.
+    pub tracks: Vec<TrackInfo>,
+}
+
 /// Cached app token for unauthenticated API requests.
 struct CachedAppToken {
     token: String,
@@ -259,17 +572,15 @@ async fn resolve_url<T: serde::de::DeserializeOwned>(
     url: &str,
     access_token: &str,
 ) -> Result<T, PlaylistError> {
-    let client = reqwest::Client::new();
+    let http = http::client();
     let resolve_url = format!(
         "https://api.soundcloud.com/resolve?url={}",
         urlencoding::encode(url),
     );
     let auth_header = format!("OAuth {}", access_token);
 
-    let response = client
-        .get(&resolve_url)
-        .header("Authorization", &auth_header)
-        .send()
+    let response = http
+        .send(http.get(&resolve_url).header("Authorization", &auth_header))
         .await?;
 
     if response.status() == reqwest::StatusCode::NOT_FOUND {
@@ -299,10 +610,8 @@ async fn resolve_url<T: serde::de::DeserializeOwned>(
         if let Some(location) = redirect.location {
             log::info!("[soundcloud] Following redirect to: {}", location);
 
-            let redirect_response = client
-                .get(&location)
-                .header("Authorization", &auth_header)
-                .send()
+            let redirect_response = http
+                .send(http.get(&location).header("Authorization", &auth_header))
                 .await?;
 
             if redirect_response.status() == reqwest::StatusCode::UNAUTHORIZED {
@@ -335,15 +644,8 @@ async fn resolve_url<T: serde::de::DeserializeOwned>(
 /// page structure. If hydration extraction fails, callers should fall back to the
 /// OAuth API via `fetch_playlist_info_via_api`.
 async fn fetch_hydration_data(url: &str) -> Result<Vec<HydrationItem>, PlaylistError> {
-    let client = reqwest::Client::new();
-    let response = client
-        .get(url)
-        .header(
-            "User-Agent",
-            "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/537.36",
-        )
-        .send()
-        .await?;
+    let http = http::client();
+    let response = http.send(http.get(url)).await?;
 
     if !response.status().is_success() {
         return Err(PlaylistError::FetchFailed(format!(
@@ -470,23 +772,36 @@ fn extract_full_tracks_from_hydration(tracks: &[Value]) -> Vec<TrackInfo> {
 }
 
 /// Fetches track details by IDs using the batch API endpoint.
-/// SoundCloud allows fetching up to 50 tracks per request.
-/// Includes rate limiting (100ms delay between batches) to avoid hitting API limits.
+/// SoundCloud allows fetching up to 50 tracks per request; requests are
+/// paced by the shared API rate limiter.
 async fn fetch_tracks_by_ids(
     ids: &[u64],
     access_token: &str,
+    options: &FetchOptions<'_>,
 ) -> Result<Vec<TrackInfo>, PlaylistError> {
     if ids.is_empty() {
         return Ok(vec![]);
     }
 
-    let client = reqwest::Client::new();
-    let mut all_tracks = Vec::new();
-    let chunks: Vec<_> = ids.chunks(50).collect();
-    let total_chunks = chunks.len();
+    let mut all_tracks = options.cached_tracks(ids).await;
+    if !all_tracks.is_empty() {
+        log::info!(
+            "[soundcloud] {} of {} tracks served from the metadata cache",
+            all_tracks.len(),
+            ids.len()
+        );
+        options.report(&all_tracks);
+    }
+    let cached_ids: std::collections::HashSet<u64> = all_tracks.iter().map(|t| t.id).collect();
+    let ids: Vec<u64> = ids
+        .iter()
+        .filter(|id| !cached_ids.contains(id))
+        .copied()
+        .collect();
+
+    let http = http::client();
 
-    // Process in batches of 50 with rate limiting
-    for (i, chunk) in chunks.into_iter().enumerate() {
+    for chunk in ids.chunks(50) {
         let ids_param: String = chunk
             .iter()
             .map(|id| id.to_string())
@@ -494,16 +809,20 @@ async fn fetch_tracks_by_ids(
             .join(",");
 
         let url = format!("https://api.soundcloud.com/tracks?ids={}", ids_param);
-        let response = client
-            .get(&url)
-            .header("Authorization", format!("OAuth {}", access_token))
-            .send()
+        let response = http
+            .send(
+                http.get(&url)
+                    .header("Authorization", format!("OAuth {}", access_token)),
+            )
             .await?;
 
         if response.status().is_success() {
             match response.json::<Vec<RawTrackInfo>>().await {
                 Ok(raw_tracks) => {
-                    all_tracks.extend(raw_tracks.into_iter().map(TrackInfo::from));
+                    let tracks: Vec<TrackInfo> =
+                        raw_tracks.into_iter().map(TrackInfo::from).collect();
+                    options.report(&tracks);
+                    all_tracks.extend(tracks);
                 }
                 Err(e) => {
                     log::warn!(
@@ -520,44 +839,139 @@ async fn fetch_tracks_by_ids(
                 response.status()
             );
         }
-
-        // Rate limiting: small delay between batches (except after the last one)
-        if i < total_chunks - 1 {
-            sleep(Duration::from_millis(100)).await;
-        }
     }
 
     Ok(all_tracks)
 }
 
+/// Fetches a track from `/tracks/{id}`, mapping error statuses to the
+/// matching `PlaylistError`.
+async fn request_track(id: u64, access_token: &str) -> Result<TrackInfo, PlaylistError> {
+    let http = http::client();
+    let response = http
+        .send(
+            http.get(format!("https://api.soundcloud.com/tracks/{}", id))
+                .header("Authorization", format!("OAuth {}", access_token)),
+        )
+        .await?;
+
+    match response.status() {
+        reqwest::StatusCode::NOT_FOUND | reqwest::StatusCode::GONE => {
+            Err(PlaylistError::TrackNotFound)
+        }
+        reqwest::StatusCode::FORBIDDEN => Err(PlaylistError::GeoBlocked),
+        reqwest::StatusCode::UNAUTHORIZED => Err(PlaylistError::AuthRequired),
+        status if !status.is_success() => {
+            let body = response.text().await.unwrap_or_default();
+            Err(PlaylistError::FetchFailed(format!(
+                "HTTP {}: {}",
+                status, body
+            )))
+        }
+        _ => {
+            let raw: RawTrackInfo = response
+                .json()
+                .await
+                .map_err(|_| PlaylistError::InvalidResponse)?;
+            Ok(TrackInfo::from(raw))
+        }
+    }
+}
+
 /// Fetches a single track by ID (fallback for tracks not in batch response).
-async fn fetch_track_by_id(id: u64, access_token: &str) -> Option<TrackInfo> {
-    let client = reqwest::Client::new();
-    let url = format!("https://api.soundcloud.com/tracks/{}", id);
-
-    match client
-        .get(&url)
-        .header("Authorization", format!("OAuth {}", access_token))
-        .send()
-        .await
-    {
-        Ok(response) if response.status().is_success() => response
-            .json::<RawTrackInfo>()
-            .await
-            .ok()
-            .map(TrackInfo::from),
-        _ => None,
+async fn fetch_track_by_id(id: u64, access_token: &str) -> Result<TrackInfo, PlaylistError> {
+    request_track(id, access_token).await.inspect_err(|e| {
+        log::warn!("[soundcloud] Could not fetch track {}: {}", id, e);
+    })
+}
+
+/// Fetches multiple tracks by ID in parallel (for tracks filtered by batch API),
+/// at most [`MAX_CONCURRENT_REQUESTS`] at a time.
+///
+/// Returns the fetched tracks and the reason each other track is
+/// unavailable; tracks whose request failed without telling why (server or
+/// network errors) are [`UnavailableReason::Unknown`]. Fails only when no
+/// request got through at all.
+async fn fetch_tracks_by_ids_parallel(
+    ids: &[u64],
+    access_token: &str,
+) -> Result<(Vec<TrackInfo>, HashMap<u64, UnavailableReason>), PlaylistError> {
+    let results: Vec<(u64, Result<TrackInfo, PlaylistError>)> = stream::iter(ids.to_vec())
+        .map(|id| async move { (id, fetch_track_by_id(id, access_token).await) })
+        .buffer_unordered(MAX_CONCURRENT_REQUESTS)
+        .collect()
+        .await;
+
+    collect_track_results(results)
+}
+
+/// Splits per-track fetch results into tracks and unavailable reasons, see
+/// [`fetch_tracks_by_ids_parallel`].
+fn collect_track_results(
+    results: Vec<(u64, Result<TrackInfo, PlaylistError>)>,
+) -> Result<(Vec<TrackInfo>, HashMap<u64, UnavailableReason>), PlaylistError> {
+    let mut tracks = Vec::new();
+    let mut failures = HashMap::new();
+    let mut last_error = None;
+    for (id, result) in results {
+        match result {
+            Ok(track) => tracks.push(track),
+            Err(e) => {
+                let reason = UnavailableReason::from_error(&e).unwrap_or_else(|| {
+                    last_error = Some(e);
+                    UnavailableReason::Unknown
+                });
+                failures.insert(id, reason);
+            }
+        }
+    }
+
+    let all_errored = failures
+        .values()
+        .all(|reason| *reason == UnavailableReason::Unknown);
+    match last_error {
+        Some(e) if tracks.is_empty() && all_errored => Err(e),
+        _ => Ok((tracks, failures)),
     }
 }
 
-/// Fetches multiple tracks by ID in parallel (for tracks filtered by batch API).
-async fn fetch_tracks_by_ids_parallel(ids: &[u64], access_token: &str) -> Vec<TrackInfo> {
-    let futures: Vec<_> = ids
-        .iter()
-        .map(|id| fetch_track_by_id(*id, access_token))
-        .collect();
+/// Sets the playlist position of tracks listed in playlist order.
+fn numbered(mut tracks: Vec<TrackInfo>) -> Vec<TrackInfo> {
+    for (i, track) in tracks.iter_mut().enumerate() {
+        track.playlist_position = Some((i + 1) as u32);
+    }
+    tracks
+}
 
-    join_all(futures).await.into_iter().flatten().collect()
+/// Puts fetched tracks in playlist order and records the ones that are
+/// missing, keeping the real position of every track.
+fn order_playlist_tracks(
+    ids: &[u64],
+    mut fetched: HashMap<u64, TrackInfo>,
+    failures: &HashMap<u64, UnavailableReason>,
+) -> (Vec<TrackInfo>, Vec<UnavailableTrack>) {
+    let mut tracks = Vec::with_capacity(ids.len());
+    let mut unavailable = Vec::new();
+
+    for (i, id) in ids.iter().enumerate() {
+        let position = (i + 1) as u32;
+        match fetched.remove(id) {
+            Some(mut track) => {
+                track.playlist_position = Some(position);
+                tracks.push(track);
+            }
+            None => unavailable.push(UnavailableTrack {
+                id: *id,
+                position,
+                // The batch endpoint silently drops tracks it won't return
+                reason: failures
+                    .get(id)
+                    .copied()
+                    .unwrap_or(UnavailableReason::ApiFiltered),
+            }),
+        }
+    }
+    (tracks, unavailable)
 }
 
 /// Validates that a URL is a SoundCloud URL.
@@ -568,19 +982,161 @@ fn is_valid_soundcloud_url(url: &str) -> bool {
         || url.starts_with("http://www.soundcloud.com/")
 }
 
+/// Fetches the tracks of a playlist page by page.
+async fn fetch_playlist_tracks(
+    id: u64,
+    secret_token: Option<&str>,
+    access_token: &str,
+    options: &FetchOptions<'_>,
+) -> Result<Vec<TrackInfo>, PlaylistError> {
+    let mut endpoint = format!("https://api.soundcloud.com/playlists/{}/tracks", id);
+    if let Some(token) = secret_token {
+        endpoint.push_str(&format!("?secret_token={}", urlencoding::encode(token)));
+    }
+
+    fetch_track_pages(&endpoint, access_token, options, Some).await
+}
+
+/// A playlist as listed by `api-v2`, which gives the ID of every track,
+/// including the ones the public API leaves out.
+#[derive(Debug, Deserialize)]
+struct RawPlaylistTrackIds {
+    tracks: Vec<Value>,
+}
+
+/// Fetches the IDs of every track in a playlist, in playlist order.
+async fn fetch_playlist_track_ids(
+    id: u64,
+    secret_token: Option<&str>,
+    access_token: &str,
+) -> Result<Vec<u64>, PlaylistError> {
+    let mut endpoint = format!("https://api-v2.soundcloud.com/playlists/{}", id);
+    if let Some(token) = secret_token {
+        endpoint.push_str(&format!("?secret_token={}", urlencoding::encode(token)));
+    }
+
+    let http = http::client();
+    let response = http
+        .send(
+            http.get(&endpoint)
+                .header("Authorization", format!("OAuth {}", access_token)),
+        )
+        .await?;
+    let listing: RawPlaylistTrackIds =
+        http::handle_json_response(response, PlaylistError::FetchFailed).await?;
+    Ok(extract_track_ids(&listing.tracks))
+}
+
+/// Completes a playlist from the API whose `tracks` array was cut short,
+/// as happens for playlists with thousands of tracks or tracks the API
+/// won't return.
+///
+/// The fetched tracks are matched against the playlist's full ID list
+/// (`listed_ids` when the caller already has it) so they keep their real
+/// positions and the missing ones are reported as unavailable.
+async fn complete_playlist(
+    raw: RawPlaylistInfo,
+    listed_ids: Option<Vec<u64>>,
+    access_token: &str,
+    options: &FetchOptions<'_>,
+) -> Result<PlaylistInfo, PlaylistError> {
+    if raw.tracks.len() >= raw.track_count as usize {
+        let playlist = PlaylistInfo::from(raw);
+        options.report(&playlist.tracks);
+        return Ok(playlist);
+    }
+
+    log::info!(
+        "[soundcloud] Playlist '{}' lists {} of {} tracks, paging through the rest",
+        raw.title,
+        raw.tracks.len(),
+        raw.track_count
+    );
+    let tracks =
+        fetch_playlist_tracks(raw.id, raw.secret_token.as_deref(), access_token, options).await?;
+
+    let ids = match listed_ids {
+        Some(ids) => Some(ids),
+        None => fetch_playlist_track_ids(raw.id, raw.secret_token.as_deref(), access_token)
+            .await
+            .inspect_err(|e| {
+                log::warn!(
+                    "[soundcloud] Could not list track IDs of '{}': {}",
+                    raw.title,
+                    e
+                );
+            })
+            .ok(),
+    };
+
+    let mut playlist = PlaylistInfo::from(RawPlaylistInfo {
+        tracks: vec![],
+        ..raw
+    });
+    match ids {
+        Some(ids) => {
+            let mut fetched: HashMap<u64, TrackInfo> =
+                tracks.into_iter().map(|t| (t.id, t)).collect();
+
+            // Ask for each missing track, to learn why it was left out
+            let missing: Vec<u64> = ids
+                .iter()
+                .filter(|id| !fetched.contains_key(id))
+                .copied()
+                .collect();
+            let mut failures = HashMap::new();
+            if !missing.is_empty() {
+                match fetch_tracks_by_ids_parallel(&missing, access_token).await {
+                    Ok((found, missing_failures)) => {
+                        options.report(&found);
+                        fetched.extend(found.into_iter().map(|t| (t.id, t)));
+                        failures = missing_failures;
+                    }
+                    Err(e) => {
+                        log::warn!("[soundcloud] Could not look up missing tracks: {}", e);
+                        failures = missing
+                            .iter()
+                            .map(|id| (*id, UnavailableReason::Unknown))
+                            .collect();
+                    }
+                }
+            }
+
+            (playlist.tracks, playlist.unavailable) =
+                order_playlist_tracks(&ids, fetched, &failures);
+        }
+        // Without the ID list, positions only follow the returned tracks
+        None => playlist.tracks = numbered(tracks),
+    }
+    Ok(playlist)
+}
+
 /// Fetches playlist info using the OAuth API (fallback for private playlists).
-/// This is used when web hydration fails (e.g., for private content).
-async fn fetch_playlist_info_via_api(url: &str) -> Result<PlaylistInfo, PlaylistError> {
+/// This is used when web hydration fails (e.g., for private content) or
+/// doesn't list every track.
+///
+/// `listed_ids` is the playlist's full track ID list, when already known.
+async fn fetch_playlist_info_via_api(
+    url: &str,
+    listed_ids: Option<Vec<u64>>,
+    options: &FetchOptions<'_>,
+) -> Result<PlaylistInfo, PlaylistError> {
     let token = get_access_token().await?;
     log::info!(
         "[soundcloud] Fetching playlist via OAuth API for URL: {}",
         url
     );
     let raw: RawPlaylistInfo = resolve_url(url, &token).await?;
-    Ok(PlaylistInfo::from(raw))
+    complete_playlist(raw, listed_ids, &token, options).await
 }
 
-pub async fn fetch_playlist_info(url: &str) -> Result<PlaylistInfo, PlaylistError> {
+/// Fetches a playlist with all its tracks.
+///
+/// Partial results are passed to `options.progress` as batches arrive.
+pub async fn fetch_playlist_info(
+    url: &str,
+    options: &FetchOptions<'_>,
+) -> Result<PlaylistInfo, PlaylistError> {
     // Validate URL before making any requests
     if !is_valid_soundcloud_url(url) {
         return Err(PlaylistError::FetchFailed(
@@ -602,7 +1158,7 @@ pub async fn fetch_playlist_info(url: &str) -> Result<PlaylistInfo, PlaylistErro
                 "[soundcloud] Web hydration failed: {}, falling back to OAuth API",
                 e
             );
-            return fetch_playlist_info_via_api(url).await;
+            return fetch_playlist_info_via_api(url, None, options).await;
         }
     };
 
@@ -613,7 +1169,7 @@ pub async fn fetch_playlist_info(url: &str) -> Result<PlaylistInfo, PlaylistErro
                 "[soundcloud] Failed to extract playlist from hydration: {}, falling back to OAuth API",
                 e
             );
-            return fetch_playlist_info_via_api(url).await;
+            return fetch_playlist_info_via_api(url, None, options).await;
         }
     };
 
@@ -633,6 +1189,20 @@ pub async fn fetch_playlist_info(url: &str) -> Result<PlaylistInfo, PlaylistErro
         all_track_ids.len()
     );
 
+    // Very large playlists don't list every track ID in the page
+    if all_track_ids.len() < playlist_data.track_count as usize {
+        log::info!("[soundcloud] Hydration is missing track IDs, falling back to OAuth API");
+        return fetch_playlist_info_via_api(url, None, options).await;
+    }
+    // Private tracks can't be fetched by ID without their secret token, but
+    // the playlist's API listing includes them with their tokens
+    let is_private = non_empty(playlist_data.secret_token.clone()).is_some();
+    if is_private && hydration_tracks.len() < all_track_ids.len() {
+        log::info!("[soundcloud] Private playlist lists track IDs only, falling back to OAuth API");
+        return fetch_playlist_info_via_api(url, Some(all_track_ids), options).await;
+    }
+    options.report(&hydration_tracks);
+
     // Step 3: Determine which track IDs need to be fetched
     let hydration_track_ids: std::collections::HashSet<u64> =
         hydration_tracks.iter().map(|t| t.id).collect();
@@ -644,7 +1214,7 @@ pub async fn fetch_playlist_info(url: &str) -> Result<PlaylistInfo, PlaylistErro
 
     // Step 4: Get access token and fetch missing tracks
     let token = get_access_token().await?;
-    let mut fetched_tracks = fetch_tracks_by_ids(&missing_ids, &token).await?;
+    let mut fetched_tracks = fetch_tracks_by_ids(&missing_ids, &token, options).await?;
 
     log::info!(
         "[soundcloud] Batch API returned {} of {} missing tracks",
@@ -660,48 +1230,51 @@ pub async fn fetch_playlist_info(url: &str) -> Result<PlaylistInfo, PlaylistErro
         .copied()
         .collect();
 
+    let mut failures = HashMap::new();
     if !still_missing.is_empty() {
         log::info!(
             "[soundcloud] Fetching {} tracks in parallel (filtered by batch API)",
             still_missing.len()
         );
-        let parallel_tracks = fetch_tracks_by_ids_parallel(&still_missing, &token).await;
+        let (parallel_tracks, parallel_failures) =
+            fetch_tracks_by_ids_parallel(&still_missing, &token).await?;
+        options.report(&parallel_tracks);
         log::info!(
             "[soundcloud] Parallel fetch returned {} tracks",
             parallel_tracks.len()
         );
         fetched_tracks.extend(parallel_tracks);
-
-        // Log any tracks that couldn't be fetched
-        let final_fetched_ids: std::collections::HashSet<u64> =
-            fetched_tracks.iter().map(|t| t.id).collect();
-        for id in &still_missing {
-            if !final_fetched_ids.contains(id) {
-                log::warn!("[soundcloud] Could not fetch track {}", id);
-            }
-        }
+        failures = parallel_failures;
     }
 
     // Step 6: Combine all tracks and sort by original order
-    let mut all_tracks: Vec<TrackInfo> = hydration_tracks;
-    all_tracks.extend(fetched_tracks);
-
-    // Create a map for quick lookup
-    let track_map: std::collections::HashMap<u64, TrackInfo> =
-        all_tracks.into_iter().map(|t| (t.id, t)).collect();
-
-    // Reconstruct in original order
-    let ordered_tracks: Vec<TrackInfo> = all_track_ids
-        .iter()
-        .filter_map(|id| track_map.get(id).cloned())
+    let track_map: HashMap<u64, TrackInfo> = hydration_tracks
+        .into_iter()
+        .chain(fetched_tracks)
+        .map(|t| (t.id, t))
         .collect();
-
+    let (ordered_tracks, unavailable) = order_playlist_tracks(&all_track_ids, track_map, &failures);
+
+    for track in &unavailable {
+        log::warn!(
+            "[soundcloud] Could not fetch track {} at position {}: {:?}",
+            track.id,
+            track.position,
+            track.reason
+        );
+    }
     log::info!(
         "[soundcloud] Final playlist has {} of {} tracks",
         ordered_tracks.len(),
         playlist_data.track_count
     );
 
+    let set_type = non_empty(playlist_data.set.set_type.clone());
+    let album = playlist_data.set.into_album(
+        &playlist_data.user.username,
+        playlist_data.artwork_url.as_ref(),
+    );
+
     Ok(PlaylistInfo {
         id: playlist_data.id,
         title: playlist_data.title,
@@ -711,6 +1284,11 @@ pub async fn fetch_playlist_info(url: &str) -> Result<PlaylistInfo, PlaylistErro
         artwork_url: playlist_data.artwork_url,
         track_count: playlist_data.track_count,
         tracks: ordered_tracks,
+        set_type,
+        album,
+        secret_token: non_empty(playlist_data.secret_token),
+        permalink_url: non_empty(playlist_data.permalink_url),
+        unavailable,
     })
 }
 
@@ -721,82 +1299,642 @@ pub async fn fetch_track_info(url: &str) -> Result<TrackInfo, PlaylistError> {
     Ok(TrackInfo::from(raw))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Fetches track info by SoundCloud track ID.
+pub async fn fetch_track_info_by_id(id: u64) -> Result<TrackInfo, PlaylistError> {
+    let token = get_access_token().await?;
+    log::info!("[soundcloud] Fetching track info for ID: {}", id);
+    request_track(id, &token).await
+}
 
-    // UserInfo tests
-    #[test]
-    fn test_user_info_deserializes_correctly() {
-        let json = r#"{"username": "test_artist"}"#;
-        let user: UserInfo = serde_json::from_str(json).unwrap();
-        assert_eq!(user.username, "test_artist");
-    }
+/// Raw user profile from the resolve endpoint.
+#[derive(Debug, Clone, Deserialize)]
+struct RawProfile {
+    pub id: u64,
+    pub username: String,
+    pub avatar_url: Option<String>,
+}
 
-    #[test]
-    fn test_user_info_serializes_correctly() {
-        let user = UserInfo {
-            username: "test_artist".to_string(),
-        };
-        let json = serde_json::to_string(&user).unwrap();
-        assert!(json.contains("\"username\":\"test_artist\""));
-    }
+/// Normalizes a profile URL (`/user`, `/user/tracks`) to `https://soundcloud.com/user`.
+fn profile_url(url: &str) -> Option<String> {
+    let parsed = url::Url::parse(url).ok()?;
+    let user = parsed.path_segments()?.find(|s| !s.is_empty())?;
+    Some(format!("https://soundcloud.com/{}", user))
+}
 
-    // RawTrackInfo and TrackInfo tests
-    #[test]
-    fn test_raw_track_info_deserializes_with_all_fields() {
-        let json = r#"{
-            "id": 123456,
-            "title": "Test Track",
-            "user": {"username": "test_artist"},
-            "artwork_url": "https://i1.sndcdn.com/artworks-xxx-large.jpg",
-            "duration": 180000
-        }"#;
-        let raw: RawTrackInfo = serde_json::from_str(json).unwrap();
-        let track = TrackInfo::from(raw);
-        assert_eq!(track.id, 123456);
-        assert_eq!(track.title, "Test Track");
-        // Without publisher_metadata, falls back to user.username
-        assert_eq!(track.user.username, "test_artist");
-        assert_eq!(
-            track.artwork_url,
-            Some("https://i1.sndcdn.com/artworks-xxx-large.jpg".to_string())
-        );
-        assert_eq!(track.duration, 180000);
+/// Fetches every track of a paginated endpoint, reporting each page.
+///
+/// `into_track` extracts the track from a collection item (e.g. a repost);
+/// items without one are skipped.
+async fn fetch_track_pages<T, F>(
+    endpoint: &str,
+    access_token: &str,
+    options: &FetchOptions<'_>,
+    into_track: F,
+) -> Result<Vec<TrackInfo>, PlaylistError>
+where
+    T: serde::de::DeserializeOwned,
+    F: Fn(T) -> Option<RawTrackInfo>,
+{
+    let mut pages = Pages::new(endpoint, access_token, options.page_size);
+    let mut tracks = Vec::new();
+
+    while let Some(page) = pages.next_page::<T>().await? {
+        let page: Vec<TrackInfo> = page
+            .into_iter()
+            .filter_map(&into_track)
+            .map(TrackInfo::from)
+            .collect();
+        options.report(&page);
+        tracks.extend(page);
     }
 
-    #[test]
-    fn test_raw_track_info_deserializes_with_null_artwork() {
-        let json = r#"{
-            "id": 123456,
-            "title": "Test Track",
-            "user": {"username": "test_artist"},
-            "artwork_url": null,
-            "duration": 180000
-        }"#;
-        let raw: RawTrackInfo = serde_json::from_str(json).unwrap();
-        let track = TrackInfo::from(raw);
-        assert!(track.artwork_url.is_none());
-    }
+    Ok(tracks)
+}
 
-    #[test]
-    fn test_track_artwork_falls_back_to_user_avatar() {
-        let json = r#"{
-            "id": 123456,
-            "title": "Test Track",
-            "user": {"username": "test_artist", "avatar_url": "https://i1.sndcdn.com/avatars-xxx.jpg"},
-            "artwork_url": null,
-            "duration": 180000
-        }"#;
-        let raw: RawTrackInfo = serde_json::from_str(json).unwrap();
-        let track = TrackInfo::from(raw);
-        assert_eq!(
-            track.artwork_url,
-            Some("https://i1.sndcdn.com/avatars-xxx.jpg".to_string())
-        );
+/// Resolves a profile URL (or any URL below it) to the user.
+async fn resolve_profile(url: &str, access_token: &str) -> Result<RawProfile, PlaylistError> {
+    let url = match profile_url(url) {
+        Some(url) if is_valid_soundcloud_url(&url) => url,
+        _ => {
+            return Err(PlaylistError::FetchFailed(
+                "Invalid SoundCloud URL".to_string(),
+            ))
+        }
+    };
+
+    log::info!("[soundcloud] Fetching profile for URL: {}", url);
+    resolve_url(&url, access_token).await
+}
+
+/// Endpoint listing every track uploaded by a user.
+fn user_tracks_endpoint(user_id: u64) -> String {
+    format!("https://api.soundcloud.com/users/{}/tracks", user_id)
+}
+
+/// Builds a playlist-like collection of tracks owned by a profile.
+fn profile_playlist(profile: &RawProfile, title: String, tracks: Vec<TrackInfo>) -> PlaylistInfo {
+    PlaylistInfo {
+        id: profile.id,
+        title,
+        user: UserInfo {
+            username: profile.username.clone(),
+        },
+        artwork_url: profile.avatar_url.clone(),
+        track_count: tracks.len() as u32,
+        tracks,
+        set_type: None,
+        album: None,
+        secret_token: None,
+        permalink_url: None,
+        unavailable: vec![],
     }
+}
 
-    #[test]
+/// Fetches every track uploaded by an artist as a playlist-like collection.
+///
+/// The profile is resolved first, then its tracks are paged through with
+/// `linked_partitioning`. The collection is titled after the artist.
+pub async fn fetch_profile_info(
+    url: &str,
+    options: &FetchOptions<'_>,
+) -> Result<PlaylistInfo, PlaylistError> {
+    let token = get_access_token().await?;
+    let profile = resolve_profile(url, &token).await?;
+    let tracks =
+        fetch_track_pages(&user_tracks_endpoint(profile.id), &token, options, Some).await?;
+
+    log::info!(
+        "[soundcloud] Profile '{}' has {} tracks",
+        profile.username,
+        tracks.len()
+    );
+
+    Ok(profile_playlist(&profile, profile.username.clone(), tracks))
+}
+
+/// Tracks or playlists listed on a profile tab (`/likes`, `/sets`, ...).
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct CollectionInfo {
+    pub kind: CollectionKind,
+    pub user: UserInfo,
+    /// A single playlist for track tabs, one per set for `/albums` and
+    /// `/sets`. Each set is meant to be downloaded as its own queue.
+    pub playlists: Vec<PlaylistInfo>,
+}
+
+/// An item of the reposts stream; playlist reposts have no `track`.
+#[derive(Debug, Deserialize)]
+struct RawRepost {
+    track: Option<RawTrackInfo>,
+}
+
+/// A playlist in a user's playlist listing, without its tracks.
+#[derive(Debug, Deserialize)]
+struct RawPlaylistSummary {
+    title: String,
+    permalink_url: Option<String>,
+    set_type: Option<String>,
+}
+
+/// Whether a set is listed on the `/albums` tab rather than `/sets`.
+fn is_album_set_type(set_type: Option<&str>) -> bool {
+    matches!(
+        set_type.map(str::to_ascii_lowercase).as_deref(),
+        Some("album" | "ep" | "single" | "compilation")
+    )
+}
+
+/// Orders tracks by play count, most played first.
+fn sort_by_popularity(tracks: &mut [RawTrackInfo]) {
+    tracks.sort_by_key(|t| std::cmp::Reverse(t.playback_count));
+}
+
+/// Fetches a user's tracks, most played first.
+///
+/// Pages are reported as they arrive; the order is only final once every
+/// page is in.
+async fn fetch_popular_tracks(
+    user_id: u64,
+    access_token: &str,
+    options: &FetchOptions<'_>,
+) -> Result<Vec<TrackInfo>, PlaylistError> {
+    let mut pages = Pages::new(
+        &user_tracks_endpoint(user_id),
+        access_token,
+        options.page_size,
+    );
+    let mut tracks: Vec<RawTrackInfo> = Vec::new();
+
+    while let Some(page) = pages.next_page::<RawTrackInfo>().await? {
+        let partial: Vec<TrackInfo> = page.iter().cloned().map(TrackInfo::from).collect();
+        options.report(&partial);
+        tracks.extend(page);
+    }
+
+    sort_by_popularity(&mut tracks);
+    Ok(tracks.into_iter().map(TrackInfo::from).collect())
+}
+
+/// Fetches the playlists of a user listed on the `/albums` or `/sets` tab.
+///
+/// Each playlist is fetched like a pasted playlist URL, so it has its full
+/// track list. Playlists that fail to load are skipped.
+async fn fetch_user_playlists(
+    user_id: u64,
+    albums: bool,
+    access_token: &str,
+    options: &FetchOptions<'_>,
+) -> Result<Vec<PlaylistInfo>, PlaylistError> {
+    let endpoint = format!(
+        "https://api.soundcloud.com/users/{}/playlists?show_tracks=false",
+        user_id
+    );
+    let summaries: Vec<RawPlaylistSummary> = Pages::new(&endpoint, access_token, options.page_size)
+        .collect_all()
+        .await?;
+
+    let mut playlists = Vec::new();
+    for summary in summaries
+        .into_iter()
+        .filter(|p| is_album_set_type(p.set_type.as_deref()) == albums)
+    {
+        let Some(permalink_url) = summary.permalink_url else {
+            log::warn!(
+                "[soundcloud] Playlist '{}' has no URL, skipping",
+                summary.title
+            );
+            continue;
+        };
+
+        match fetch_playlist_info(&permalink_url, options).await {
+            Ok(playlist) => playlists.push(playlist),
+            Err(PlaylistError::TokenExpired) => return Err(PlaylistError::TokenExpired),
+            Err(e) => log::warn!("[soundcloud] Skipping playlist '{}': {}", summary.title, e),
+        }
+    }
+
+    Ok(playlists)
+}
+
+/// Fetches the content of a profile tab.
+///
+/// Likes, reposts and popular tracks become one playlist titled
+/// `<user> - <Tab>`; albums and sets expand into their playlists.
+/// Reposted playlists are not included in reposts.
+pub async fn fetch_collection_info(
+    url: &str,
+    kind: CollectionKind,
+    options: &FetchOptions<'_>,
+) -> Result<CollectionInfo, PlaylistError> {
+    let token = get_access_token().await?;
+    let profile = resolve_profile(url, &token).await?;
+    let title = collection_title(&profile.username, kind);
+
+    let playlists = match kind {
+        CollectionKind::Likes => {
+            let endpoint = format!(
+                "https://api.soundcloud.com/users/{}/likes/tracks",
+                profile.id
+            );
+            let tracks = fetch_track_pages(&endpoint, &token, options, Some).await?;
+            vec![profile_playlist(&profile, title, tracks)]
+        }
+        CollectionKind::Reposts => {
+            let endpoint = format!(
+                "https://api-v2.soundcloud.com/stream/users/{}/reposts",
+                profile.id
+            );
+            let tracks =
+                fetch_track_pages(&endpoint, &token, options, |r: RawRepost| r.track).await?;
+            vec![profile_playlist(&profile, title, tracks)]
+        }
+        CollectionKind::PopularTracks => {
+            let tracks = fetch_popular_tracks(profile.id, &token, options).await?;
+            vec![profile_playlist(&profile, title, tracks)]
+        }
+        CollectionKind::Albums | CollectionKind::Sets => {
+            fetch_user_playlists(profile.id, kind == CollectionKind::Albums, &token, options)
+                .await?
+        }
+    };
+
+    log::info!(
+        "[soundcloud] Collection '{}' of '{}' has {} playlists",
+        kind.path_segment(),
+        profile.username,
+        playlists.len()
+    );
+
+    Ok(CollectionInfo {
+        kind,
+        user: UserInfo {
+            username: profile.username,
+        },
+        playlists,
+    })
+}
+
+/// Title of a track collection, e.g. `Artist - Likes`.
+fn collection_title(username: &str, kind: CollectionKind) -> String {
+    let tab = match kind {
+        CollectionKind::Likes => "Likes",
+        CollectionKind::Reposts => "Reposts",
+        CollectionKind::PopularTracks => "Popular Tracks",
+        CollectionKind::Albums => "Albums",
+        CollectionKind::Sets => "Playlists",
+    };
+    format!("{} - {}", username, tab)
+}
+
+/// Gets the signed-in user's access token; `/me` endpoints have no app-level
+/// equivalent.
+async fn get_user_access_token() -> Result<String, PlaylistError> {
+    match load_tokens() {
+        Ok(Some(tokens)) if is_token_expired_or_expiring(tokens.expires_at) => {
+            Err(PlaylistError::TokenExpired)
+        }
+        Ok(Some(tokens)) => Ok(tokens.access_token),
+        Ok(None) => Err(PlaylistError::AuthRequired),
+        Err(e) => {
+            log::warn!("[soundcloud] Failed to load user tokens: {}", e);
+            Err(PlaylistError::AuthRequired)
+        }
+    }
+}
+
+/// Fetches the signed-in user's profile.
+async fn fetch_me(access_token: &str) -> Result<RawProfile, PlaylistError> {
+    let http = http::client();
+    let response = http
+        .send(
+            http.get("https://api.soundcloud.com/me")
+                .header("Authorization", format!("OAuth {}", access_token)),
+        )
+        .await?;
+
+    match response.status() {
+        reqwest::StatusCode::UNAUTHORIZED => Err(PlaylistError::AuthRequired),
+        status if !status.is_success() => {
+            Err(PlaylistError::FetchFailed(format!("HTTP {}", status)))
+        }
+        _ => response
+            .json()
+            .await
+            .map_err(|_| PlaylistError::InvalidResponse),
+    }
+}
+
+/// Fetches the tracks the signed-in user liked.
+pub async fn fetch_my_likes(options: &FetchOptions<'_>) -> Result<PlaylistInfo, PlaylistError> {
+    let token = get_user_access_token().await?;
+    let me = fetch_me(&token).await?;
+
+    let tracks = fetch_track_pages(
+        "https://api.soundcloud.com/me/likes/tracks",
+        &token,
+        options,
+        Some,
+    )
+    .await?;
+
+    Ok(profile_playlist(
+        &me,
+        collection_title(&me.username, CollectionKind::Likes),
+        tracks,
+    ))
+}
+
+/// Fetches the signed-in user's uploads, private tracks included.
+pub async fn fetch_my_uploads(options: &FetchOptions<'_>) -> Result<PlaylistInfo, PlaylistError> {
+    let token = get_user_access_token().await?;
+    let me = fetch_me(&token).await?;
+
+    let tracks = fetch_track_pages(
+        "https://api.soundcloud.com/me/tracks",
+        &token,
+        options,
+        Some,
+    )
+    .await?;
+
+    Ok(profile_playlist(
+        &me,
+        format!("{} - Uploads", me.username),
+        tracks,
+    ))
+}
+
+/// Fetches the signed-in user's playlists, private ones included.
+///
+/// Playlists whose listing doesn't carry every track are paged through
+/// individually. Playlists that fail to load are skipped.
+pub async fn fetch_my_playlists(
+    options: &FetchOptions<'_>,
+) -> Result<Vec<PlaylistInfo>, PlaylistError> {
+    let token = get_user_access_token().await?;
+
+    let listed: Vec<RawPlaylistInfo> = Pages::new(
+        "https://api.soundcloud.com/me/playlists",
+        &token,
+        options.page_size,
+    )
+    .collect_all()
+    .await?;
+
+    let mut playlists = Vec::with_capacity(listed.len());
+    for raw in listed {
+        let title = raw.title.clone();
+        match complete_playlist(raw, None, &token, options).await {
+            Ok(playlist) => playlists.push(playlist),
+            Err(e) => log::warn!("[soundcloud] Skipping playlist '{}': {}", title, e),
+        }
+    }
+
+    log::info!("[soundcloud] Found {} of your playlists", playlists.len());
+    Ok(playlists)
+}
+
+/// What to search SoundCloud for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum SearchKind {
+    Tracks,
+    Playlists,
+    Users,
+}
+
+impl SearchKind {
+    fn endpoint(&self) -> &'static str {
+        match self {
+            SearchKind::Tracks => "https://api.soundcloud.com/tracks",
+            SearchKind::Playlists => "https://api.soundcloud.com/playlists",
+            SearchKind::Users => "https://api.soundcloud.com/users",
+        }
+    }
+}
+
+/// Filters for track searches; ignored when searching playlists or users.
+#[derive(Debug, Clone, Default, Deserialize, Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct SearchFilters {
+    /// Shortest track to return, in milliseconds.
+    pub min_duration: Option<u64>,
+    /// Longest track to return, in milliseconds.
+    pub max_duration: Option<u64>,
+    pub genre: Option<String>,
+    /// Only tracks whose uploader enabled downloads.
+    pub downloadable_only: bool,
+}
+
+impl SearchFilters {
+    /// Query parameters narrowing the search on the API side.
+    fn query_pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        if let Some(min) = self.min_duration {
+            pairs.push(("duration[from]", min.to_string()));
+        }
+        if let Some(max) = self.max_duration {
+            pairs.push(("duration[to]", max.to_string()));
+        }
+        if let Some(genre) = self.genre.as_deref().filter(|g| !g.trim().is_empty()) {
+            pairs.push(("genres", genre.trim().to_string()));
+        }
+        pairs
+    }
+
+    /// Checks a result again, as the API treats some filters as hints and
+    /// has no downloadable filter at all.
+    fn matches(&self, track: &RawTrackInfo) -> bool {
+        let genre_matches = match self.genre.as_deref().map(str::trim) {
+            Some(wanted) if !wanted.is_empty() => track
+                .genre
+                .as_deref()
+                .is_some_and(|genre| genre.trim().eq_ignore_ascii_case(wanted)),
+            _ => true,
+        };
+
+        !matches!(self.min_duration, Some(min) if track.duration < min)
+            && !matches!(self.max_duration, Some(max) if track.duration > max)
+            && genre_matches
+            && (!self.downloadable_only || track.downloadable == Some(true))
+    }
+}
+
+/// A user found by search; download their tracks with `get_profile_info`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Type)]
+pub struct UserSummary {
+    pub id: u64,
+    pub username: String,
+    #[serde(default)]
+    pub avatar_url: Option<String>,
+    #[serde(default)]
+    pub permalink_url: Option<String>,
+    #[serde(default)]
+    pub track_count: Option<u32>,
+    #[serde(default)]
+    pub followers_count: Option<u32>,
+}
+
+/// Items of a search results page, depending on the kind searched.
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(tag = "kind", content = "items", rename_all = "camelCase")]
+pub enum SearchItems {
+    Tracks(Vec<TrackInfo>),
+    /// Playlists with the tracks listed by the API; fetch `permalink_url`
+    /// with `get_playlist_info` when `track_count` is larger.
+    Playlists(Vec<PlaylistInfo>),
+    Users(Vec<UserSummary>),
+}
+
+/// One page of search results.
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct SearchResults {
+    pub results: SearchItems,
+    /// Offset of the next page, or None on the last page.
+    pub next_offset: Option<u32>,
+}
+
+/// Builds the search URL for a kind, including the API-side filters.
+fn search_endpoint(query: &str, kind: SearchKind, offset: u32, filters: &SearchFilters) -> String {
+    let mut url = url::Url::parse(kind.endpoint()).expect("search endpoint is a valid URL");
+    {
+        let mut pairs = url.query_pairs_mut();
+        pairs.append_pair("q", query.trim());
+        pairs.append_pair("offset", &offset.to_string());
+        if kind == SearchKind::Tracks {
+            for (key, value) in filters.query_pairs() {
+                pairs.append_pair(key, &value);
+            }
+        }
+    }
+    url.to_string()
+}
+
+/// Searches SoundCloud for tracks, playlists or users.
+///
+/// Uses the signed-in user's token when available, the app token otherwise.
+/// Track results are filtered after fetching, so a page can hold fewer than
+/// `limit` items while more pages remain.
+pub async fn search(
+    query: &str,
+    kind: SearchKind,
+    limit: u32,
+    offset: u32,
+    filters: &SearchFilters,
+) -> Result<SearchResults, PlaylistError> {
+    if query.trim().is_empty() {
+        return Err(PlaylistError::FetchFailed(
+            "Search query is empty".to_string(),
+        ));
+    }
+
+    let token = get_access_token().await?;
+    let endpoint = search_endpoint(query, kind, offset, filters);
+    log::info!("[soundcloud] Searching {:?} for '{}'", kind, query.trim());
+
+    let mut pages = Pages::new(&endpoint, &token, limit);
+    let results = match kind {
+        SearchKind::Tracks => {
+            let tracks: Vec<RawTrackInfo> = pages.next_page().await?.unwrap_or_default();
+            SearchItems::Tracks(
+                tracks
+                    .into_iter()
+                    .filter(|t| filters.matches(t))
+                    .map(TrackInfo::from)
+                    .collect(),
+            )
+        }
+        SearchKind::Playlists => {
+            let playlists: Vec<RawPlaylistInfo> = pages.next_page().await?.unwrap_or_default();
+            SearchItems::Playlists(playlists.into_iter().map(PlaylistInfo::from).collect())
+        }
+        SearchKind::Users => SearchItems::Users(pages.next_page().await?.unwrap_or_default()),
+    };
+
+    Ok(SearchResults {
+        results,
+        next_offset: pages
+            .has_next()
+            .then(|| offset + limit.clamp(1, MAX_PAGE_SIZE)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // UserInfo tests
+    #[test]
+    fn test_user_info_deserializes_correctly() {
+        let json = r#"{"username": "test_artist"}"#;
+        let user: UserInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(user.username, "test_artist");
+    }
+
+    #[test]
+    fn test_user_info_serializes_correctly() {
+        let user = UserInfo {
+            username: "test_artist".to_string(),
+        };
+        let json = serde_json::to_string(&user).unwrap();
+        assert!(json.contains("\"username\":\"test_artist\""));
+    }
+
+    // RawTrackInfo and TrackInfo tests
+    #[test]
+    fn test_raw_track_info_deserializes_with_all_fields() {
+        let json = r#"{
+            "id": 123456,
+            "title": "Test Track",
+            "user": {"username": "test_artist"},
+            "artwork_url": "https://i1.sndcdn.com/artworks-xxx-large.jpg",
+            "duration": 180000
+        }"#;
+        let raw: RawTrackInfo = serde_json::from_str(json).unwrap();
+        let track = TrackInfo::from(raw);
+        assert_eq!(track.id, 123456);
+        assert_eq!(track.title, "Test Track");
+        // Without publisher_metadata, falls back to user.username
+        assert_eq!(track.user.username, "test_artist");
+        assert_eq!(
+            track.artwork_url,
+            Some("https://i1.sndcdn.com/artworks-xxx-large.jpg".to_string())
+        );
+        assert_eq!(track.duration, 180000);
+    }
+
+    #[test]
+    fn test_raw_track_info_deserializes_with_null_artwork() {
+        let json = r#"{
+            "id": 123456,
+            "title": "Test Track",
+            "user": {"username": "test_artist"},
+            "artwork_url": null,
+            "duration": 180000
+        }"#;
+        let raw: RawTrackInfo = serde_json::from_str(json).unwrap();
+        let track = TrackInfo::from(raw);
+        assert!(track.artwork_url.is_none());
+    }
+
+    #[test]
+    fn test_track_artwork_falls_back_to_user_avatar() {
+        let json = r#"{
+            "id": 123456,
+            "title": "Test Track",
+            "user": {"username": "test_artist", "avatar_url": "https://i1.sndcdn.com/avatars-xxx.jpg"},
+            "artwork_url": null,
+            "duration": 180000
+        }"#;
+        let raw: RawTrackInfo = serde_json::from_str(json).unwrap();
+        let track = TrackInfo::from(raw);
+        assert_eq!(
+            track.artwork_url,
+            Some("https://i1.sndcdn.com/avatars-xxx.jpg".to_string())
+        );
+    }
+
+    #[test]
     fn test_track_artwork_prefers_track_artwork_over_avatar() {
         let json = r#"{
             "id": 123456,
@@ -875,6 +2013,63 @@ mod tests {
         assert_eq!(track.user.username, "uploader");
     }
 
+    #[test]
+    fn test_track_info_carries_extended_metadata() {
+        let json = r#"{
+            "id": 123456,
+            "title": "Test Track",
+            "user": {"username": "uploader"},
+            "artwork_url": null,
+            "duration": 180000,
+            "genre": "Techno",
+            "created_at": "2023/05/01 12:00:00 +0000",
+            "release_date": "2023-06-02T00:00:00Z",
+            "label_name": "Some Label",
+            "description": "Out now",
+            "permalink_url": "https://soundcloud.com/uploader/test-track",
+            "bpm": 128,
+            "publisher_metadata": {"artist": "Real Artist", "isrc": "GBXXX2300001"}
+        }"#;
+        let raw: RawTrackInfo = serde_json::from_str(json).unwrap();
+        let track = TrackInfo::from(raw);
+        assert_eq!(track.user.username, "Real Artist");
+        assert_eq!(track.genre, Some("Techno".to_string()));
+        assert_eq!(track.release_date, Some("2023-06-02T00:00:00Z".to_string()));
+        assert_eq!(track.label, Some("Some Label".to_string()));
+        assert_eq!(track.isrc, Some("GBXXX2300001".to_string()));
+        assert_eq!(track.description, Some("Out now".to_string()));
+        assert_eq!(
+            track.permalink_url,
+            Some("https://soundcloud.com/uploader/test-track".to_string())
+        );
+        assert_eq!(track.bpm, Some(128.0));
+    }
+
+    #[test]
+    fn test_track_info_release_date_falls_back_to_created_at() {
+        let json = r#"{
+            "id": 123456,
+            "title": "Test Track",
+            "user": {"username": "uploader"},
+            "artwork_url": null,
+            "duration": 180000,
+            "created_at": "2023/05/01 12:00:00 +0000",
+            "release_date": "",
+            "genre": "",
+            "label_name": null,
+            "bpm": null
+        }"#;
+        let raw: RawTrackInfo = serde_json::from_str(json).unwrap();
+        let track = TrackInfo::from(raw);
+        assert_eq!(
+            track.release_date,
+            Some("2023/05/01 12:00:00 +0000".to_string())
+        );
+        assert!(track.genre.is_none());
+        assert!(track.label.is_none());
+        assert!(track.bpm.is_none());
+    }
+
     #[test]
     fn test_track_info_serializes_correctly() {
         let track = TrackInfo {
@@ -885,6 +2080,7 @@ mod tests {
             },
             artwork_url: Some("https://example.com/art.jpg".to_string()),
             duration: 180000,
+            ..Default::default()
         };
         let json = serde_json::to_string(&track).unwrap();
         assert!(json.contains("\"id\":123456"));
@@ -980,6 +2176,52 @@ mod tests {
         assert_eq!(playlist.tracks[1].user.username, "regular_uploader");
     }
 
+    #[test]
+    fn test_album_set_exposes_album_metadata() {
+        let json = r#"{
+            "id": 999,
+            "title": "Debut EP",
+            "user": {"username": "label_owner"},
+            "artwork_url": "https://i1.sndcdn.com/artworks-ep-large.jpg",
+            "track_count": 0,
+            "tracks": [],
+            "set_type": "ep",
+            "release_date": "2024-02-01T00:00:00Z",
+            "label_name": "Some Label",
+            "genre": "House"
+        }"#;
+        let raw: RawPlaylistInfo = serde_json::from_str(json).unwrap();
+        let playlist = PlaylistInfo::from(raw);
+        assert_eq!(playlist.set_type, Some("ep".to_string()));
+        let album = playlist.album.expect("EP should expose album metadata");
+        assert_eq!(album.artist, "label_owner");
+        assert_eq!(
+            album.artwork_url,
+            Some("https://i1.sndcdn.com/artworks-ep-large.jpg".to_string())
+        );
+        assert_eq!(album.release_date, Some("2024-02-01T00:00:00Z".to_string()));
+        assert_eq!(album.label, Some("Some Label".to_string()));
+        assert_eq!(album.genre, Some("House".to_string()));
+    }
+
+    #[test]
+    fn test_regular_playlist_has_no_album_metadata() {
+        let json = r#"{
+            "id": 999,
+            "title": "Mix Favourites",
+            "user": {"username": "owner"},
+            "artwork_url": null,
+            "track_count": 0,
+            "tracks": [],
+            "set_type": "playlist",
+            "release_date": "2024-02-01T00:00:00Z"
+        }"#;
+        let raw: RawPlaylistInfo = serde_json::from_str(json).unwrap();
+        let playlist = PlaylistInfo::from(raw);
+        assert_eq!(playlist.set_type, Some("playlist".to_string()));
+        assert!(playlist.album.is_none());
+    }
+
     #[test]
     fn test_playlist_info_serializes_correctly() {
         let playlist = PlaylistInfo {
@@ -998,7 +2240,9 @@ mod tests {
                 },
                 artwork_url: None,
                 duration: 180000,
+                ..Default::default()
             }],
+            ..Default::default()
         };
         let json = serde_json::to_string(&playlist).unwrap();
         assert!(json.contains("\"id\":999"));
@@ -1167,6 +2411,31 @@ mod tests {
         assert_eq!(playlist.id, 999);
         assert_eq!(playlist.title, "Test Playlist");
         assert_eq!(playlist.track_count, 2);
+        assert!(playlist.set.set_type.is_none());
+    }
+
+    #[test]
+    fn test_extract_album_from_hydration() {
+        let items = vec![HydrationItem {
+            hydratable: "playlist".to_string(),
+            data: serde_json::json!({
+                "id": 999,
+                "title": "Album",
+                "user": {"username": "owner"},
+                "artwork_url": "https://i1.sndcdn.com/artworks-album-large.jpg",
+                "track_count": 1,
+                "tracks": [123],
+                "set_type": "album",
+                "release_date": "2022-10-14T00:00:00Z"
+            }),
+        }];
+        let playlist = extract_playlist_from_hydration(&items).unwrap();
+        let album = playlist
+            .set
+            .into_album(&playlist.user.username, playlist.artwork_url.as_ref())
+            .unwrap();
+        assert_eq!(album.artist, "owner");
+        assert_eq!(album.release_date, Some("2022-10-14T00:00:00Z".to_string()));
     }
 
     #[test]
@@ -1178,4 +2447,548 @@ mod tests {
         let result = extract_playlist_from_hydration(&items);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_profile_url_normalizes_tracks_tab() {
+        assert_eq!(
+            profile_url("https://soundcloud.com/artist/tracks?ref=clipboard"),
+            Some("https://soundcloud.com/artist".to_string())
+        );
+        assert_eq!(
+            profile_url("https://www.soundcloud.com/artist/"),
+            Some("https://soundcloud.com/artist".to_string())
+        );
+        assert_eq!(profile_url("https://soundcloud.com/"), None);
+    }
+
+    #[test]
+    fn test_is_album_set_type() {
+        assert!(is_album_set_type(Some("album")));
+        assert!(is_album_set_type(Some("EP")));
+        assert!(is_album_set_type(Some("single")));
+        assert!(!is_album_set_type(Some("playlist")));
+        assert!(!is_album_set_type(None));
+    }
+
+    #[test]
+    fn test_sort_by_popularity() {
+        let track = |id: u64, plays: Option<u64>| RawTrackInfo {
+            id,
+            title: id.to_string(),
+            user: RawUserInfo {
+                username: "u".to_string(),
+                avatar_url: None,
+            },
+            artwork_url: None,
+            duration: 0,
+            publisher_metadata: None,
+            genre: None,
+            created_at: None,
+            release_date: None,
+            label_name: None,
+            description: None,
+            permalink_url: None,
+            bpm: None,
+            playback_count: plays,
+            secret_token: None,
+            secret_uri: None,
+            downloadable: None,
+            streamable: None,
+            policy: None,
+            access: None,
+        };
+        let mut tracks = vec![track(1, Some(10)), track(2, None), track(3, Some(500))];
+
+        sort_by_popularity(&mut tracks);
+        let ids: Vec<u64> = tracks.iter().map(|t| t.id).collect();
+        assert_eq!(ids, vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn test_repost_stream_items() {
+        let json = r#"[
+            {"type": "track-repost", "track": {"id": 1, "title": "A", "user": {"username": "u"}, "duration": 1000}},
+            {"type": "playlist-repost", "playlist": {"id": 2}}
+        ]"#;
+        let reposts: Vec<RawRepost> = serde_json::from_str(json).unwrap();
+        let tracks: Vec<RawTrackInfo> = reposts.into_iter().filter_map(|r| r.track).collect();
+        assert_eq!(tracks.len(), 1);
+        assert_eq!(tracks[0].id, 1);
+    }
+
+    #[test]
+    fn test_collection_title() {
+        assert_eq!(collection_title("DJ", CollectionKind::Likes), "DJ - Likes");
+        assert_eq!(
+            collection_title("DJ", CollectionKind::PopularTracks),
+            "DJ - Popular Tracks"
+        );
+    }
+
+    #[test]
+    fn test_private_track_keeps_secret_token() {
+        let json = r#"{
+            "id": 1,
+            "title": "Private",
+            "user": {"username": "me"},
+            "duration": 1000,
+            "sharing": "private",
+            "secret_token": "s-AbC123"
+        }"#;
+        let raw: RawTrackInfo = serde_json::from_str(json).unwrap();
+        let track = TrackInfo::from(raw);
+        assert_eq!(track.secret_token.as_deref(), Some("s-AbC123"));
+
+        let json = serde_json::to_string(&track).unwrap();
+        assert!(json.contains("\"secret_token\":\"s-AbC123\""));
+    }
+
+    #[test]
+    fn test_track_info_carries_playability_fields() {
+        let json = r#"{
+            "id": 1,
+            "title": "Go+ Track",
+            "user": {"username": "me"},
+            "artwork_url": null,
+            "duration": 30000,
+            "streamable": true,
+            "policy": "SNIP",
+            "access": "preview",
+            "downloadable": false
+        }"#;
+        let raw: RawTrackInfo = serde_json::from_str(json).unwrap();
+        let track = TrackInfo::from(raw);
+        assert_eq!(track.streamable, Some(true));
+        assert_eq!(track.policy.as_deref(), Some("SNIP"));
+        assert_eq!(track.access.as_deref(), Some("preview"));
+        assert_eq!(track.downloadable, Some(false));
+        assert_eq!(track.playability, Playability::PreviewOnly);
+
+        let json = serde_json::to_string(&track).unwrap();
+        assert!(json.contains("\"playability\":\"previewOnly\""));
+    }
+
+    #[test]
+    fn test_playability_from_raw() {
+        assert_eq!(
+            Playability::from_raw(None, None, None),
+            Playability::Playable
+        );
+        assert_eq!(
+            Playability::from_raw(Some(true), Some("MONETIZE"), Some("playable")),
+            Playability::Playable
+        );
+        assert_eq!(
+            Playability::from_raw(Some(true), Some("ALLOW"), Some("preview")),
+            Playability::PreviewOnly
+        );
+        assert_eq!(
+            Playability::from_raw(Some(false), Some("ALLOW"), None),
+            Playability::Blocked
+        );
+        assert_eq!(
+            Playability::from_raw(Some(true), Some("BLOCK"), Some("playable")),
+            Playability::Blocked
+        );
+        assert_eq!(
+            Playability::from_raw(None, Some("SNIP"), Some("blocked")),
+            Playability::Blocked
+        );
+    }
+
+    #[test]
+    fn test_private_playlist_tracks_fixture_keep_secret_tokens() {
+        use crate::services::pagination::CollectionPage;
+        use crate::services::url_validator::with_secret_token;
+
+        let page: CollectionPage =
+            serde_json::from_str(include_str!("fixtures/private_playlist_tracks.json")).unwrap();
+        let tracks: Vec<TrackInfo> = page
+            .collection
+            .into_iter()
+            .map(|value| TrackInfo::from(serde_json::from_value::<RawTrackInfo>(value).unwrap()))
+            .collect();
+
+        // Private tracks listed in a private playlist only carry `secret_uri`
+        assert_eq!(tracks[0].secret_token.as_deref(), Some("s-Qx7vTnB2pLm"));
+        assert_eq!(tracks[1].secret_token, None);
+
+        let download_urls: Vec<String> = tracks
+            .iter()
+            .map(|t| {
+                with_secret_token(
+                    t.permalink_url.as_deref().unwrap(),
+                    t.secret_token.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            download_urls,
+            vec![
+                "https://soundcloud.com/studio-owner/demo-1-unreleased/s-Qx7vTnB2pLm",
+                "https://soundcloud.com/studio-owner/public-single",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_private_playlist_hydration_fixture() {
+        let items: Vec<HydrationItem> =
+            serde_json::from_str(include_str!("fixtures/private_playlist_hydration.json")).unwrap();
+        let playlist = extract_playlist_from_hydration(&items).unwrap();
+        assert_eq!(playlist.secret_token.as_deref(), Some("s-PlAyL1sTtok"));
+
+        let full_tracks = extract_full_tracks_from_hydration(&playlist.tracks);
+        assert_eq!(full_tracks.len(), 1);
+        assert_eq!(
+            full_tracks[0].secret_token.as_deref(),
+            Some("s-Qx7vTnB2pLm")
+        );
+
+        // The second track is listed by ID only, without its token, so the
+        // playlist has to be completed through the API
+        assert_eq!(
+            extract_track_ids(&playlist.tracks),
+            vec![1723001001, 1723001003]
+        );
+    }
+
+    #[test]
+    fn test_secret_token_from_uri() {
+        assert_eq!(
+            secret_token_from_uri("https://api.soundcloud.com/tracks/1?secret_token=s-AbC"),
+            Some("s-AbC".to_string())
+        );
+        assert_eq!(
+            secret_token_from_uri("https://api.soundcloud.com/tracks/1"),
+            None
+        );
+        assert_eq!(secret_token_from_uri("not a url"), None);
+    }
+
+    #[test]
+    fn test_private_playlist_keeps_secret_token() {
+        let json = r#"{
+            "id": 9,
+            "title": "Drafts",
+            "user": {"username": "me"},
+            "artwork_url": null,
+            "track_count": 0,
+            "tracks": [],
+            "secret_token": "s-Xyz"
+        }"#;
+        let raw: RawPlaylistInfo = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            PlaylistInfo::from(raw).secret_token.as_deref(),
+            Some("s-Xyz")
+        );
+    }
+
+    #[test]
+    fn test_unavailable_reason_from_error() {
+        assert_eq!(
+            UnavailableReason::from_error(&PlaylistError::GeoBlocked),
+            Some(UnavailableReason::GeoBlocked)
+        );
+        assert_eq!(
+            UnavailableReason::from_error(&PlaylistError::TrackNotFound),
+            Some(UnavailableReason::Removed)
+        );
+        assert_eq!(
+            UnavailableReason::from_error(&PlaylistError::AuthRequired),
+            Some(UnavailableReason::Private)
+        );
+        // Server and network errors say nothing about the track
+        assert_eq!(
+            UnavailableReason::from_error(&PlaylistError::FetchFailed(
+                "HTTP 500 Internal Server Error: ".to_string()
+            )),
+            None
+        );
+        assert_eq!(
+            UnavailableReason::from_error(&PlaylistError::InvalidResponse),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_tracks_by_ids_serves_cached_tracks() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = Arc::new(MetadataCache::new(Some(dir.path().to_path_buf())));
+        let track = TrackInfo {
+            id: 7,
+            title: "Cached".to_string(),
+            ..Default::default()
+        };
+        cache.put_track(None, &track);
+
+        let reported = Mutex::new(Vec::new());
+        let progress =
+            |tracks: &[TrackInfo]| reported.lock().unwrap().extend(tracks.iter().map(|t| t.id));
+        let options = FetchOptions {
+            progress: Some(&progress),
+            cache: Some(&cache),
+            ..Default::default()
+        };
+
+        // No request is made when every track is cached
+        let tracks = fetch_tracks_by_ids(&[7], "invalid-token", &options)
+            .await
+            .unwrap();
+        assert_eq!(tracks.len(), 1);
+        assert_eq!(tracks[0].title, "Cached");
+        assert_eq!(*reported.lock().unwrap(), vec![7]);
+    }
+
+    #[test]
+    fn test_collect_track_results_keeps_going_after_errors() {
+        let track = |id| TrackInfo {
+            id,
+            ..Default::default()
+        };
+        let (tracks, failures) = collect_track_results(vec![
+            (1, Ok(track(1))),
+            (2, Err(PlaylistError::GeoBlocked)),
+            (3, Err(PlaylistError::InvalidResponse)),
+        ])
+        .unwrap();
+        assert_eq!(tracks.len(), 1);
+        assert_eq!(failures[&2], UnavailableReason::GeoBlocked);
+        assert_eq!(failures[&3], UnavailableReason::Unknown);
+
+        // A track that answered with a status still counts as a response
+        assert!(collect_track_results(vec![
+            (2, Err(PlaylistError::TrackNotFound)),
+            (3, Err(PlaylistError::InvalidResponse)),
+        ])
+        .is_ok());
+        assert!(collect_track_results(vec![
+            (1, Err(PlaylistError::InvalidResponse)),
+            (2, Err(PlaylistError::FetchFailed("HTTP 503".to_string()))),
+        ])
+        .is_err());
+        assert!(collect_track_results(vec![]).is_ok());
+    }
+
+    #[test]
+    fn test_order_playlist_tracks_keeps_positions() {
+        let track = |id| TrackInfo {
+            id,
+            ..Default::default()
+        };
+        let fetched: HashMap<u64, TrackInfo> = [track(30), track(10), track(50)]
+            .into_iter()
+            .map(|t| (t.id, t))
+            .collect();
+        let failures = HashMap::from([(20, UnavailableReason::GeoBlocked)]);
+
+        let (tracks, unavailable) =
+            order_playlist_tracks(&[10, 20, 30, 40, 50], fetched, &failures);
+
+        let positions: Vec<(u64, Option<u32>)> =
+            tracks.iter().map(|t| (t.id, t.playlist_position)).collect();
+        assert_eq!(positions, vec![(10, Some(1)), (30, Some(3)), (50, Some(5))]);
+        assert_eq!(
+            unavailable,
+            vec![
+                UnavailableTrack {
+                    id: 20,
+                    position: 2,
+                    reason: UnavailableReason::GeoBlocked,
+                },
+                // Dropped by the batch endpoint without a status
+                UnavailableTrack {
+                    id: 40,
+                    position: 4,
+                    reason: UnavailableReason::ApiFiltered,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unavailable_track_serialize() {
+        let json = serde_json::to_string(&UnavailableTrack {
+            id: 7,
+            position: 3,
+            reason: UnavailableReason::GeoBlocked,
+        })
+        .unwrap();
+        assert_eq!(json, r#"{"id":7,"position":3,"reason":"geoBlocked"}"#);
+    }
+
+    #[test]
+    fn test_api_playlist_tracks_are_numbered() {
+        let json = r#"{
+            "id": 9,
+            "title": "Mix",
+            "user": {"username": "me"},
+            "artwork_url": null,
+            "track_count": 2,
+            "tracks": [
+                {"id": 1, "title": "One", "user": {"username": "a"}, "artwork_url": null, "duration": 1000},
+                {"id": 2, "title": "Two", "user": {"username": "a"}, "artwork_url": null, "duration": 1000}
+            ]
+        }"#;
+        let raw: RawPlaylistInfo = serde_json::from_str(json).unwrap();
+        let playlist = PlaylistInfo::from(raw);
+        assert_eq!(playlist.tracks[1].playlist_position, Some(2));
+        assert!(playlist.unavailable.is_empty());
+    }
+
+    #[test]
+    fn test_playlist_track_ids_include_stubs() {
+        let json = r#"{
+            "id": 9,
+            "tracks": [
+                {"id": 1, "title": "One", "user": {"username": "a"}},
+                {"id": 2, "kind": "track"},
+                {"id": 3, "kind": "track"}
+            ]
+        }"#;
+        let listing: RawPlaylistTrackIds = serde_json::from_str(json).unwrap();
+        let ids = extract_track_ids(&listing.tracks);
+        assert_eq!(ids, vec![1, 2, 3]);
+
+        // The public API left out track 2
+        let fetched = [1, 3]
+            .map(|id| TrackInfo {
+                id,
+                ..Default::default()
+            })
+            .into_iter()
+            .map(|t| (t.id, t))
+            .collect();
+        let (tracks, unavailable) = order_playlist_tracks(&ids, fetched, &HashMap::new());
+        assert_eq!(tracks[1].playlist_position, Some(3));
+        assert_eq!(unavailable[0].id, 2);
+        assert_eq!(unavailable[0].position, 2);
+    }
+
+    #[test]
+    fn test_fetch_options_report_skips_empty_batches() {
+        let calls = std::sync::Mutex::new(Vec::new());
+        let progress = |tracks: &[TrackInfo]| calls.lock().unwrap().push(tracks.len());
+        let options = FetchOptions {
+            progress: Some(&progress),
+            ..Default::default()
+        };
+
+        options.report(&[]);
+        options.report(&[TrackInfo::default(), TrackInfo::default()]);
+        FetchOptions::default().report(&[TrackInfo::default()]);
+
+        assert_eq!(*calls.lock().unwrap(), vec![2]);
+        assert_eq!(options.page_size, DEFAULT_PAGE_SIZE);
+    }
+
+    #[test]
+    fn test_playlist_fetch_progress_event_serialize() {
+        let event = PlaylistFetchProgressEvent {
+            source: "me/likes".to_string(),
+            fetched: 400,
+            tracks: vec![],
+        };
+        let json = serde_json::to_string(&event).unwrap();
+        assert_eq!(json, r#"{"source":"me/likes","fetched":400,"tracks":[]}"#);
+    }
+
+    fn search_track(
+        duration: u64,
+        genre: Option<&str>,
+        downloadable: Option<bool>,
+    ) -> RawTrackInfo {
+        serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "title": "Track",
+            "user": {"username": "artist"},
+            "artwork_url": null,
+            "duration": duration,
+            "genre": genre,
+            "downloadable": downloadable
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_search_filters_deserialize_defaults() {
+        let filters: SearchFilters =
+            serde_json::from_str(r#"{"maxDuration": 600000, "downloadableOnly": true}"#).unwrap();
+        assert_eq!(filters.max_duration, Some(600_000));
+        assert!(filters.min_duration.is_none());
+        assert!(filters.downloadable_only);
+    }
+
+    #[test]
+    fn test_search_filters_match_tracks() {
+        let filters = SearchFilters {
+            min_duration: Some(60_000),
+            max_duration: Some(600_000),
+            genre: Some("House ".to_string()),
+            downloadable_only: true,
+        };
+
+        assert!(filters.matches(&search_track(300_000, Some("house"), Some(true))));
+        assert!(!filters.matches(&search_track(30_000, Some("House"), Some(true))));
+        assert!(!filters.matches(&search_track(900_000, Some("House"), Some(true))));
+        assert!(!filters.matches(&search_track(300_000, Some("Techno"), Some(true))));
+        assert!(!filters.matches(&search_track(300_000, Some("House"), None)));
+        assert!(SearchFilters::default().matches(&search_track(0, None, None)));
+    }
+
+    #[test]
+    fn test_search_endpoint() {
+        let filters = SearchFilters {
+            min_duration: Some(60_000),
+            genre: Some("Drum & Bass".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            search_endpoint(" deep house ", SearchKind::Tracks, 50, &filters),
+            "https://api.soundcloud.com/tracks?q=deep+house&offset=50&duration%5Bfrom%5D=60000&genres=Drum+%26+Bass"
+        );
+        // Track filters don't apply to other kinds
+        assert_eq!(
+            search_endpoint("mix", SearchKind::Users, 0, &filters),
+            "https://api.soundcloud.com/users?q=mix&offset=0"
+        );
+    }
+
+    #[test]
+    fn test_search_results_serialize() {
+        let results = SearchResults {
+            results: SearchItems::Users(vec![UserSummary {
+                id: 7,
+                username: "artist".to_string(),
+                ..Default::default()
+            }]),
+            next_offset: Some(20),
+        };
+        let json = serde_json::to_value(&results).unwrap();
+        assert_eq!(json["results"]["kind"], "users");
+        assert_eq!(json["results"]["items"][0]["username"], "artist");
+        assert_eq!(json["next_offset"], 20);
+    }
+
+    #[test]
+    fn test_search_playlist_keeps_permalink() {
+        let raw: RawPlaylistInfo = serde_json::from_str(
+            r#"{
+                "id": 5,
+                "title": "Set",
+                "user": {"username": "owner"},
+                "artwork_url": null,
+                "track_count": 300,
+                "tracks": [],
+                "permalink_url": "https://soundcloud.com/owner/sets/set"
+            }"#,
+        )
+        .unwrap();
+        let playlist = PlaylistInfo::from(raw);
+        assert_eq!(
+            playlist.permalink_url.as_deref(),
+            Some("https://soundcloud.com/owner/sets/set")
+        );
+    }
 }
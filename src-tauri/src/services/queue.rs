@@ -1,19 +1,23 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use specta::Type;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Runtime};
 use tauri_plugin_shell::process::CommandChild;
 use tokio::sync::{watch, Mutex};
 
-use crate::models::error::{HasErrorCode, PipelineError, YtDlpError};
+use crate::models::error::{HasErrorCode, MetadataError, PipelineError, YtDlpError};
+use crate::services::artwork::{save_folder_art, ArtworkCache, FolderArtName};
 use crate::services::auth_choice::{AuthChoice, AuthChoiceState, DownloadAuthNeededEvent};
-use crate::services::metadata::TrackMetadata;
+use crate::services::metadata::{TagOptions, TrackMetadata};
+use crate::services::mix_splitter::MixSplitOptions;
 use crate::services::pipeline::{download_and_convert, PipelineConfig};
-use crate::services::ytdlp::PlaylistContext;
+use crate::services::playlist::AlbumMetadata;
+use crate::services::title_rules::TitleRuleEngine;
+use crate::services::ytdlp::{sanitize_filename, PlaylistContext};
 
 /// An item in the download queue.
-#[derive(Clone, Debug, Type)]
+#[derive(Clone, Debug, Default, Type)]
 pub struct QueueItem {
     pub track_url: String,
     pub track_id: String,
@@ -21,6 +25,29 @@ pub struct QueueItem {
     pub artist: String,
     pub artwork_url: Option<String>,
     pub track_number: Option<u32>,
+    pub genre: Option<String>,
+    pub release_date: Option<String>,
+    pub label: Option<String>,
+    pub isrc: Option<String>,
+    pub description: Option<String>,
+    pub permalink_url: Option<String>,
+    pub bpm: Option<f64>,
+    /// Duration in milliseconds.
+    pub duration: Option<u64>,
+}
+
+/// Subfolder of the output directory the tracks are saved to.
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum SubfolderNaming {
+    /// One folder for the queue, named after the album artist (the playlist
+    /// or profile owner).
+    AlbumArtist,
+    /// One folder per track artist.
+    TrackArtist,
+    /// One folder for the queue, named after the album (the playlist
+    /// title), so each set of a collection gets its own folder.
+    Playlist,
 }
 
 /// Event payload for queue progress updates.
@@ -40,6 +67,16 @@ pub struct QueueCompleteEvent {
     pub failed: u32,
     pub total: u32,
     pub failed_tracks: Vec<(String, String)>,
+    /// Downloaded files in queue order, for exporting the playlist.
+    pub completed_tracks: Vec<CompletedTrack>,
+}
+
+/// A track that finished downloading.
+#[derive(Clone, Debug, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct CompletedTrack {
+    pub track_id: String,
+    pub path: String,
 }
 
 /// Event payload for queue cancellation.
@@ -64,6 +101,14 @@ pub struct QueueProcessContext {
     pub active_child: Arc<Mutex<Option<CommandChild>>>,
     pub active_pid: Arc<Mutex<Option<u32>>>,
     pub auth_choice_state: Arc<AuthChoiceState>,
+    pub tag_options: TagOptions,
+    pub artwork_cache: Arc<ArtworkCache>,
+    /// Save the cover as `cover.jpg`/`folder.jpg` in the output directory
+    pub folder_art: Option<FolderArtName>,
+    /// Split downloaded mixes into songs
+    pub split_mix: Option<MixSplitOptions>,
+    /// Save tracks to an artist subfolder of `output_dir`
+    pub subfolder: Option<SubfolderNaming>,
 }
 
 /// Download queue manager for processing multiple tracks sequentially.
@@ -72,7 +117,14 @@ pub struct DownloadQueue {
     current_index: usize,
     is_processing: bool,
     album_name: Option<String>,
+    album_artist: Option<String>,
+    album: Option<AlbumMetadata>,
+    is_compilation: bool,
     total_tracks: u32,
+    /// Tracks in the source playlist, unavailable ones included. Track
+    /// numbers are playlist positions, so TRCK and file name prefixes count
+    /// against this rather than the queue length.
+    playlist_total: u32,
 }
 
 impl DownloadQueue {
@@ -83,8 +135,54 @@ impl DownloadQueue {
             current_index: 0,
             is_processing: false,
             album_name,
+            album_artist: None,
+            album: None,
+            is_compilation: false,
             total_tracks: total,
+            playlist_total: total,
+        }
+    }
+
+    /// Set the number of tracks in the source playlist (`PlaylistInfo.track_count`).
+    pub fn with_playlist_total(mut self, total: Option<u32>) -> Self {
+        if let Some(total) = total {
+            self.playlist_total = total.max(self.total_tracks);
         }
+        self
+    }
+
+    /// Clean up item titles and artists with the given title rules.
+    ///
+    /// Must run before [`Self::with_album`] so the compilation check sees
+    /// the cleaned artists.
+    pub fn with_title_rules(mut self, rules: Option<&TitleRuleEngine>) -> Self {
+        if let Some(rules) = rules {
+            for item in &mut self.items {
+                let cleaned = rules.apply(&item.title, &item.artist);
+                item.title = cleaned.title;
+                item.artist = cleaned.artist;
+            }
+        }
+        self
+    }
+
+    /// Set the album artist (TPE2) written to every track, usually the playlist owner.
+    pub fn with_album_artist(mut self, album_artist: Option<String>) -> Self {
+        self.album_artist = album_artist;
+        self
+    }
+
+    /// Enable album mode for an album/EP set.
+    ///
+    /// The playlist artwork, owner, release date, label and genre are applied
+    /// to every track, and the compilation flag is set when track artists differ.
+    pub fn with_album(mut self, album: Option<AlbumMetadata>) -> Self {
+        if let Some(album) = &album {
+            self.album_artist = Some(album.artist.clone());
+            self.is_compilation = has_multiple_artists(&self.items);
+        }
+        self.album = album;
+        self
     }
 
     /// Process all items in the queue sequentially.
@@ -108,8 +206,13 @@ impl DownloadQueue {
         let mut completed = 0u32;
         let mut failed = 0u32;
         let mut failed_tracks: Vec<(String, String)> = vec![];
+        let mut completed_tracks: Vec<CompletedTrack> = vec![];
         let mut retry_count = 0u32;
 
+        if let Some(name) = ctx.folder_art {
+            self.save_folder_art(&ctx, name).await;
+        }
+
         while self.current_index < self.items.len() {
             if *ctx.cancel_rx.borrow() {
                 log::info!("[queue] Cancellation requested, stopping queue");
@@ -137,10 +240,10 @@ impl DownloadQueue {
                 },
             );
 
-            let playlist_context = if self.total_tracks > 1 {
+            let playlist_context = if self.playlist_total > 1 {
                 Some(PlaylistContext {
                     track_position: item.track_number.unwrap_or((self.current_index + 1) as u32),
-                    total_tracks: self.total_tracks,
+                    total_tracks: self.playlist_total,
                 })
             } else {
                 None
@@ -149,16 +252,12 @@ impl DownloadQueue {
             let config = PipelineConfig {
                 track_url: item.track_url.clone(),
                 track_id: item.track_id.clone(),
-                output_dir: ctx.output_dir.clone(),
-                metadata: TrackMetadata {
-                    title: item.title.clone(),
-                    artist: item.artist.clone(),
-                    album: self.album_name.clone(),
-                    track_number: item.track_number,
-                    total_tracks: Some(self.total_tracks),
-                    artwork_url: item.artwork_url.clone(),
-                },
+                output_dir: self.track_output_dir(&ctx.output_dir, ctx.subfolder, item),
+                metadata: self.build_metadata(item),
                 playlist_context,
+                tag_options: ctx.tag_options.clone(),
+                artwork_cache: ctx.artwork_cache.clone(),
+                split_mix: ctx.split_mix.clone(),
             };
 
             match download_and_convert(
@@ -171,7 +270,7 @@ impl DownloadQueue {
             )
             .await
             {
-                Ok(_) => {
+                Ok(paths) => {
                     let _ = app.emit(
                         "download-progress",
                         serde_json::json!({
@@ -181,6 +280,10 @@ impl DownloadQueue {
                         }),
                     );
                     completed += 1;
+                    completed_tracks.extend(paths.iter().map(|path| CompletedTrack {
+                        track_id: item.track_id.clone(),
+                        path: path.to_string_lossy().to_string(),
+                    }));
                     retry_count = 0;
                 }
                 Err(PipelineError::Download(YtDlpError::Cancelled)) => {
@@ -308,11 +411,143 @@ impl DownloadQueue {
                 failed,
                 total: self.total_tracks,
                 failed_tracks: failed_tracks.clone(),
+                completed_tracks,
             },
         );
 
         QueueResult { completed, failed }
     }
+
+    /// Save the playlist cover next to the downloaded tracks.
+    ///
+    /// Uses the album artwork in album mode, otherwise the first track's
+    /// artwork. Failures are logged and do not affect the queue.
+    async fn save_folder_art(&self, ctx: &QueueProcessContext, name: FolderArtName) {
+        let Some(url) = self.cover_artwork_url() else {
+            return;
+        };
+
+        let result = match ctx
+            .artwork_cache
+            .get(url, ctx.tag_options.artwork_size)
+            .await
+        {
+            Ok(artwork) => {
+                let dir = self.folder_art_dir(&ctx.output_dir, ctx.subfolder);
+                tokio::task::spawn_blocking(move || save_folder_art(&dir, &artwork, name))
+                    .await
+                    .map_err(|e| MetadataError::WriteFailed(e.to_string()))
+                    .and_then(|result| result)
+            }
+            Err(e) => Err(e),
+        };
+
+        if let Err(e) = result {
+            log::warn!("[queue] Failed to save {}: {}", name.file_name(), e);
+        }
+    }
+
+    /// Directory a track is saved to, including its artist subfolder.
+    fn track_output_dir(
+        &self,
+        output_dir: &Path,
+        subfolder: Option<SubfolderNaming>,
+        item: &QueueItem,
+    ) -> PathBuf {
+        let name = match subfolder {
+            None => return output_dir.to_path_buf(),
+            Some(SubfolderNaming::AlbumArtist) => {
+                self.album_artist.as_deref().unwrap_or(&item.artist)
+            }
+            Some(SubfolderNaming::TrackArtist) => &item.artist,
+            Some(SubfolderNaming::Playlist) => self
+                .album_name
+                .as_deref()
+                .or(self.album_artist.as_deref())
+                .unwrap_or(&item.artist),
+        };
+        output_dir.join(subfolder_name(name))
+    }
+
+    /// Directory the folder art is saved to: the shared subfolder when all
+    /// tracks go to one, otherwise the output directory.
+    fn folder_art_dir(&self, output_dir: &Path, subfolder: Option<SubfolderNaming>) -> PathBuf {
+        let name = match subfolder {
+            Some(SubfolderNaming::AlbumArtist) => self.album_artist.as_ref(),
+            Some(SubfolderNaming::Playlist) => {
+                self.album_name.as_ref().or(self.album_artist.as_ref())
+            }
+            _ => None,
+        };
+        match name {
+            Some(name) => output_dir.join(subfolder_name(name)),
+            None => output_dir.to_path_buf(),
+        }
+    }
+
+    fn cover_artwork_url(&self) -> Option<&String> {
+        self.album
+            .as_ref()
+            .and_then(|a| a.artwork_url.as_ref())
+            .or_else(|| self.items.iter().find_map(|i| i.artwork_url.as_ref()))
+    }
+
+    /// Build the tags for a queue item, applying album-level values in album mode.
+    fn build_metadata(&self, item: &QueueItem) -> TrackMetadata {
+        let album = self.album.as_ref();
+
+        TrackMetadata {
+            title: item.title.clone(),
+            artist: item.artist.clone(),
+            album: self.album_name.clone(),
+            track_number: item.track_number,
+            total_tracks: Some(self.playlist_total),
+            artwork_url: album
+                .and_then(|a| a.artwork_url.clone())
+                .or_else(|| item.artwork_url.clone()),
+            album_artist: self.album_artist.clone(),
+            genre: album
+                .and_then(|a| a.genre.clone())
+                .or_else(|| item.genre.clone()),
+            release_date: album
+                .and_then(|a| a.release_date.clone())
+                .or_else(|| item.release_date.clone()),
+            label: album
+                .and_then(|a| a.label.clone())
+                .or_else(|| item.label.clone()),
+            isrc: item.isrc.clone(),
+            description: item.description.clone(),
+            permalink_url: item.permalink_url.clone(),
+            bpm: item.bpm,
+            compilation: self.is_compilation,
+            track_id: Some(item.track_id.clone()),
+            duration: item.duration,
+            mix_url: None,
+        }
+    }
+}
+
+/// Folder name for an artist, safe on every platform.
+///
+/// Windows drops trailing dots and spaces from folder names, so they are
+/// trimmed to keep paths stable across systems.
+fn subfolder_name(artist: &str) -> String {
+    let name = sanitize_filename(artist);
+    let name = name.trim().trim_end_matches('.').trim_end();
+    if name.is_empty() {
+        "Unknown Artist".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// Whether the items were made by more than one artist (case-insensitive).
+fn has_multiple_artists(items: &[QueueItem]) -> bool {
+    let mut artists = items.iter().map(|i| i.artist.trim().to_lowercase());
+    match artists.next() {
+        Some(first) => artists.any(|a| a != first),
+        None => false,
+    }
 }
 
 fn calculate_backoff(retry_count: u32) -> u64 {
@@ -332,6 +567,7 @@ fn calculate_backoff(retry_count: u32) -> u64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::services::title_rules::TitleRules;
 
     #[test]
     fn test_queue_item_creation() {
@@ -342,6 +578,7 @@ mod tests {
             artist: "Artist".to_string(),
             artwork_url: Some("https://example.com/art.jpg".to_string()),
             track_number: Some(1),
+            ..Default::default()
         };
 
         assert_eq!(item.track_url, "https://soundcloud.com/test/track");
@@ -364,6 +601,7 @@ mod tests {
             artist: "Artist".to_string(),
             artwork_url: None,
             track_number: None,
+            ..Default::default()
         };
 
         let cloned = item.clone();
@@ -381,6 +619,7 @@ mod tests {
                 artist: "Artist".to_string(),
                 artwork_url: None,
                 track_number: Some(1),
+                ..Default::default()
             },
             QueueItem {
                 track_url: "url2".to_string(),
@@ -389,6 +628,7 @@ mod tests {
                 artist: "Artist".to_string(),
                 artwork_url: None,
                 track_number: Some(2),
+                ..Default::default()
             },
         ];
 
@@ -398,6 +638,33 @@ mod tests {
         assert_eq!(queue.current_index, 0);
         assert!(!queue.is_processing);
         assert_eq!(queue.album_name, Some("Album Name".to_string()));
+        assert!(queue.album_artist.is_none());
+    }
+
+    #[test]
+    fn test_playlist_total_numbers_against_the_playlist() {
+        let item = QueueItem {
+            track_id: "1".to_string(),
+            track_number: Some(50),
+            ..Default::default()
+        };
+        let queue = DownloadQueue::new(vec![item.clone()], None).with_playlist_total(Some(50));
+
+        assert_eq!(queue.total_tracks, 1);
+        let metadata = queue.build_metadata(&item);
+        assert_eq!(metadata.track_number, Some(50));
+        assert_eq!(metadata.total_tracks, Some(50));
+
+        let queue = DownloadQueue::new(vec![item.clone()], None);
+        assert_eq!(queue.build_metadata(&item).total_tracks, Some(1));
+    }
+
+    #[test]
+    fn test_download_queue_with_album_artist() {
+        let queue = DownloadQueue::new(vec![], Some("Album Name".to_string()))
+            .with_album_artist(Some("Owner".to_string()));
+
+        assert_eq!(queue.album_artist, Some("Owner".to_string()));
     }
 
     #[test]
@@ -409,6 +676,180 @@ mod tests {
         assert!(queue.album_name.is_none());
     }
 
+    fn album_item(id: &str, artist: &str) -> QueueItem {
+        QueueItem {
+            track_url: format!("url{}", id),
+            track_id: id.to_string(),
+            title: format!("Track {}", id),
+            artist: artist.to_string(),
+            artwork_url: Some(format!("https://example.com/track{}.jpg", id)),
+            track_number: id.parse().ok(),
+            release_date: Some("2020-01-01T00:00:00Z".to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn album_metadata() -> AlbumMetadata {
+        AlbumMetadata {
+            artist: "Owner".to_string(),
+            artwork_url: Some("https://example.com/album.jpg".to_string()),
+            release_date: Some("2024-02-01T00:00:00Z".to_string()),
+            label: Some("Label".to_string()),
+            genre: None,
+        }
+    }
+
+    #[test]
+    fn test_album_mode_applies_playlist_metadata() {
+        let items = vec![album_item("1", "Artist"), album_item("2", "artist ")];
+        let queue =
+            DownloadQueue::new(items, Some("Album".to_string())).with_album(Some(album_metadata()));
+
+        assert_eq!(queue.album_artist, Some("Owner".to_string()));
+        assert!(!queue.is_compilation);
+
+        let metadata = queue.build_metadata(&queue.items[0]);
+        assert_eq!(metadata.album, Some("Album".to_string()));
+        assert_eq!(metadata.album_artist, Some("Owner".to_string()));
+        assert_eq!(
+            metadata.artwork_url,
+            Some("https://example.com/album.jpg".to_string())
+        );
+        assert_eq!(
+            metadata.release_date,
+            Some("2024-02-01T00:00:00Z".to_string())
+        );
+        assert_eq!(metadata.label, Some("Label".to_string()));
+        assert!(!metadata.compilation);
+    }
+
+    #[test]
+    fn test_album_mode_sets_compilation_when_artists_differ() {
+        let items = vec![album_item("1", "Artist A"), album_item("2", "Artist B")];
+        let queue = DownloadQueue::new(items, Some("Various".to_string()))
+            .with_album(Some(album_metadata()));
+
+        assert!(queue.is_compilation);
+        assert!(queue.build_metadata(&queue.items[1]).compilation);
+    }
+
+    #[test]
+    fn test_without_album_mode_uses_track_metadata() {
+        let items = vec![album_item("1", "Artist A"), album_item("2", "Artist B")];
+        let queue = DownloadQueue::new(items, Some("Playlist".to_string()));
+
+        let metadata = queue.build_metadata(&queue.items[1]);
+        assert_eq!(
+            metadata.artwork_url,
+            Some("https://example.com/track2.jpg".to_string())
+        );
+        assert_eq!(
+            metadata.release_date,
+            Some("2020-01-01T00:00:00Z".to_string())
+        );
+        assert!(metadata.album_artist.is_none());
+        assert!(!metadata.compilation);
+    }
+
+    #[test]
+    fn test_title_rules_applied_before_album_mode() {
+        let mut items = vec![
+            album_item("1", "Some Records"),
+            album_item("2", "Some Records"),
+        ];
+        items[0].title = "Artist A - First (Free Download)".to_string();
+        items[1].title = "Artist B - Second".to_string();
+        let engine = TitleRuleEngine::new(TitleRules::default()).unwrap();
+
+        let queue = DownloadQueue::new(items, Some("Compilation".to_string()))
+            .with_title_rules(Some(&engine))
+            .with_album(Some(album_metadata()));
+
+        let metadata = queue.build_metadata(&queue.items[0]);
+        assert_eq!(metadata.artist, "Artist A");
+        assert_eq!(metadata.title, "First");
+        assert!(queue.is_compilation);
+    }
+
+    #[test]
+    fn test_cover_artwork_url_prefers_album_artwork() {
+        let items = vec![album_item("1", "Artist")];
+        let queue = DownloadQueue::new(items, None).with_album(Some(album_metadata()));
+
+        assert_eq!(
+            queue.cover_artwork_url().map(String::as_str),
+            Some("https://example.com/album.jpg")
+        );
+    }
+
+    #[test]
+    fn test_cover_artwork_url_falls_back_to_first_track() {
+        let mut first = album_item("1", "Artist");
+        first.artwork_url = None;
+        let queue = DownloadQueue::new(vec![first, album_item("2", "Artist")], None);
+
+        assert_eq!(
+            queue.cover_artwork_url().map(String::as_str),
+            Some("https://example.com/track2.jpg")
+        );
+    }
+
+    #[test]
+    fn test_track_output_dir_subfolders() {
+        let output = Path::new("/music");
+        let queue = DownloadQueue::new(vec![], None).with_album_artist(Some("Owner".to_string()));
+        let item = QueueItem {
+            artist: "AC/DC".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(queue.track_output_dir(output, None, &item), output);
+        assert_eq!(
+            queue.track_output_dir(output, Some(SubfolderNaming::AlbumArtist), &item),
+            output.join("Owner")
+        );
+        assert_eq!(
+            queue.track_output_dir(output, Some(SubfolderNaming::TrackArtist), &item),
+            output.join("AC_DC")
+        );
+        assert_eq!(
+            queue.folder_art_dir(output, Some(SubfolderNaming::TrackArtist)),
+            output
+        );
+    }
+
+    #[test]
+    fn test_playlist_subfolder_uses_album_name() {
+        let output = Path::new("/music");
+        let queue = DownloadQueue::new(vec![], Some("Summer EP".to_string()));
+        let item = QueueItem {
+            artist: "Artist".to_string(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            queue.track_output_dir(output, Some(SubfolderNaming::Playlist), &item),
+            output.join("Summer EP")
+        );
+        assert_eq!(
+            queue.folder_art_dir(output, Some(SubfolderNaming::Playlist)),
+            output.join("Summer EP")
+        );
+    }
+
+    #[test]
+    fn test_subfolder_name() {
+        assert_eq!(subfolder_name(" Mr. Oizo. "), "Mr. Oizo");
+        assert_eq!(subfolder_name("..."), "Unknown Artist");
+        assert_eq!(subfolder_name("A: B"), "A_ B");
+    }
+
+    #[test]
+    fn test_subfolder_naming_deserialize() {
+        let naming: SubfolderNaming = serde_json::from_str(r#""trackArtist""#).unwrap();
+        assert_eq!(naming, SubfolderNaming::TrackArtist);
+    }
+
     #[test]
     fn test_calculate_backoff_first() {
         assert_eq!(calculate_backoff(0), 1);
@@ -462,6 +903,10 @@ mod tests {
                 ("track1".to_string(), "Error 1".to_string()),
                 ("track2".to_string(), "Error 2".to_string()),
             ],
+            completed_tracks: vec![CompletedTrack {
+                track_id: "track3".to_string(),
+                path: "/music/track3.mp3".to_string(),
+            }],
         };
 
         let json = serde_json::to_string(&event).unwrap();
@@ -469,6 +914,9 @@ mod tests {
         assert!(json.contains("\"failed\":2"));
         assert!(json.contains("\"total\":10"));
         assert!(json.contains("\"failedTracks\""));
+        assert!(json.contains(
+            "\"completedTracks\":[{\"trackId\":\"track3\",\"path\":\"/music/track3.mp3\"}]"
+        ));
     }
 
     #[test]
@@ -0,0 +1,295 @@
+//! Client-side rate limiting of SoundCloud API requests.
+//!
+//! Every request to the API hosts takes a token from one shared bucket, so
+//! bursts (e.g. fetching hundreds of tracks of a playlist) are spread out.
+//! When the API still answers 429, all callers pause until the requested
+//! delay has passed, and the UI is told through a status listener.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use specta::Type;
+use tokio::time::sleep;
+
+/// Hosts whose requests count against the API rate limit.
+const API_HOSTS: &[&str] = &["api.soundcloud.com", "api-v2.soundcloud.com"];
+
+/// Upper bound of concurrent metadata requests for a single operation.
+pub const MAX_CONCURRENT_REQUESTS: usize = 8;
+
+/// Token bucket parameters.
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitConfig {
+    /// Requests that can be sent at once after a quiet period.
+    pub burst: u32,
+    /// Sustained requests per second.
+    pub per_second: f64,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            burst: 10,
+            per_second: 8.0,
+        }
+    }
+}
+
+/// Rate limit state reported to the UI as `rate-limit-status`.
+#[derive(Clone, Debug, PartialEq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimitStatus {
+    /// Whether requests are paused after a 429.
+    pub limited: bool,
+    /// Time until requests resume, in milliseconds.
+    pub resume_in_ms: u64,
+}
+
+type StatusListener = Box<dyn Fn(RateLimitStatus) + Send + Sync>;
+
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    refilled_at: Instant,
+    paused_until: Option<Instant>,
+}
+
+impl Bucket {
+    fn new(config: &RateLimitConfig, now: Instant) -> Self {
+        Self {
+            tokens: config.burst as f64,
+            refilled_at: now,
+            paused_until: None,
+        }
+    }
+
+    /// Takes a token, or returns how long to wait before trying again.
+    fn try_take(&mut self, config: &RateLimitConfig, now: Instant) -> Result<(), Duration> {
+        if let Some(until) = self.paused_until {
+            if until > now {
+                return Err(until - now);
+            }
+            self.paused_until = None;
+        }
+
+        let elapsed = now
+            .saturating_duration_since(self.refilled_at)
+            .as_secs_f64();
+        self.tokens = (self.tokens + elapsed * config.per_second).min(config.burst as f64);
+        self.refilled_at = now;
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - self.tokens) / config.per_second,
+            ))
+        }
+    }
+
+    /// Pauses until `until`, keeping a later pause already in place.
+    /// Returns whether the pause was extended.
+    fn pause(&mut self, until: Instant) -> bool {
+        if self.paused_until.is_some_and(|current| current >= until) {
+            return false;
+        }
+        // Start refilling from the end of the pause to avoid a burst then
+        self.paused_until = Some(until);
+        self.tokens = 0.0;
+        self.refilled_at = until;
+        true
+    }
+}
+
+/// Shared token bucket with a global pause on rate-limit responses.
+pub struct RateLimiter {
+    config: RateLimitConfig,
+    bucket: Mutex<Bucket>,
+    /// Set while paused, so the resume is reported exactly once.
+    limited: Mutex<bool>,
+    listener: Mutex<Option<StatusListener>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: RateLimitConfig) -> Self {
+        Self {
+            bucket: Mutex::new(Bucket::new(&config, Instant::now())),
+            config,
+            limited: Mutex::new(false),
+            listener: Mutex::new(None),
+        }
+    }
+
+    /// Registers the callback receiving rate limit status changes.
+    pub fn set_listener(&self, listener: impl Fn(RateLimitStatus) + Send + Sync + 'static) {
+        *self.listener.lock().unwrap() = Some(Box::new(listener));
+    }
+
+    fn notify(&self, status: RateLimitStatus) {
+        if let Some(listener) = self.listener.lock().unwrap().as_ref() {
+            listener(status);
+        }
+    }
+
+    /// Waits until a request may be sent.
+    pub async fn acquire(&self) {
+        loop {
+            let result = self
+                .bucket
+                .lock()
+                .unwrap()
+                .try_take(&self.config, Instant::now());
+
+            match result {
+                Ok(()) => break,
+                Err(wait) => sleep(wait).await,
+            }
+        }
+
+        let resumed = std::mem::replace(&mut *self.limited.lock().unwrap(), false);
+        if resumed {
+            log::info!("[rate-limit] Resuming API requests");
+            self.notify(RateLimitStatus {
+                limited: false,
+                resume_in_ms: 0,
+            });
+        }
+    }
+
+    /// Pauses every caller for `delay` after the API answered 429.
+    pub fn back_off(&self, delay: Duration) {
+        let extended = self.bucket.lock().unwrap().pause(Instant::now() + delay);
+
+        if extended {
+            *self.limited.lock().unwrap() = true;
+            log::warn!(
+                "[rate-limit] Rate limited, pausing API requests for {:?}",
+                delay
+            );
+            self.notify(RateLimitStatus {
+                limited: true,
+                resume_in_ms: delay.as_millis() as u64,
+            });
+        }
+    }
+}
+
+static API_LIMITER: Lazy<RateLimiter> = Lazy::new(|| RateLimiter::new(RateLimitConfig::default()));
+
+/// The limiter shared by all SoundCloud API requests.
+pub fn api_limiter() -> &'static RateLimiter {
+    &API_LIMITER
+}
+
+/// The limiter a request to `url` goes through, if any.
+pub fn limiter_for(url: &url::Url) -> Option<&'static RateLimiter> {
+    let host = url.host_str()?;
+    API_HOSTS.contains(&host).then(api_limiter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    const CONFIG: RateLimitConfig = RateLimitConfig {
+        burst: 2,
+        per_second: 4.0,
+    };
+
+    #[test]
+    fn test_bucket_allows_burst_then_waits() {
+        let now = Instant::now();
+        let mut bucket = Bucket::new(&CONFIG, now);
+
+        assert!(bucket.try_take(&CONFIG, now).is_ok());
+        assert!(bucket.try_take(&CONFIG, now).is_ok());
+        assert_eq!(
+            bucket.try_take(&CONFIG, now),
+            Err(Duration::from_millis(250))
+        );
+
+        // One token is back after 1/4 s
+        assert!(bucket
+            .try_take(&CONFIG, now + Duration::from_millis(250))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_bucket_refill_is_capped_at_burst() {
+        let now = Instant::now();
+        let mut bucket = Bucket::new(&CONFIG, now);
+        let later = now + Duration::from_secs(60);
+
+        assert!(bucket.try_take(&CONFIG, later).is_ok());
+        assert!(bucket.try_take(&CONFIG, later).is_ok());
+        assert!(bucket.try_take(&CONFIG, later).is_err());
+    }
+
+    #[test]
+    fn test_bucket_pause_blocks_until_elapsed() {
+        let now = Instant::now();
+        let mut bucket = Bucket::new(&CONFIG, now);
+
+        assert!(bucket.pause(now + Duration::from_secs(5)));
+        // A shorter pause does not cut the current one short
+        assert!(!bucket.pause(now + Duration::from_secs(1)));
+
+        assert_eq!(
+            bucket.try_take(&CONFIG, now + Duration::from_secs(2)),
+            Err(Duration::from_secs(3))
+        );
+        // Tokens refill from the end of the pause
+        let resumed = now + Duration::from_secs(5);
+        assert_eq!(
+            bucket.try_take(&CONFIG, resumed),
+            Err(Duration::from_millis(250))
+        );
+        assert!(bucket
+            .try_take(&CONFIG, resumed + Duration::from_millis(250))
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_back_off_reports_status() {
+        let limiter = RateLimiter::new(CONFIG);
+        let statuses = Arc::new(Mutex::new(Vec::new()));
+        let recorded = statuses.clone();
+        limiter.set_listener(move |status| recorded.lock().unwrap().push(status));
+
+        limiter.back_off(Duration::from_millis(20));
+        limiter.acquire().await;
+        limiter.acquire().await;
+
+        let statuses = statuses.lock().unwrap();
+        assert_eq!(statuses.len(), 2);
+        assert!(statuses[0].limited);
+        assert_eq!(statuses[0].resume_in_ms, 20);
+        assert!(!statuses[1].limited);
+    }
+
+    #[test]
+    fn test_limiter_for_api_hosts_only() {
+        let url = |s: &str| url::Url::parse(s).unwrap();
+
+        assert!(limiter_for(&url("https://api.soundcloud.com/tracks?ids=1")).is_some());
+        assert!(
+            limiter_for(&url("https://api-v2.soundcloud.com/stream/users/1/reposts")).is_some()
+        );
+        assert!(limiter_for(&url("https://soundcloud.com/artist/sets/mix")).is_none());
+        assert!(limiter_for(&url("https://i1.sndcdn.com/artworks-1-large.jpg")).is_none());
+    }
+
+    #[test]
+    fn test_rate_limit_status_serialize() {
+        let json = serde_json::to_string(&RateLimitStatus {
+            limited: true,
+            resume_in_ms: 5000,
+        })
+        .unwrap();
+        assert_eq!(json, r#"{"limited":true,"resumeInMs":5000}"#);
+    }
+}
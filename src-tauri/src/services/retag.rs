@@ -0,0 +1,918 @@
+//! Re-tagging of previously downloaded files from SoundCloud metadata.
+//!
+//! Files are identified by the track ID frame written since this feature
+//! was added, by a SoundCloud URL frame, or by matching the file name
+//! against the download history. Files from older versions carry none of
+//! these; they are searched for by artist and title and only matched when
+//! a result has the same duration.
+
+use id3::{Tag, TagLike};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
+
+use crate::models::url::UrlType;
+use crate::services::artwork::ArtworkCache;
+use crate::services::ffmpeg::probe_duration;
+use crate::services::history::{find_by_file_name, HistoryEntry};
+use crate::services::metadata::{
+    apply_metadata, embed_metadata, TagOptions, TagWriteMode, TrackMetadata, MIX_URL_FRAME,
+    TRACK_ID_FRAME,
+};
+use crate::services::metadata_cache::MetadataCache;
+use crate::services::playlist::{
+    fetch_track_info, fetch_track_info_by_id, search, SearchFilters, SearchItems, SearchKind,
+    TrackInfo,
+};
+use crate::services::tag_profile::{self, upgrade_frames, TagProfile};
+use crate::services::title_rules::{TitleRuleEngine, TitleRules};
+use crate::services::url_validator::{normalize_url, validate_url};
+
+/// File extensions scanned for re-tagging.
+const AUDIO_EXTENSIONS: &[&str] = &["mp3"];
+
+/// Largest difference between the duration of a file and a search result
+/// for the result to count as the same track.
+const MATCH_DURATION_TOLERANCE_MS: u64 = 2_000;
+
+/// Search results considered when matching a file by artist and title.
+const SEARCH_LIMIT: u32 = 10;
+
+/// Options for a re-tag run.
+#[derive(Debug, Clone, Default, Deserialize, Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct RetagOptions {
+    /// Only report proposed changes, don't write anything.
+    pub dry_run: bool,
+    /// Only convert existing tags to `tag_options.profile`, without looking
+    /// up SoundCloud metadata.
+    pub convert_only: bool,
+    pub tag_options: TagOptions,
+    pub title_rules: Option<TitleRules>,
+}
+
+/// Outcome for a single file.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Type)]
+#[serde(rename_all = "snake_case")]
+pub enum RetagStatus {
+    Updated,
+    /// Dry run: the file would be updated.
+    WouldUpdate,
+    Unchanged,
+    /// No SoundCloud track could be matched to the file.
+    Unidentified,
+    Failed,
+}
+
+/// A single tag field that differs between the file and SoundCloud.
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+pub struct TagChange {
+    pub field: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// Result for a single file.
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RetagFileResult {
+    pub path: String,
+    pub track_id: Option<String>,
+    pub status: RetagStatus,
+    pub changes: Vec<TagChange>,
+    pub error: Option<String>,
+}
+
+/// Summary of a re-tag run.
+#[derive(Debug, Clone, Default, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RetagSummary {
+    pub dry_run: bool,
+    pub total: u32,
+    pub updated: u32,
+    pub unchanged: u32,
+    pub unidentified: u32,
+    pub failed: u32,
+    pub results: Vec<RetagFileResult>,
+}
+
+/// Event payload for re-tag progress updates.
+#[derive(Clone, Debug, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RetagProgressEvent {
+    pub current: u32,
+    pub total: u32,
+    pub path: String,
+    pub status: RetagStatus,
+}
+
+/// How a file was matched to a SoundCloud track.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrackRef {
+    Id(u64),
+    Url(String),
+}
+
+/// Recursively lists audio files in a directory, sorted by path.
+pub fn scan_audio_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&current) else {
+            log::warn!("[retag] Failed to read directory {:?}", current);
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if has_audio_extension(&path) {
+                files.push(path);
+            }
+        }
+    }
+
+    files.sort();
+    files
+}
+
+fn has_audio_extension(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| AUDIO_EXTENSIONS.iter().any(|a| e.eq_ignore_ascii_case(a)))
+        .unwrap_or(false)
+}
+
+/// Identifies the SoundCloud track of a file.
+///
+/// Checks, in order: the track ID frame, the WOAS/WOAF URL frames, any
+/// TXXX frame holding a SoundCloud URL (yt-dlp writes `purl`), then the
+/// download history by file name. Pieces split from a mix are not tracks
+/// of their own and are never identified.
+pub fn identify_track(
+    tag: Option<&Tag>,
+    file_name: &str,
+    history: &[HistoryEntry],
+) -> Option<TrackRef> {
+    if let Some(tag) = tag {
+        if tag.extended_texts().any(|t| t.description == MIX_URL_FRAME) {
+            return None;
+        }
+
+        let track_id = tag
+            .extended_texts()
+            .find(|t| t.description == TRACK_ID_FRAME)
+            .and_then(|t| t.value.trim().parse().ok());
+        if let Some(id) = track_id {
+            return Some(TrackRef::Id(id));
+        }
+
+        let urls = ["WOAS", "WOAF"]
+            .iter()
+            .filter_map(|id| tag.get(id).and_then(|f| f.content().link()))
+            .chain(tag.extended_texts().map(|t| t.value.as_str()));
+        for url in urls {
+            if let Some(track_ref) = parse_track_url(url) {
+                return Some(track_ref);
+            }
+        }
+    }
+
+    find_by_file_name(history, file_name)
+        .and_then(|entry| entry.track_id.parse().ok().map(TrackRef::Id))
+}
+
+/// Parses a SoundCloud track URL (permalink or API URL).
+fn parse_track_url(value: &str) -> Option<TrackRef> {
+    let url = url::Url::parse(value.trim()).ok()?;
+    let host = url.host_str()?;
+
+    if host == "api.soundcloud.com" {
+        let mut segments = url.path_segments()?;
+        if segments.next() == Some("tracks") {
+            return segments.next()?.parse().ok().map(TrackRef::Id);
+        }
+        return None;
+    }
+
+    // Playlists, profiles and profile tabs are not tracks
+    let url = normalize_url(value, false)?;
+    (validate_url(&url).url_type == Some(UrlType::Track)).then_some(TrackRef::Url(url))
+}
+
+/// Artist and title to search for, from the TPE1/TIT2 frames or else an
+/// `Artist - Title` file name (optionally prefixed with a track number, as
+/// playlist downloads are).
+pub fn search_terms(tag: Option<&Tag>, file_name: &str) -> Option<(String, String)> {
+    let non_empty = |artist: &str, title: &str| {
+        let (artist, title) = (artist.trim(), title.trim());
+        (!artist.is_empty() && !title.is_empty()).then(|| (artist.to_string(), title.to_string()))
+    };
+
+    if let Some((artist, title)) = tag.and_then(|t| t.artist().zip(t.title())) {
+        if let Some(terms) = non_empty(artist, title) {
+            return Some(terms);
+        }
+    }
+
+    let stem = Path::new(file_name).file_stem()?.to_str()?;
+    let stem = match stem.split_once(" - ") {
+        Some((number, rest))
+            if number.chars().all(|c| c.is_ascii_digit()) && rest.contains(" - ") =>
+        {
+            rest
+        }
+        _ => stem,
+    };
+    let (artist, title) = stem.split_once(" - ")?;
+    non_empty(artist, title)
+}
+
+/// Lowercase letters and digits only, so punctuation and spacing don't
+/// prevent a match.
+fn match_key(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Picks the search result that is confidently the file: its duration is
+/// within [`MATCH_DURATION_TOLERANCE_MS`], its title contains the file's
+/// title and its uploader or title contains the artist. The closest
+/// duration wins.
+pub fn confident_match(
+    candidates: Vec<TrackInfo>,
+    artist: &str,
+    title: &str,
+    duration_ms: u64,
+) -> Option<TrackInfo> {
+    let (artist, title) = (match_key(artist), match_key(title));
+    if artist.is_empty() || title.is_empty() {
+        return None;
+    }
+
+    candidates
+        .into_iter()
+        .filter(|track| {
+            let track_title = match_key(&track.title);
+            track_title.contains(&title)
+                && (match_key(&track.user.username).contains(&artist)
+                    || track_title.contains(&artist))
+        })
+        .map(|track| (track.duration.abs_diff(duration_ms), track))
+        .filter(|(diff, _)| *diff <= MATCH_DURATION_TOLERANCE_MS)
+        .min_by_key(|(diff, _)| *diff)
+        .map(|(_, track)| track)
+}
+
+/// Finds the SoundCloud track of a file without SoundCloud frames by
+/// searching for its artist and title.
+async fn find_by_search<R: Runtime>(
+    app: &AppHandle<R>,
+    path: &Path,
+    tag: Option<&Tag>,
+    file_name: &str,
+) -> Option<TrackInfo> {
+    let (artist, title) = search_terms(tag, file_name)?;
+
+    let duration = match tag.and_then(|t| t.duration()) {
+        Some(duration) => u64::from(duration),
+        None => probe_duration(app, path)
+            .await
+            .map_err(|e| log::warn!("[retag] Could not read duration of {:?}: {}", path, e))
+            .ok()?,
+    };
+
+    let filters = SearchFilters {
+        min_duration: Some(duration.saturating_sub(MATCH_DURATION_TOLERANCE_MS)),
+        max_duration: Some(duration + MATCH_DURATION_TOLERANCE_MS),
+        ..Default::default()
+    };
+    let query = format!("{} {}", artist, title);
+    let results = search(&query, SearchKind::Tracks, SEARCH_LIMIT, 0, &filters)
+        .await
+        .map_err(|e| log::warn!("[retag] Search for '{}' failed: {}", query, e))
+        .ok()?;
+
+    let SearchItems::Tracks(tracks) = results.results else {
+        return None;
+    };
+    let found = confident_match(tracks, &artist, &title, duration);
+    if let Some(track) = &found {
+        log::info!("[retag] Matched {:?} to track {} by search", path, track.id);
+    }
+    found
+}
+
+/// Builds track metadata from fetched track info.
+pub fn metadata_from_track_info(
+    info: TrackInfo,
+    title_rules: Option<&TitleRuleEngine>,
+) -> TrackMetadata {
+    let (title, artist) = match title_rules {
+        Some(rules) => {
+            let cleaned = rules.apply(&info.title, &info.user.username);
+            (cleaned.title, cleaned.artist)
+        }
+        None => (info.title, info.user.username),
+    };
+
+    TrackMetadata {
+        title,
+        artist,
+        artwork_url: info.artwork_url,
+        genre: info.genre,
+        release_date: info.release_date,
+        label: info.label,
+        isrc: info.isrc,
+        description: info.description,
+        permalink_url: info.permalink_url,
+        bpm: info.bpm,
+        track_id: Some(info.id.to_string()),
+        duration: Some(info.duration),
+        ..Default::default()
+    }
+}
+
+/// Lists the fields that differ between two tags.
+pub fn diff_tags(old: &Tag, new: &Tag) -> Vec<TagChange> {
+    const TEXT_FIELDS: &[(&str, &str)] = &[
+        ("title", "TIT2"),
+        ("artist", "TPE1"),
+        ("album", "TALB"),
+        ("album_artist", "TPE2"),
+        ("track", "TRCK"),
+        ("genre", "TCON"),
+        ("date", "TDRC"),
+        ("label", "TPUB"),
+        ("isrc", "TSRC"),
+        ("bpm", "TBPM"),
+        ("compilation", "TCMP"),
+    ];
+
+    let text = |tag: &Tag, id: &str| {
+        tag.get(id)
+            .and_then(|f| f.content().text())
+            .map(str::to_string)
+    };
+    let comment = |tag: &Tag| {
+        tag.comments()
+            .find(|c| c.description.is_empty())
+            .map(|c| c.text.clone())
+    };
+    let url = |tag: &Tag| {
+        tag.get("WOAS")
+            .and_then(|f| f.content().link())
+            .map(str::to_string)
+    };
+    let track_id = |tag: &Tag| {
+        tag.extended_texts()
+            .find(|t| t.description == TRACK_ID_FRAME)
+            .map(|t| t.value.clone())
+    };
+
+    let mut fields: Vec<(&str, Option<String>, Option<String>)> = TEXT_FIELDS
+        .iter()
+        .map(|(field, id)| (*field, text(old, id), text(new, id)))
+        .collect();
+    fields.push(("comment", comment(old), comment(new)));
+    fields.push(("url", url(old), url(new)));
+    fields.push(("track_id", track_id(old), track_id(new)));
+
+    fields
+        .into_iter()
+        .filter(|(_, old, new)| old != new)
+        .map(|(field, old, new)| TagChange {
+            field: field.to_string(),
+            old,
+            new,
+        })
+        .collect()
+}
+
+/// Proposed changes for a file, including missing artwork.
+fn proposed_changes(
+    existing: Option<&Tag>,
+    metadata: &TrackMetadata,
+    options: &TagOptions,
+) -> Vec<TagChange> {
+    let empty = Tag::new();
+    let existing = existing.unwrap_or(&empty);
+
+    let mut proposed = match options.write_mode {
+        TagWriteMode::Merge => existing.clone(),
+        TagWriteMode::Replace => Tag::new(),
+    };
+    apply_metadata(&mut proposed, metadata, None);
+
+    let mut changes = diff_tags(existing, &proposed);
+
+    if existing.pictures().next().is_none() {
+        if let Some(artwork_url) = &metadata.artwork_url {
+            changes.push(TagChange {
+                field: "artwork".to_string(),
+                old: None,
+                new: Some(artwork_url.clone()),
+            });
+        }
+    }
+
+    changes
+}
+
+/// Change of tag profile for a file, if its current profile differs.
+fn profile_change(current: TagProfile, target: TagProfile) -> Option<TagChange> {
+    (current != target).then(|| TagChange {
+        field: "profile".to_string(),
+        old: Some(current.label().to_string()),
+        new: Some(target.label().to_string()),
+    })
+}
+
+/// Rewrites a file's existing tag in another profile.
+fn convert_file(path: &Path, existing: Option<&Tag>, options: &RetagOptions) -> RetagFileResult {
+    let mut result = RetagFileResult {
+        path: path.to_string_lossy().to_string(),
+        track_id: None,
+        status: RetagStatus::Unchanged,
+        changes: vec![],
+        error: None,
+    };
+
+    let Some(tag) = existing else {
+        return result;
+    };
+    let target = options.tag_options.profile;
+    let Some(change) = profile_change(TagProfile::detect(path, tag), target) else {
+        return result;
+    };
+    result.changes.push(change);
+
+    if options.dry_run {
+        result.status = RetagStatus::WouldUpdate;
+        return result;
+    }
+
+    match tag_profile::write_tag(path, tag, target) {
+        Ok(()) => result.status = RetagStatus::Updated,
+        Err(e) => {
+            result.status = RetagStatus::Failed;
+            result.error = Some(e.to_string());
+        }
+    }
+
+    result
+}
+
+/// Fetches the track a file refers to. Tracks known by ID are looked up in
+/// the metadata cache first, so retagging a downloaded playlist does not
+/// request every track again.
+async fn fetch_track<R: Runtime>(
+    app: &AppHandle<R>,
+    track_ref: &TrackRef,
+) -> Result<TrackInfo, String> {
+    let id = match track_ref {
+        TrackRef::Id(id) => *id,
+        TrackRef::Url(url) => return fetch_track_info(url).await.map_err(|e| e.to_string()),
+    };
+
+    let cache = app
+        .try_state::<Arc<MetadataCache>>()
+        .map(|c| Arc::clone(&c));
+    if let Some(cache) = cache.clone() {
+        if let Ok(Some(track)) = tokio::task::spawn_blocking(move || cache.track(id)).await {
+            return Ok(track);
+        }
+    }
+
+    let track = fetch_track_info_by_id(id)
+        .await
+        .map_err(|e| e.to_string())?;
+    if let Some(cache) = cache {
+        let cached = track.clone();
+        tokio::task::spawn_blocking(move || cache.put_track(None, &cached));
+    }
+    Ok(track)
+}
+
+async fn retag_file<R: Runtime>(
+    app: &AppHandle<R>,
+    path: &Path,
+    options: &RetagOptions,
+    title_rules: Option<&TitleRuleEngine>,
+    history: &[HistoryEntry],
+    artwork_cache: &ArtworkCache,
+) -> RetagFileResult {
+    let mut result = RetagFileResult {
+        path: path.to_string_lossy().to_string(),
+        track_id: None,
+        status: RetagStatus::Unidentified,
+        changes: vec![],
+        error: None,
+    };
+
+    let existing = Tag::read_from_path(path).ok().map(|mut tag| {
+        upgrade_frames(&mut tag);
+        tag
+    });
+
+    if options.convert_only {
+        return convert_file(path, existing.as_ref(), options);
+    }
+
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let info = match identify_track(existing.as_ref(), &file_name, history) {
+        Some(track_ref) => match fetch_track(app, &track_ref).await {
+            Ok(info) => info,
+            Err(e) => {
+                result.status = RetagStatus::Failed;
+                result.error = Some(e);
+                return result;
+            }
+        },
+        None => match find_by_search(app, path, existing.as_ref(), &file_name).await {
+            Some(info) => info,
+            None => return result,
+        },
+    };
+
+    let metadata = metadata_from_track_info(info, title_rules);
+    result.track_id = metadata.track_id.clone();
+    result.changes = proposed_changes(existing.as_ref(), &metadata, &options.tag_options);
+    if let Some(tag) = &existing {
+        result.changes.extend(profile_change(
+            TagProfile::detect(path, tag),
+            options.tag_options.profile,
+        ));
+    }
+
+    if result.changes.is_empty() {
+        result.status = RetagStatus::Unchanged;
+        return result;
+    }
+
+    if options.dry_run {
+        result.status = RetagStatus::WouldUpdate;
+        return result;
+    }
+
+    match embed_metadata(path, metadata, &options.tag_options, artwork_cache).await {
+        Ok(()) => result.status = RetagStatus::Updated,
+        Err(e) => {
+            result.status = RetagStatus::Failed;
+            result.error = Some(e.to_string());
+        }
+    }
+
+    result
+}
+
+/// Re-tags every audio file in a directory.
+///
+/// Emits `retag-progress` after each file.
+pub async fn retag_directory<R: Runtime>(
+    app: &AppHandle<R>,
+    dir: &Path,
+    options: &RetagOptions,
+    history: &[HistoryEntry],
+    artwork_cache: &ArtworkCache,
+) -> Result<RetagSummary, String> {
+    let title_rules = options
+        .title_rules
+        .clone()
+        .map(TitleRuleEngine::new)
+        .transpose()?;
+
+    let files = scan_audio_files(dir);
+    let total = files.len() as u32;
+    log::info!("[retag] Found {} audio files in {:?}", total, dir);
+
+    let mut summary = RetagSummary {
+        dry_run: options.dry_run,
+        total,
+        ..Default::default()
+    };
+
+    for (i, path) in files.iter().enumerate() {
+        let result = retag_file(
+            app,
+            path,
+            options,
+            title_rules.as_ref(),
+            history,
+            artwork_cache,
+        )
+        .await;
+
+        match result.status {
+            RetagStatus::Updated | RetagStatus::WouldUpdate => summary.updated += 1,
+            RetagStatus::Unchanged => summary.unchanged += 1,
+            RetagStatus::Unidentified => summary.unidentified += 1,
+            RetagStatus::Failed => {
+                log::warn!("[retag] Failed {:?}: {:?}", path, result.error);
+                summary.failed += 1;
+            }
+        }
+
+        let _ = app.emit(
+            "retag-progress",
+            RetagProgressEvent {
+                current: (i + 1) as u32,
+                total,
+                path: result.path.clone(),
+                status: result.status,
+            },
+        );
+
+        summary.results.push(result);
+    }
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::mix_splitter::{segment_metadata, MixSegment};
+    use id3::frame::ExtendedText;
+    use id3::Frame;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn history_entry(id: &str, file_name: &str) -> HistoryEntry {
+        HistoryEntry {
+            track_id: id.to_string(),
+            track_url: format!("https://api.soundcloud.com/tracks/{}", id),
+            title: "Title".to_string(),
+            artist: "Artist".to_string(),
+            file_name: file_name.to_string(),
+            downloaded_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_scan_audio_files_recurses_and_filters() {
+        let dir = tempdir().unwrap();
+        fs::create_dir(dir.path().join("sub")).unwrap();
+        fs::write(dir.path().join("b.mp3"), b"").unwrap();
+        fs::write(dir.path().join("sub").join("a.MP3"), b"").unwrap();
+        fs::write(dir.path().join("cover.jpg"), b"").unwrap();
+
+        let files = scan_audio_files(dir.path());
+        assert_eq!(
+            files,
+            vec![
+                dir.path().join("b.mp3"),
+                dir.path().join("sub").join("a.MP3")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_identify_track_by_id_frame() {
+        let mut tag = Tag::new();
+        tag.add_frame(ExtendedText {
+            description: TRACK_ID_FRAME.to_string(),
+            value: "42".to_string(),
+        });
+        tag.add_frame(Frame::link("WOAS", "https://soundcloud.com/a/b"));
+
+        assert_eq!(
+            identify_track(Some(&tag), "x.mp3", &[]),
+            Some(TrackRef::Id(42))
+        );
+    }
+
+    #[test]
+    fn test_identify_track_by_url_frame() {
+        let mut tag = Tag::new();
+        tag.add_frame(Frame::link("WOAS", "https://soundcloud.com/artist/track"));
+
+        assert_eq!(
+            identify_track(Some(&tag), "x.mp3", &[]),
+            Some(TrackRef::Url(
+                "https://soundcloud.com/artist/track".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_identify_track_by_ytdlp_purl() {
+        let mut tag = Tag::new();
+        tag.add_frame(ExtendedText {
+            description: "purl".to_string(),
+            value: "https://soundcloud.com/artist/track".to_string(),
+        });
+
+        assert!(matches!(
+            identify_track(Some(&tag), "x.mp3", &[]),
+            Some(TrackRef::Url(_))
+        ));
+    }
+
+    #[test]
+    fn test_identify_track_by_history() {
+        let history = vec![history_entry("7", "Artist - Title.mp3")];
+
+        assert_eq!(
+            identify_track(None, "Artist - Title.mp3", &history),
+            Some(TrackRef::Id(7))
+        );
+        assert_eq!(
+            identify_track(Some(&Tag::new()), "Other.mp3", &history),
+            None
+        );
+    }
+
+    #[test]
+    fn test_identify_track_skips_split_pieces() {
+        let mix = TrackMetadata {
+            title: "Summer Mix".to_string(),
+            artist: "DJ".to_string(),
+            permalink_url: Some("https://soundcloud.com/dj/summer-mix".to_string()),
+            track_id: Some("1".to_string()),
+            ..Default::default()
+        };
+        let segment = MixSegment {
+            start_ms: 0,
+            end_ms: Some(60_000),
+            artist: Some("A".to_string()),
+            title: "One".to_string(),
+        };
+        let mut tag = Tag::new();
+        apply_metadata(&mut tag, &segment_metadata(&mix, &segment, 0, 2), None);
+        let history = vec![history_entry("", "01 - A - One.mp3")];
+
+        assert_eq!(
+            identify_track(Some(&tag), "01 - A - One.mp3", &history),
+            None
+        );
+        assert_eq!(identify_track(None, "01 - A - One.mp3", &history), None);
+    }
+
+    #[test]
+    fn test_search_terms_from_tags_or_file_name() {
+        let mut tag = Tag::new();
+        tag.set_artist("Artist");
+        tag.set_title("Title");
+        assert_eq!(
+            search_terms(Some(&tag), "whatever.mp3"),
+            Some(("Artist".to_string(), "Title".to_string()))
+        );
+
+        assert_eq!(
+            search_terms(Some(&Tag::new()), "05 - Some Artist - Track - Remix.mp3"),
+            Some(("Some Artist".to_string(), "Track - Remix".to_string()))
+        );
+        assert_eq!(
+            search_terms(None, "Artist - Title.mp3"),
+            Some(("Artist".to_string(), "Title".to_string()))
+        );
+        assert_eq!(search_terms(None, "untitled.mp3"), None);
+    }
+
+    #[test]
+    fn test_confident_match_requires_close_duration() {
+        let track = |id, title: &str, username: &str, duration| TrackInfo {
+            id,
+            title: title.to_string(),
+            user: crate::services::playlist::UserInfo {
+                username: username.to_string(),
+            },
+            duration,
+            ..Default::default()
+        };
+        let candidates = vec![
+            track(1, "Title (Extended Mix)", "Artist", 400_000),
+            track(2, "Title", "Artist", 181_500),
+            track(3, "Artist - Title", "Some Label", 180_200),
+            track(4, "Other", "Artist", 180_000),
+        ];
+
+        let found = confident_match(candidates.clone(), "Artist", "Title", 180_000).unwrap();
+        assert_eq!(found.id, 3);
+        assert!(confident_match(candidates, "Artist", "Title", 300_000).is_none());
+    }
+
+    #[test]
+    fn test_parse_track_url() {
+        assert_eq!(
+            parse_track_url("https://api.soundcloud.com/tracks/123"),
+            Some(TrackRef::Id(123))
+        );
+        assert_eq!(
+            parse_track_url("https://m.soundcloud.com/artist/track?si=abc"),
+            Some(TrackRef::Url(
+                "https://soundcloud.com/artist/track".to_string()
+            ))
+        );
+        assert!(parse_track_url("https://soundcloud.com/artist").is_none());
+        assert!(parse_track_url("https://soundcloud.com/artist/sets/mix").is_none());
+        assert!(parse_track_url("https://soundcloud.com/artist/likes").is_none());
+        assert!(parse_track_url("https://soundcloud.com/artist/tracks").is_none());
+        assert!(parse_track_url("https://soundcloud.com/discover/sets/x").is_none());
+        assert!(parse_track_url("https://example.com/a/b").is_none());
+        assert!(parse_track_url("not a url").is_none());
+    }
+
+    #[test]
+    fn test_metadata_from_track_info_applies_title_rules() {
+        let info = TrackInfo {
+            id: 5,
+            title: "Artist - Track (Free DL)".to_string(),
+            user: crate::services::playlist::UserInfo {
+                username: "Some Records".to_string(),
+            },
+            genre: Some("House".to_string()),
+            ..Default::default()
+        };
+        let rules = TitleRuleEngine::new(TitleRules::default()).unwrap();
+
+        let metadata = metadata_from_track_info(info, Some(&rules));
+        assert_eq!(metadata.artist, "Artist");
+        assert_eq!(metadata.title, "Track");
+        assert_eq!(metadata.genre, Some("House".to_string()));
+        assert_eq!(metadata.track_id, Some("5".to_string()));
+    }
+
+    #[test]
+    fn test_proposed_changes_lists_differences() {
+        let mut existing = Tag::new();
+        existing.set_title("Old Title");
+        existing.set_artist("Artist");
+
+        let metadata = TrackMetadata {
+            title: "New Title".to_string(),
+            artist: "Artist".to_string(),
+            genre: Some("Techno".to_string()),
+            artwork_url: Some("https://example.com/a.jpg".to_string()),
+            ..Default::default()
+        };
+
+        let changes = proposed_changes(Some(&existing), &metadata, &TagOptions::default());
+        let fields: Vec<_> = changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec!["title", "genre", "artwork"]);
+        assert_eq!(changes[0].old, Some("Old Title".to_string()));
+        assert_eq!(changes[0].new, Some("New Title".to_string()));
+    }
+
+    #[test]
+    fn test_proposed_changes_empty_when_up_to_date() {
+        let metadata = TrackMetadata {
+            title: "Title".to_string(),
+            artist: "Artist".to_string(),
+            track_id: Some("1".to_string()),
+            ..Default::default()
+        };
+        let mut existing = Tag::new();
+        apply_metadata(&mut existing, &metadata, None);
+
+        assert!(proposed_changes(Some(&existing), &metadata, &TagOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn test_convert_file_changes_profile() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("a.mp3");
+        fs::write(&path, [0xFF, 0xFB, 0x90, 0x00]).unwrap();
+        let mut tag = Tag::new();
+        tag.set_title("Title");
+        tag.write_to_path(&path, id3::Version::Id3v24).unwrap();
+
+        let options = RetagOptions {
+            convert_only: true,
+            tag_options: TagOptions {
+                profile: TagProfile::Id3v23WithV1,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let result = convert_file(&path, Some(&tag), &options);
+        assert_eq!(result.status, RetagStatus::Updated);
+        assert_eq!(
+            result.changes,
+            vec![TagChange {
+                field: "profile".to_string(),
+                old: Some("ID3v2.4".to_string()),
+                new: Some("ID3v2.3 + ID3v1".to_string()),
+            }]
+        );
+
+        let tag = Tag::read_from_path(&path).unwrap();
+        assert_eq!(tag.version(), id3::Version::Id3v23);
+        let result = convert_file(&path, Some(&tag), &options);
+        assert_eq!(result.status, RetagStatus::Unchanged);
+        assert!(result.changes.is_empty());
+    }
+}
@@ -0,0 +1,197 @@
+//! Resolving `on.soundcloud.com` share links.
+//!
+//! The mobile app shares short links that redirect to the real page. The
+//! redirects are followed one hop at a time without reading any page body,
+//! until they land on `soundcloud.com`; the type of that URL is then known.
+
+use reqwest::header::LOCATION;
+use reqwest::redirect::Policy;
+use serde::Serialize;
+use specta::Type;
+use url::Url;
+
+use crate::models::url::UrlType;
+use crate::services::http;
+use crate::services::playlist::PlaylistError;
+use crate::services::url_validator::{normalize_url, validate_url};
+
+/// Host of short share links.
+const SHARE_LINK_HOST: &str = "on.soundcloud.com";
+
+/// Redirects followed before giving up on a share link.
+const MAX_REDIRECTS: usize = 5;
+
+/// A share link resolved to its canonical page.
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ResolvedLink {
+    /// Canonical `https://soundcloud.com/...` URL.
+    pub url: String,
+    pub url_type: Option<UrlType>,
+}
+
+fn parse(url: &str) -> Option<Url> {
+    let url = url.trim();
+    Url::parse(url)
+        .or_else(|_| Url::parse(&format!("https://{}", url)))
+        .ok()
+}
+
+/// Whether `url` is an `on.soundcloud.com` short link.
+pub fn is_share_link(url: &str) -> bool {
+    parse(url).is_some_and(|u| u.host_str() == Some(SHARE_LINK_HOST))
+}
+
+/// Where a response sends the resolver next.
+#[derive(Debug, PartialEq)]
+enum Hop {
+    /// Landed on a SoundCloud page.
+    Done(String),
+    /// Redirected to another intermediate URL.
+    Follow(Url),
+}
+
+/// Reads the redirect target of a response to `current`.
+fn next_hop(current: &Url, location: Option<&str>) -> Result<Hop, PlaylistError> {
+    let location = location.ok_or_else(|| {
+        PlaylistError::FetchFailed("Share link did not redirect to SoundCloud".to_string())
+    })?;
+    let target = current
+        .join(location)
+        .map_err(|_| PlaylistError::FetchFailed(format!("Invalid redirect: {}", location)))?;
+
+    Ok(match normalize_url(target.as_str(), false) {
+        Some(url) if !is_share_link(&url) => Hop::Done(url),
+        _ => Hop::Follow(target),
+    })
+}
+
+/// Follows the redirects of a share link to its canonical SoundCloud URL.
+///
+/// Goes through the configured proxy like other requests. Only response
+/// headers are read.
+pub async fn resolve_share_link(url: &str) -> Result<ResolvedLink, PlaylistError> {
+    let mut current = parse(url)
+        .filter(|u| u.host_str() == Some(SHARE_LINK_HOST))
+        .ok_or_else(|| PlaylistError::FetchFailed("Not a share link".to_string()))?;
+
+    let client = http::build_client(&http::client().config(), Policy::none())
+        .map_err(|e| PlaylistError::FetchFailed(e.to_string()))?;
+
+    log::info!("[share-link] Resolving {}", current);
+    for _ in 0..MAX_REDIRECTS {
+        let response = client.get(current.clone()).send().await?;
+        if !response.status().is_redirection() {
+            return Err(PlaylistError::FetchFailed(format!(
+                "Share link returned HTTP {}",
+                response.status()
+            )));
+        }
+
+        let location = response
+            .headers()
+            .get(LOCATION)
+            .and_then(|value| value.to_str().ok());
+        match next_hop(&current, location)? {
+            Hop::Done(url) => {
+                log::info!("[share-link] Resolved to {}", url);
+                let url_type = validate_url(&url).url_type;
+                return Ok(ResolvedLink { url, url_type });
+            }
+            Hop::Follow(next) => current = next,
+        }
+    }
+
+    Err(PlaylistError::FetchFailed(
+        "Share link redirected too many times".to_string(),
+    ))
+}
+
+/// Resolves `url` if it is a share link, otherwise returns it unchanged.
+pub async fn canonicalize(url: &str) -> Result<String, PlaylistError> {
+    if is_share_link(url) {
+        Ok(resolve_share_link(url).await?.url)
+    } else {
+        Ok(url.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn url(s: &str) -> Url {
+        Url::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_is_share_link() {
+        assert!(is_share_link(
+            "https://on.soundcloud.com/rtg5VHcWCnHLN2fxYl"
+        ));
+        assert!(is_share_link("on.soundcloud.com/rtg5VHcWCnHLN2fxYl"));
+        assert!(!is_share_link("https://soundcloud.com/artist/track"));
+        assert!(!is_share_link("not a url at all"));
+    }
+
+    #[test]
+    fn test_next_hop_canonicalizes_mobile_links() {
+        let current = url("https://on.soundcloud.com/abc123");
+        assert_eq!(
+            next_hop(
+                &current,
+                Some("https://m.soundcloud.com/artist/sets/mix/?ref=clipboard&p=i&c=1&si=abc")
+            )
+            .unwrap(),
+            Hop::Done("https://soundcloud.com/artist/sets/mix".to_string())
+        );
+        assert_eq!(
+            next_hop(&current, Some("https://soundcloud.com/artist/demo/s-AbC12")).unwrap(),
+            Hop::Done("https://soundcloud.com/artist/demo/s-AbC12".to_string())
+        );
+    }
+
+    #[test]
+    fn test_next_hop_lands_on_soundcloud() {
+        let current = url("https://on.soundcloud.com/abc123");
+        assert_eq!(
+            next_hop(
+                &current,
+                Some("https://soundcloud.com/artist/track?si=x&utm_medium=text")
+            )
+            .unwrap(),
+            Hop::Done("https://soundcloud.com/artist/track".to_string())
+        );
+    }
+
+    #[test]
+    fn test_next_hop_follows_intermediate_redirects() {
+        let current = url("https://on.soundcloud.com/abc123");
+        assert_eq!(
+            next_hop(&current, Some("/abc123/")).unwrap(),
+            Hop::Follow(url("https://on.soundcloud.com/abc123/"))
+        );
+    }
+
+    #[test]
+    fn test_next_hop_without_location_fails() {
+        let current = url("https://on.soundcloud.com/abc123");
+        assert!(matches!(
+            next_hop(&current, None),
+            Err(PlaylistError::FetchFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_resolved_link_serialize() {
+        let json = serde_json::to_string(&ResolvedLink {
+            url: "https://soundcloud.com/artist/track".to_string(),
+            url_type: Some(UrlType::Track),
+        })
+        .unwrap();
+        assert_eq!(
+            json,
+            r#"{"url":"https://soundcloud.com/artist/track","urlType":"track"}"#
+        );
+    }
+}
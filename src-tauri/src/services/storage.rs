@@ -2,18 +2,24 @@
 //!
 //! This module provides encrypted token persistence using the operating system's
 //! native credential storage (Keychain on macOS, Credential Manager on Windows,
-//! Secret Service on Linux).
+//! Secret Service on Linux). Proxy settings are kept there too, since they may
+//! hold a password.
 
 use keyring::Entry;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::services::proxy::ProxySettings;
+
 /// Service name for keychain entries.
 const SERVICE_NAME: &str = "com.infrabooth.downloader";
 
 /// Account name for the OAuth tokens entry.
 const TOKENS_ACCOUNT: &str = "oauth_tokens";
 
+/// Account name for the proxy settings entry.
+const PROXY_ACCOUNT: &str = "proxy_settings";
+
 /// Errors that can occur during storage operations.
 #[derive(Debug, Error)]
 pub enum StorageError {
@@ -112,6 +118,26 @@ pub fn delete_tokens() -> Result<(), StorageError> {
     }
 }
 
+/// Stores the proxy settings in the OS keychain, so they are applied again
+/// on the next launch.
+pub fn store_proxy(settings: &ProxySettings) -> Result<(), StorageError> {
+    let entry = Entry::new(SERVICE_NAME, PROXY_ACCOUNT)?;
+    let json = serde_json::to_string(settings)?;
+    entry.set_password(&json)?;
+    Ok(())
+}
+
+/// Loads the proxy settings from the OS keychain, or None if they were
+/// never saved.
+pub fn load_proxy() -> Result<Option<ProxySettings>, StorageError> {
+    let entry = Entry::new(SERVICE_NAME, PROXY_ACCOUNT)?;
+    match entry.get_password() {
+        Ok(json) => Ok(Some(serde_json::from_str(&json)?)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(StorageError::from(e)),
+    }
+}
+
 /// Calculates the expiry timestamp from an `expires_in` duration.
 ///
 /// # Arguments
@@ -0,0 +1,577 @@
+//! Reading and editing tags of downloaded files.
+//!
+//! MP3, AIFF and WAV files carry ID3v2 tags and are edited with the `id3`
+//! crate. These are the only formats downloads are saved in. M4A, FLAC and
+//! Ogg files use their own tag formats (MP4 atoms, Vorbis comments) that
+//! `id3` cannot write, so they are deliberately out of scope and rejected
+//! with `UnsupportedFormat`, like any other container.
+
+use id3::frame::{Comment, Picture, PictureType};
+use id3::{Frame, Tag, TagLike};
+use serde::{Deserialize, Deserializer, Serialize};
+use specta::Type;
+use std::path::Path;
+
+use crate::models::error::MetadataError;
+use crate::services::artwork::{download_artwork_from_url, Artwork};
+use crate::services::metadata::parse_release_date;
+use crate::services::tag_profile::{self, upgrade_frames, TagProfile};
+
+/// Audio containers that can hold an ID3v2 tag.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Container {
+    Mp3,
+    Aiff,
+    Wav,
+}
+
+impl Container {
+    /// Determines the container from the file extension.
+    pub fn from_path(path: &Path) -> Result<Self, MetadataError> {
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())
+            .unwrap_or_default();
+
+        match ext.as_str() {
+            "mp3" => Ok(Container::Mp3),
+            "aif" | "aiff" => Ok(Container::Aiff),
+            "wav" => Ok(Container::Wav),
+            "m4a" | "mp4" | "aac" | "flac" | "ogg" | "oga" | "opus" => {
+                Err(MetadataError::UnsupportedFormat(format!(
+                    "{} (only MP3, AIFF and WAV tags can be edited)",
+                    ext
+                )))
+            }
+            _ => Err(MetadataError::UnsupportedFormat(if ext.is_empty() {
+                "no file extension".to_string()
+            } else {
+                ext
+            })),
+        }
+    }
+}
+
+/// Reads the tag of a file, returning an empty tag if it has none.
+///
+/// `id3` detects AIFF/WAV chunks from the file content, so the container
+/// only needs checking for support. ID3v2.3 date frames are upgraded to
+/// TDRC; the tag keeps its original version.
+pub fn read_tag(path: &Path) -> Result<Tag, MetadataError> {
+    Container::from_path(path)?;
+
+    match Tag::read_from_path(path) {
+        Ok(mut tag) => {
+            upgrade_frames(&mut tag);
+            Ok(tag)
+        }
+        Err(e) if matches!(e.kind, id3::ErrorKind::NoTag) => Ok(Tag::new()),
+        Err(e) => Err(MetadataError::ReadFailed(e.to_string())),
+    }
+}
+
+/// Writes a tag to a file using a profile.
+///
+/// Edits keep the file's existing profile (see [`TagProfile::detect`]).
+pub fn write_tag(path: &Path, tag: &Tag, profile: TagProfile) -> Result<(), MetadataError> {
+    Container::from_path(path)?;
+
+    tag_profile::write_tag(path, tag, profile)
+        .map_err(|e| MetadataError::WriteFailed(e.to_string()))
+}
+
+/// Embedded artwork summary.
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ArtworkSummary {
+    pub mime_type: String,
+    pub size_bytes: u32,
+}
+
+/// Editable tag fields of a file.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct EditableTags {
+    pub title: Option<String>,
+    pub artist: Option<String>,
+    pub album: Option<String>,
+    pub album_artist: Option<String>,
+    pub genre: Option<String>,
+    /// Recording date (`YYYY`, `YYYY-MM` or `YYYY-MM-DD`).
+    pub date: Option<String>,
+    pub track_number: Option<u32>,
+    pub total_tracks: Option<u32>,
+    pub label: Option<String>,
+    pub isrc: Option<String>,
+    pub bpm: Option<String>,
+    pub comment: Option<String>,
+    pub url: Option<String>,
+    pub artwork: Option<ArtworkSummary>,
+}
+
+impl EditableTags {
+    pub fn from_tag(tag: &Tag) -> Self {
+        let text = |id: &str| {
+            tag.get(id)
+                .and_then(|f| f.content().text())
+                .map(str::to_string)
+        };
+
+        Self {
+            title: tag.title().map(str::to_string),
+            artist: tag.artist().map(str::to_string),
+            album: tag.album().map(str::to_string),
+            album_artist: tag.album_artist().map(str::to_string),
+            genre: tag.genre().map(str::to_string),
+            date: tag.date_recorded().map(|d| d.to_string()),
+            track_number: tag.track(),
+            total_tracks: tag.total_tracks(),
+            label: text("TPUB"),
+            isrc: text("TSRC"),
+            bpm: text("TBPM"),
+            comment: tag
+                .comments()
+                .find(|c| c.description.is_empty())
+                .map(|c| c.text.clone()),
+            url: tag
+                .get("WOAS")
+                .and_then(|f| f.content().link())
+                .map(str::to_string),
+            artwork: tag
+                .pictures()
+                .find(|p| p.picture_type == PictureType::CoverFront)
+                .or_else(|| tag.pictures().next())
+                .map(|p| ArtworkSummary {
+                    mime_type: p.mime_type.clone(),
+                    size_bytes: p.data.len() as u32,
+                }),
+        }
+    }
+}
+
+/// Distinguishes an absent field (`None`) from an explicit `null` (`Some(None)`).
+fn deserialize_some<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+/// New cover art for a file.
+#[derive(Debug, Clone, PartialEq, Deserialize, Type)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum ArtworkSource {
+    /// Image file on disk.
+    Path { path: String },
+    /// Image downloaded from a URL.
+    Url { url: String },
+    /// Remove the front cover.
+    Remove,
+}
+
+/// Changes to apply to one or more files.
+///
+/// A missing field is left untouched, `null` (or an empty string) removes
+/// the frame, and a value replaces it.
+#[derive(Debug, Clone, Default, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TagPatch {
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub title: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub artist: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub album: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub album_artist: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub genre: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub date: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub track_number: Option<Option<u32>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub total_tracks: Option<Option<u32>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub label: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub isrc: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub bpm: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub comment: Option<Option<String>>,
+    #[serde(default, deserialize_with = "deserialize_some")]
+    pub url: Option<Option<String>>,
+    #[serde(default)]
+    pub artwork: Option<ArtworkSource>,
+}
+
+/// Result of writing a patch to one file.
+#[derive(Debug, Clone, Serialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct TagWriteResult {
+    pub path: String,
+    pub error: Option<String>,
+}
+
+/// Returns the value to write, or None if the frame should be removed.
+fn patch_value(value: &Option<String>) -> Option<&str> {
+    value.as_deref().map(str::trim).filter(|v| !v.is_empty())
+}
+
+fn patch_text(tag: &mut Tag, id: &str, value: &Option<Option<String>>) {
+    match value {
+        None => {}
+        Some(v) => match patch_value(v) {
+            Some(v) => tag.set_text(id, v),
+            None => {
+                tag.remove(id);
+            }
+        },
+    }
+}
+
+/// Applies a patch to a tag.
+///
+/// `artwork` is the already-loaded image for `ArtworkSource::Path`/`Url`.
+pub fn apply_patch(tag: &mut Tag, patch: &TagPatch, artwork: Option<&Artwork>) {
+    patch_text(tag, "TIT2", &patch.title);
+    patch_text(tag, "TPE1", &patch.artist);
+    patch_text(tag, "TALB", &patch.album);
+    patch_text(tag, "TPE2", &patch.album_artist);
+    patch_text(tag, "TCON", &patch.genre);
+    patch_text(tag, "TPUB", &patch.label);
+    patch_text(tag, "TSRC", &patch.isrc);
+    patch_text(tag, "TBPM", &patch.bpm);
+
+    if let Some(date) = &patch.date {
+        match patch_value(date).and_then(parse_release_date) {
+            Some(date) => tag.set_date_recorded(date),
+            None => tag.remove_date_recorded(),
+        }
+    }
+
+    match patch.track_number {
+        Some(Some(track)) => tag.set_track(track),
+        Some(None) => tag.remove_track(),
+        None => {}
+    }
+
+    match patch.total_tracks {
+        Some(Some(total)) => tag.set_total_tracks(total),
+        Some(None) => tag.remove_total_tracks(),
+        None => {}
+    }
+
+    if let Some(comment) = &patch.comment {
+        tag.remove_comment(Some(""), None);
+        if let Some(text) = patch_value(comment) {
+            tag.add_frame(Comment {
+                lang: "eng".to_string(),
+                description: String::new(),
+                text: text.to_string(),
+            });
+        }
+    }
+
+    if let Some(url) = &patch.url {
+        tag.remove("WOAS");
+        if let Some(url) = patch_value(url) {
+            tag.add_frame(Frame::link("WOAS", url));
+        }
+    }
+
+    match (&patch.artwork, artwork) {
+        (Some(ArtworkSource::Remove), _) => tag.remove_picture_by_type(PictureType::CoverFront),
+        (Some(_), Some(artwork)) => {
+            tag.add_frame(Picture {
+                mime_type: artwork.mime_type.to_string(),
+                picture_type: PictureType::CoverFront,
+                description: "Cover".to_string(),
+                data: artwork.data.clone(),
+            });
+        }
+        _ => {}
+    }
+}
+
+/// Loads the image referenced by a patch, if any.
+pub async fn load_patch_artwork(patch: &TagPatch) -> Result<Option<Artwork>, MetadataError> {
+    match &patch.artwork {
+        Some(ArtworkSource::Path { path }) => {
+            let data =
+                std::fs::read(path).map_err(|e| MetadataError::ArtworkFailed(e.to_string()))?;
+            Artwork::from_bytes(data).map(Some)
+        }
+        Some(ArtworkSource::Url { url }) => download_artwork_from_url(url).await.map(Some),
+        Some(ArtworkSource::Remove) | None => Ok(None),
+    }
+}
+
+/// Reads the editable tags of a file.
+pub fn read_tags(path: &Path) -> Result<EditableTags, MetadataError> {
+    if !path.is_file() {
+        return Err(MetadataError::ReadFailed(format!(
+            "File not found: {}",
+            path.display()
+        )));
+    }
+
+    read_tag(path).map(|tag| EditableTags::from_tag(&tag))
+}
+
+/// Applies a patch to every file, returning a result per file.
+///
+/// The artwork is loaded once and shared by all files; if it cannot be
+/// loaded nothing is written.
+pub async fn write_tags(
+    paths: &[String],
+    patch: &TagPatch,
+) -> Result<Vec<TagWriteResult>, MetadataError> {
+    let artwork = load_patch_artwork(patch).await?;
+
+    let results = paths
+        .iter()
+        .map(|path| {
+            let file = Path::new(path);
+            let result = read_tag(file).and_then(|mut tag| {
+                let profile = TagProfile::detect(file, &tag);
+                apply_patch(&mut tag, patch, artwork.as_ref());
+                write_tag(file, &tag, profile)
+            });
+
+            TagWriteResult {
+                path: path.clone(),
+                error: result.err().map(|e| e.to_string()),
+            }
+        })
+        .collect();
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn create_mp3(dir: &Path, name: &str) -> String {
+        let path = dir.join(name);
+        fs::write(&path, [0xFF, 0xFB, 0x90, 0x00, 0x00, 0x00, 0x00, 0x00]).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_container_from_path() {
+        assert_eq!(
+            Container::from_path(Path::new("a.MP3")).unwrap(),
+            Container::Mp3
+        );
+        assert_eq!(
+            Container::from_path(Path::new("a.aif")).unwrap(),
+            Container::Aiff
+        );
+        assert_eq!(
+            Container::from_path(Path::new("a.wav")).unwrap(),
+            Container::Wav
+        );
+        assert!(matches!(
+            Container::from_path(Path::new("a.flac")),
+            Err(MetadataError::UnsupportedFormat(ext))
+                if ext == "flac (only MP3, AIFF and WAV tags can be edited)"
+        ));
+        assert!(matches!(
+            Container::from_path(Path::new("a.xyz")),
+            Err(MetadataError::UnsupportedFormat(ext)) if ext == "xyz"
+        ));
+    }
+
+    #[test]
+    fn test_tag_patch_deserialize_distinguishes_null_and_missing() {
+        let patch: TagPatch =
+            serde_json::from_str(r#"{"album": "New Album", "genre": null}"#).unwrap();
+
+        assert_eq!(patch.album, Some(Some("New Album".to_string())));
+        assert_eq!(patch.genre, Some(None));
+        assert_eq!(patch.title, None);
+        assert_eq!(patch.artwork, None);
+    }
+
+    #[test]
+    fn test_artwork_source_deserialize() {
+        let patch: TagPatch =
+            serde_json::from_str(r#"{"artwork": {"kind": "url", "url": "https://x/a.jpg"}}"#)
+                .unwrap();
+        assert_eq!(
+            patch.artwork,
+            Some(ArtworkSource::Url {
+                url: "https://x/a.jpg".to_string()
+            })
+        );
+
+        let patch: TagPatch = serde_json::from_str(r#"{"artwork": {"kind": "remove"}}"#).unwrap();
+        assert_eq!(patch.artwork, Some(ArtworkSource::Remove));
+    }
+
+    #[test]
+    fn test_apply_patch_sets_and_removes_fields() {
+        let mut tag = Tag::new();
+        tag.set_title("Title");
+        tag.set_genre("Old Genre");
+        tag.set_text("TSSE", "Lavf");
+
+        let patch = TagPatch {
+            artist: Some(Some("Fixed Artist".to_string())),
+            genre: Some(None),
+            date: Some(Some("2021-03-04".to_string())),
+            track_number: Some(Some(3)),
+            comment: Some(Some("Note".to_string())),
+            ..Default::default()
+        };
+        apply_patch(&mut tag, &patch, None);
+
+        let tags = EditableTags::from_tag(&tag);
+        assert_eq!(tags.title, Some("Title".to_string()));
+        assert_eq!(tags.artist, Some("Fixed Artist".to_string()));
+        assert_eq!(tags.genre, None);
+        assert_eq!(tags.date, Some("2021-03-04".to_string()));
+        assert_eq!(tags.track_number, Some(3));
+        assert_eq!(tags.comment, Some("Note".to_string()));
+        assert!(tag.get("TSSE").is_some());
+    }
+
+    #[test]
+    fn test_apply_patch_empty_string_removes() {
+        let mut tag = Tag::new();
+        tag.set_album("Album");
+
+        let patch = TagPatch {
+            album: Some(Some("  ".to_string())),
+            ..Default::default()
+        };
+        apply_patch(&mut tag, &patch, None);
+
+        assert_eq!(tag.album(), None);
+    }
+
+    #[test]
+    fn test_apply_patch_artwork_replace_and_remove() {
+        let mut tag = Tag::new();
+        let artwork = Artwork {
+            data: vec![0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A],
+            mime_type: "image/png",
+        };
+        let patch = TagPatch {
+            artwork: Some(ArtworkSource::Path {
+                path: "cover.png".to_string(),
+            }),
+            ..Default::default()
+        };
+        apply_patch(&mut tag, &patch, Some(&artwork));
+        assert_eq!(
+            EditableTags::from_tag(&tag).artwork,
+            Some(ArtworkSummary {
+                mime_type: "image/png".to_string(),
+                size_bytes: 8
+            })
+        );
+
+        let patch = TagPatch {
+            artwork: Some(ArtworkSource::Remove),
+            ..Default::default()
+        };
+        apply_patch(&mut tag, &patch, None);
+        assert!(EditableTags::from_tag(&tag).artwork.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_write_tags_batch() {
+        let dir = tempdir().unwrap();
+        let paths = vec![
+            create_mp3(dir.path(), "1.mp3"),
+            create_mp3(dir.path(), "2.mp3"),
+            dir.path().join("3.flac").to_string_lossy().to_string(),
+        ];
+
+        let patch = TagPatch {
+            album: Some(Some("Playlist".to_string())),
+            ..Default::default()
+        };
+        let results = write_tags(&paths, &patch).await.unwrap();
+
+        assert!(results[0].error.is_none());
+        assert!(results[1].error.is_none());
+        assert!(results[2]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("Unsupported file format"));
+
+        for path in &paths[..2] {
+            let tags = read_tags(Path::new(path)).unwrap();
+            assert_eq!(tags.album, Some("Playlist".to_string()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_tags_keeps_v23_profile() {
+        let dir = tempdir().unwrap();
+        let path = create_mp3(dir.path(), "a.mp3");
+        let mut tag = Tag::new();
+        tag.set_text("TYER", "2019");
+        tag.write_to_path(&path, id3::Version::Id3v23).unwrap();
+
+        assert_eq!(
+            read_tags(Path::new(&path)).unwrap().date,
+            Some("2019".to_string())
+        );
+
+        let patch = TagPatch {
+            title: Some(Some("Title".to_string())),
+            ..Default::default()
+        };
+        write_tags(std::slice::from_ref(&path), &patch)
+            .await
+            .unwrap();
+
+        let tag = Tag::read_from_path(&path).unwrap();
+        assert_eq!(tag.version(), id3::Version::Id3v23);
+        assert_eq!(tag.year(), Some(2019));
+        assert_eq!(tag.title(), Some("Title"));
+    }
+
+    #[tokio::test]
+    async fn test_write_tags_artwork_from_missing_file_fails() {
+        let patch = TagPatch {
+            artwork: Some(ArtworkSource::Path {
+                path: "/nonexistent/cover.jpg".to_string(),
+            }),
+            ..Default::default()
+        };
+
+        let result = write_tags(&[], &patch).await;
+        assert!(matches!(result, Err(MetadataError::ArtworkFailed(_))));
+    }
+
+    #[test]
+    fn test_read_tags_untagged_file() {
+        let dir = tempdir().unwrap();
+        let path = create_mp3(dir.path(), "a.mp3");
+
+        assert_eq!(
+            read_tags(Path::new(&path)).unwrap(),
+            EditableTags::default()
+        );
+    }
+
+    #[test]
+    fn test_read_tags_missing_file() {
+        assert!(matches!(
+            read_tags(Path::new("/nonexistent/a.mp3")),
+            Err(MetadataError::ReadFailed(_))
+        ));
+    }
+}
@@ -0,0 +1,564 @@
+//! ID3 compatibility profiles.
+//!
+//! Tags are built in ID3v2.4 form (TDRC dates, null-separated values) and
+//! converted when written with an ID3v2.3 profile, since Windows Explorer,
+//! many car head units and older DJ software only read ID3v2.3 properly.
+//! Tags read from ID3v2.3 files are upgraded to the same form first, so the
+//! rest of the code only deals with one set of frames.
+
+use id3::{Content, Encoding, Frame, Tag, TagLike, Timestamp, Version};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::fs::{File, OpenOptions};
+use std::io::{Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Separator used for multiple values in ID3v2.3 text frames.
+const V23_VALUE_SEPARATOR: &str = "/";
+
+/// Size of an ID3v1 tag.
+const V1_TAG_SIZE: usize = 128;
+
+/// Genre IDs defined by the original ID3v1 specification.
+const V1_GENRES: &[&str] = &[
+    "Blues",
+    "Classic Rock",
+    "Country",
+    "Dance",
+    "Disco",
+    "Funk",
+    "Grunge",
+    "Hip-Hop",
+    "Jazz",
+    "Metal",
+    "New Age",
+    "Oldies",
+    "Other",
+    "Pop",
+    "R&B",
+    "Rap",
+    "Reggae",
+    "Rock",
+    "Techno",
+    "Industrial",
+    "Alternative",
+    "Ska",
+    "Death Metal",
+    "Pranks",
+    "Soundtrack",
+    "Euro-Techno",
+    "Ambient",
+    "Trip-Hop",
+    "Vocal",
+    "Jazz+Funk",
+    "Fusion",
+    "Trance",
+    "Classical",
+    "Instrumental",
+    "Acid",
+    "House",
+    "Game",
+    "Sound Clip",
+    "Gospel",
+    "Noise",
+    "AlternRock",
+    "Bass",
+    "Soul",
+    "Punk",
+    "Space",
+    "Meditative",
+    "Instrumental Pop",
+    "Instrumental Rock",
+    "Ethnic",
+    "Gothic",
+    "Darkwave",
+    "Techno-Industrial",
+    "Electronic",
+    "Pop-Folk",
+    "Eurodance",
+    "Dream",
+    "Southern Rock",
+    "Comedy",
+    "Cult",
+    "Gangsta",
+    "Top 40",
+    "Christian Rap",
+    "Pop/Funk",
+    "Jungle",
+    "Native American",
+    "Cabaret",
+    "New Wave",
+    "Psychadelic",
+    "Rave",
+    "Showtunes",
+    "Trailer",
+    "Lo-Fi",
+    "Tribal",
+    "Acid Punk",
+    "Acid Jazz",
+    "Polka",
+    "Retro",
+    "Musical",
+    "Rock & Roll",
+    "Hard Rock",
+];
+
+/// Which ID3 versions are written to a file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum TagProfile {
+    /// ID3v2.3 with UTF-16 text.
+    Id3v23,
+    /// ID3v2.4 with UTF-8 text.
+    #[default]
+    Id3v24,
+    /// ID3v2.3 plus an ID3v1 footer (MP3 only).
+    Id3v23WithV1,
+    /// ID3v2.4 plus an ID3v1 footer (MP3 only).
+    Id3v24WithV1,
+}
+
+impl TagProfile {
+    pub fn new(version: Version, id3v1: bool) -> Self {
+        match (version, id3v1) {
+            (Version::Id3v24, false) => TagProfile::Id3v24,
+            (Version::Id3v24, true) => TagProfile::Id3v24WithV1,
+            (_, false) => TagProfile::Id3v23,
+            (_, true) => TagProfile::Id3v23WithV1,
+        }
+    }
+
+    /// Detects the profile of a file from its ID3v2 version and ID3v1 footer.
+    ///
+    /// ID3v2.2 tags count as ID3v2.3, the closest version that can be written.
+    pub fn detect(path: &Path, tag: &Tag) -> Self {
+        let id3v1 = File::open(path)
+            .and_then(|file| id3::v1::Tag::is_candidate(file).map_err(std::io::Error::other))
+            .unwrap_or(false);
+
+        Self::new(tag.version(), id3v1)
+    }
+
+    pub fn version(self) -> Version {
+        match self {
+            TagProfile::Id3v23 | TagProfile::Id3v23WithV1 => Version::Id3v23,
+            TagProfile::Id3v24 | TagProfile::Id3v24WithV1 => Version::Id3v24,
+        }
+    }
+
+    /// Text encoding used for frames written with this profile.
+    pub fn encoding(self) -> Encoding {
+        match self.version() {
+            Version::Id3v24 => Encoding::UTF8,
+            _ => Encoding::UTF16,
+        }
+    }
+
+    pub fn writes_id3v1(self) -> bool {
+        matches!(self, TagProfile::Id3v23WithV1 | TagProfile::Id3v24WithV1)
+    }
+
+    /// Human-readable name, e.g. `ID3v2.3 + ID3v1`.
+    pub fn label(self) -> &'static str {
+        match self {
+            TagProfile::Id3v23 => "ID3v2.3",
+            TagProfile::Id3v24 => "ID3v2.4",
+            TagProfile::Id3v23WithV1 => "ID3v2.3 + ID3v1",
+            TagProfile::Id3v24WithV1 => "ID3v2.4 + ID3v1",
+        }
+    }
+}
+
+/// Upgrades ID3v2.3 date frames to their ID3v2.4 equivalents.
+///
+/// TYER/TDAT/TIME are combined into TDRC and TORY becomes TDOR. Existing
+/// ID3v2.4 frames win over the ID3v2.3 ones.
+pub fn upgrade_frames(tag: &mut Tag) {
+    let text = |tag: &Tag, id: &str| {
+        tag.get(id)
+            .and_then(|f| f.content().text())
+            .map(|t| t.trim().to_string())
+    };
+
+    let year = text(tag, "TYER").and_then(|y| y.parse::<i32>().ok());
+    let day_month = text(tag, "TDAT").and_then(|d| split_pairs(&d));
+    let hour_minute = text(tag, "TIME").and_then(|t| split_pairs(&t));
+    let original_year = text(tag, "TORY").and_then(|y| y.parse::<i32>().ok());
+
+    for id in ["TYER", "TDAT", "TIME", "TORY"] {
+        tag.remove(id);
+    }
+
+    if let (Some(year), None) = (year, tag.date_recorded()) {
+        let (day, month) = day_month.unzip();
+        let (hour, minute) = hour_minute.unzip();
+        tag.set_date_recorded(Timestamp {
+            year,
+            month,
+            day: month.and(day),
+            hour: day.and(hour),
+            minute: day.and(minute),
+            second: None,
+        });
+    }
+
+    if let (Some(year), None) = (original_year, tag.get("TDOR")) {
+        tag.set_text("TDOR", format!("{:04}", year));
+    }
+}
+
+/// Splits `DDMM`/`HHMM` values into two numbers.
+fn split_pairs(value: &str) -> Option<(u8, u8)> {
+    if value.len() != 4 || !value.is_ascii() {
+        return None;
+    }
+    Some((value[..2].parse().ok()?, value[2..].parse().ok()?))
+}
+
+/// Converts a tag built in ID3v2.4 form into the frames of a profile.
+///
+/// For ID3v2.3: TDRC is split into TYER/TDAT/TIME, TDOR becomes TORY, TDRL
+/// is used as year when there is no TDRC, and multiple values are joined
+/// with `/`. Frames are re-encoded in the profile's text encoding unless
+/// they use Latin-1, which both versions read fine.
+pub fn convert_tag(tag: &Tag, profile: TagProfile) -> Tag {
+    let mut converted = Tag::with_version(profile.version());
+    let to_v23 = profile.version() == Version::Id3v23;
+
+    let timestamp = |id: &str| {
+        tag.get(id)
+            .and_then(|f| f.content().text())
+            .and_then(|t| t.trim().parse::<Timestamp>().ok())
+    };
+
+    for frame in tag.frames() {
+        let frame = match frame.encoding() {
+            Some(Encoding::Latin1) | None => frame.clone(),
+            Some(_) => frame.clone().set_encoding(Some(profile.encoding())),
+        };
+
+        if !to_v23 {
+            converted.add_frame(frame);
+            continue;
+        }
+
+        match (frame.id(), frame.content()) {
+            ("TDRC" | "TDRL" | "TDOR", _) => {}
+            (_, Content::Text(text)) if text.contains('\0') => {
+                let joined = text
+                    .split('\0')
+                    .filter(|v| !v.is_empty())
+                    .collect::<Vec<_>>()
+                    .join(V23_VALUE_SEPARATOR);
+                converted.add_frame(Frame::text(frame.id(), joined).set_encoding(frame.encoding()));
+            }
+            _ => {
+                converted.add_frame(frame);
+            }
+        }
+    }
+
+    if to_v23 {
+        if let Some(date) = timestamp("TDRC").or_else(|| timestamp("TDRL")) {
+            converted.set_text("TYER", format!("{:04}", date.year));
+            if let (Some(month), Some(day)) = (date.month, date.day) {
+                converted.set_text("TDAT", format!("{:02}{:02}", day, month));
+                if let (Some(hour), Some(minute)) = (date.hour, date.minute) {
+                    converted.set_text("TIME", format!("{:02}{:02}", hour, minute));
+                }
+            }
+        }
+
+        if let Some(original) = timestamp("TDOR") {
+            converted.set_text("TORY", format!("{:04}", original.year));
+        }
+    }
+
+    converted
+}
+
+/// Writes a tag to a file using a profile.
+///
+/// Any existing ID3v1 footer is removed, then a new one is appended if the
+/// profile asks for it. ID3v1 is only written to MP3 files, as appending it
+/// to AIFF/WAV would corrupt the container.
+pub fn write_tag(path: &Path, tag: &Tag, profile: TagProfile) -> id3::Result<()> {
+    convert_tag(tag, profile).write_to_path(path, profile.version())?;
+
+    let is_mp3 = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("mp3"));
+    if !is_mp3 {
+        return Ok(());
+    }
+
+    id3::v1::Tag::remove_from_path(path)?;
+
+    if profile.writes_id3v1() {
+        let mut file = OpenOptions::new().write(true).open(path)?;
+        file.seek(SeekFrom::End(0))?;
+        file.write_all(&encode_v1(tag))?;
+    }
+
+    Ok(())
+}
+
+/// Encodes the basic fields of a tag as an ID3v1.1 footer.
+///
+/// Text is truncated to the fixed field sizes and characters outside
+/// Latin-1 are replaced with `?`.
+pub fn encode_v1(tag: &Tag) -> [u8; V1_TAG_SIZE] {
+    let mut data = [0u8; V1_TAG_SIZE];
+    data[..3].copy_from_slice(b"TAG");
+
+    fn first_value(value: Option<&str>) -> &str {
+        value.and_then(|v| v.split('\0').next()).unwrap_or("")
+    }
+
+    let year = tag
+        .date_recorded()
+        .map(|d| format!("{:04}", d.year))
+        .unwrap_or_default();
+    let comment = tag
+        .comments()
+        .find(|c| c.description.is_empty())
+        .map(|c| c.text.as_str());
+
+    write_latin1(&mut data[3..33], first_value(tag.title()));
+    write_latin1(&mut data[33..63], first_value(tag.artist()));
+    write_latin1(&mut data[63..93], first_value(tag.album()));
+    write_latin1(&mut data[93..97], &year);
+    write_latin1(&mut data[97..125], first_value(comment));
+    data[126] = tag.track().and_then(|t| u8::try_from(t).ok()).unwrap_or(0);
+    data[127] = v1_genre_id(tag.genre()).unwrap_or(255);
+
+    data
+}
+
+fn write_latin1(field: &mut [u8], value: &str) {
+    let bytes = value
+        .chars()
+        .map(|c| u8::try_from(u32::from(c)).unwrap_or(b'?'));
+
+    for (slot, byte) in field.iter_mut().zip(bytes) {
+        *slot = byte;
+    }
+}
+
+/// Looks up the ID3v1 genre ID, accepting names and `(n)` references.
+fn v1_genre_id(genre: Option<&str>) -> Option<u8> {
+    let genre = genre?.split('\0').next()?.trim();
+
+    if let Some(id) = genre
+        .strip_prefix('(')
+        .and_then(|g| g.strip_suffix(')'))
+        .and_then(|g| g.parse().ok())
+    {
+        return Some(id);
+    }
+
+    V1_GENRES
+        .iter()
+        .position(|g| g.eq_ignore_ascii_case(genre))
+        .map(|i| i as u8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn timestamp(value: &str) -> Timestamp {
+        value.parse().unwrap()
+    }
+
+    fn create_mp3(dir: &Path) -> std::path::PathBuf {
+        let path = dir.join("test.mp3");
+        let mut data = vec![0xFF, 0xFB, 0x90, 0x00];
+        data.extend(vec![0u8; 413]);
+        std::fs::write(&path, data).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_profile_deserialize() {
+        let profile: TagProfile = serde_json::from_str(r#""id3v23WithV1""#).unwrap();
+        assert_eq!(profile, TagProfile::Id3v23WithV1);
+        assert_eq!(profile.version(), Version::Id3v23);
+        assert_eq!(profile.encoding(), Encoding::UTF16);
+        assert!(profile.writes_id3v1());
+        assert_eq!(TagProfile::default().encoding(), Encoding::UTF8);
+    }
+
+    #[test]
+    fn test_convert_to_v23_splits_date() {
+        let mut tag = Tag::new();
+        tag.set_date_recorded(timestamp("2021-03-04T05:06"));
+        tag.set_text("TDOR", "1999");
+
+        let converted = convert_tag(&tag, TagProfile::Id3v23);
+        assert_eq!(converted.version(), Version::Id3v23);
+        assert!(converted.get("TDRC").is_none());
+        assert!(converted.get("TDOR").is_none());
+        assert_eq!(
+            converted.get("TYER").unwrap().content().text(),
+            Some("2021")
+        );
+        assert_eq!(
+            converted.get("TDAT").unwrap().content().text(),
+            Some("0403")
+        );
+        assert_eq!(
+            converted.get("TIME").unwrap().content().text(),
+            Some("0506")
+        );
+        assert_eq!(
+            converted.get("TORY").unwrap().content().text(),
+            Some("1999")
+        );
+    }
+
+    #[test]
+    fn test_convert_to_v23_uses_release_date_as_fallback() {
+        let mut tag = Tag::new();
+        tag.set_text("TDRL", "2020");
+
+        let converted = convert_tag(&tag, TagProfile::Id3v23);
+        assert_eq!(
+            converted.get("TYER").unwrap().content().text(),
+            Some("2020")
+        );
+        assert!(converted.get("TDAT").is_none());
+        assert!(converted.get("TDRL").is_none());
+    }
+
+    #[test]
+    fn test_convert_to_v23_joins_multiple_values() {
+        let mut tag = Tag::new();
+        tag.set_text_values("TPE1", ["Artist A", "Artist B"]);
+
+        let converted = convert_tag(&tag, TagProfile::Id3v23);
+        assert_eq!(converted.artist(), Some("Artist A/Artist B"));
+
+        let converted = convert_tag(&tag, TagProfile::Id3v24);
+        assert_eq!(converted.artist(), Some("Artist A\0Artist B"));
+    }
+
+    #[test]
+    fn test_convert_to_v23_reencodes_utf8_frames() {
+        let mut tag = Tag::new();
+        tag.add_frame(Frame::text("TIT2", "Título").set_encoding(Some(Encoding::UTF8)));
+        tag.add_frame(Frame::text("TALB", "Album").set_encoding(Some(Encoding::Latin1)));
+
+        let converted = convert_tag(&tag, TagProfile::Id3v23);
+        assert_eq!(
+            converted.get("TIT2").unwrap().encoding(),
+            Some(Encoding::UTF16)
+        );
+        assert_eq!(
+            converted.get("TALB").unwrap().encoding(),
+            Some(Encoding::Latin1)
+        );
+    }
+
+    #[test]
+    fn test_upgrade_frames_combines_v23_dates() {
+        let mut tag = Tag::with_version(Version::Id3v23);
+        tag.set_text("TYER", "2021");
+        tag.set_text("TDAT", "0403");
+        tag.set_text("TIME", "0506");
+        tag.set_text("TORY", "1999");
+
+        upgrade_frames(&mut tag);
+        assert_eq!(tag.date_recorded(), Some(timestamp("2021-03-04T05:06")));
+        assert_eq!(tag.get("TDOR").unwrap().content().text(), Some("1999"));
+        for id in ["TYER", "TDAT", "TIME", "TORY"] {
+            assert!(tag.get(id).is_none(), "{} should be removed", id);
+        }
+    }
+
+    #[test]
+    fn test_upgrade_frames_keeps_existing_tdrc() {
+        let mut tag = Tag::new();
+        tag.set_date_recorded(timestamp("2022-01-02"));
+        tag.set_text("TYER", "2021");
+
+        upgrade_frames(&mut tag);
+        assert_eq!(tag.date_recorded(), Some(timestamp("2022-01-02")));
+        assert!(tag.get("TYER").is_none());
+    }
+
+    #[test]
+    fn test_v23_round_trip_through_file() {
+        let dir = tempdir().unwrap();
+        let path = create_mp3(dir.path());
+
+        let mut tag = Tag::new();
+        tag.set_title("Title");
+        tag.set_date_recorded(timestamp("2021-03-04"));
+        write_tag(&path, &tag, TagProfile::Id3v23).unwrap();
+
+        let mut read = Tag::read_from_path(&path).unwrap();
+        assert_eq!(read.version(), Version::Id3v23);
+        assert_eq!(TagProfile::detect(&path, &read), TagProfile::Id3v23);
+
+        upgrade_frames(&mut read);
+        assert_eq!(read.title(), Some("Title"));
+        assert_eq!(read.date_recorded(), Some(timestamp("2021-03-04")));
+    }
+
+    #[test]
+    fn test_write_tag_adds_and_removes_id3v1() {
+        let dir = tempdir().unwrap();
+        let path = create_mp3(dir.path());
+
+        let mut tag = Tag::new();
+        tag.set_title("Title");
+        tag.set_artist("Artist");
+        write_tag(&path, &tag, TagProfile::Id3v24WithV1).unwrap();
+
+        let v1 = id3::v1::Tag::read_from_path(&path).unwrap();
+        assert_eq!(v1.title, "Title");
+        assert_eq!(v1.artist, "Artist");
+        let read = Tag::read_from_path(&path).unwrap();
+        assert_eq!(TagProfile::detect(&path, &read), TagProfile::Id3v24WithV1);
+
+        // Writing again must not stack a second footer
+        let size = std::fs::metadata(&path).unwrap().len();
+        write_tag(&path, &tag, TagProfile::Id3v24WithV1).unwrap();
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), size);
+
+        write_tag(&path, &tag, TagProfile::Id3v24).unwrap();
+        assert!(id3::v1::Tag::read_from_path(&path).is_err());
+    }
+
+    #[test]
+    fn test_encode_v1_fields() {
+        let mut tag = Tag::new();
+        tag.set_title("A title that is much longer than thirty characters");
+        tag.set_artist("Björk");
+        tag.set_album("日本");
+        tag.set_date_recorded(timestamp("2021-03-04"));
+        tag.set_track(7);
+        tag.set_genre("house");
+
+        let data = encode_v1(&tag);
+        assert_eq!(&data[..3], b"TAG");
+        assert_eq!(&data[3..33], b"A title that is much longer th");
+        assert_eq!(&data[33..39], b"Bj\xF6rk\0");
+        assert_eq!(&data[63..66], b"??\0");
+        assert_eq!(&data[93..97], b"2021");
+        assert_eq!(data[125], 0);
+        assert_eq!(data[126], 7);
+        assert_eq!(data[127], 35);
+    }
+
+    #[test]
+    fn test_v1_genre_id() {
+        assert_eq!(v1_genre_id(Some("Techno")), Some(18));
+        assert_eq!(v1_genre_id(Some("(52)")), Some(52));
+        assert_eq!(v1_genre_id(Some("Drum & Bass")), None);
+        assert_eq!(v1_genre_id(None), None);
+    }
+}
@@ -0,0 +1,382 @@
+//! Title cleanup rules for label and repost-channel uploads.
+//!
+//! Labels and promo channels typically upload as
+//! `Artist - Title [Label] (Free Download)` under their own account, which
+//! leaves the channel name as artist and a noisy title. These rules split
+//! the title into artist/title, strip junk patterns and move featured
+//! artists into the artist field.
+
+use once_cell::sync::Lazy;
+use regex::{Regex, RegexBuilder};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Separators recognised between artist and title.
+const SPLIT_SEPARATORS: &[&str] = &[" - ", " – ", " — "];
+
+static FEATURING: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\s*[(\[]?\s*\b(?:feat\.?|ft\.?|featuring)\s+([^)\]]+?)\s*[)\]]?\s*$")
+        .expect("featuring regex is valid")
+});
+
+static FEATURING_INLINE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)\s*[(\[]\s*(?:feat\.?|ft\.?|featuring)\s+([^)\]]+?)\s*[)\]]")
+        .expect("inline featuring regex is valid")
+});
+
+static MULTI_SPACE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\s{2,}").expect("whitespace regex is valid"));
+
+/// When an `Artist - Title` upload title is split into artist and title.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum SplitMode {
+    /// Never split; keep the uploader as artist.
+    Never,
+    /// Split only when the uploader looks like a label or promo channel.
+    #[default]
+    LabelsOnly,
+    /// Split every title containing a separator.
+    Always,
+}
+
+/// User-configurable title cleanup rules.
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TitleRules {
+    pub split_mode: SplitMode,
+    /// Words that mark an uploader name as a label (matched case-insensitively).
+    pub label_keywords: Vec<String>,
+    /// Uploader names always treated as labels.
+    pub label_accounts: Vec<String>,
+    /// Regular expressions removed from titles (matched case-insensitively).
+    pub junk_patterns: Vec<String>,
+    /// Move `feat.`/`ft.` credits from the title into the artist field.
+    pub extract_featuring: bool,
+}
+
+impl Default for TitleRules {
+    fn default() -> Self {
+        Self {
+            split_mode: SplitMode::default(),
+            label_keywords: [
+                "records",
+                "recordings",
+                "label",
+                "music",
+                "audio",
+                "tapes",
+                "collective",
+                "promotions",
+                "network",
+                "premieres",
+            ]
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+            label_accounts: vec![],
+            junk_patterns: [
+                r"[(\[]\s*free\s*(?:dl|download)[^)\]]*[)\]]",
+                r"[(\[]\s*out\s+now[^)\]]*[)\]]",
+                r"[(\[]\s*(?:premiere|exclusive)[^)\]]*[)\]]",
+                r"^\s*premiere\s*[:|]\s*",
+            ]
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+            extract_featuring: true,
+        }
+    }
+}
+
+/// Artist and title after applying the rules.
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+pub struct CleanedTitle {
+    pub artist: String,
+    pub title: String,
+}
+
+/// Title rules with their junk patterns compiled.
+pub struct TitleRuleEngine {
+    rules: TitleRules,
+    junk: Vec<Regex>,
+}
+
+impl TitleRuleEngine {
+    /// Compiles the rules, failing on the first invalid junk pattern.
+    pub fn new(rules: TitleRules) -> Result<Self, String> {
+        let junk = rules
+            .junk_patterns
+            .iter()
+            .map(|p| {
+                RegexBuilder::new(p)
+                    .case_insensitive(true)
+                    .build()
+                    .map_err(|e| format!("Invalid junk pattern '{}': {}", p, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self { rules, junk })
+    }
+
+    /// Cleans a track title uploaded by `uploader`.
+    pub fn apply(&self, title: &str, uploader: &str) -> CleanedTitle {
+        let mut title = title.to_string();
+        let mut artist = uploader.to_string();
+
+        for pattern in &self.junk {
+            title = pattern.replace_all(&title, " ").into_owned();
+        }
+
+        // Drop a trailing "[Label]" tag naming the uploader itself
+        title = strip_uploader_tag(&title, uploader);
+
+        let should_split = match self.rules.split_mode {
+            SplitMode::Never => false,
+            SplitMode::LabelsOnly => self.looks_like_label(uploader),
+            SplitMode::Always => true,
+        };
+
+        if should_split {
+            if let Some((a, t)) = split_artist_title(&title) {
+                artist = a;
+                title = t;
+            }
+        }
+
+        if self.rules.extract_featuring {
+            let (t, featured) = extract_featuring(&title);
+            title = t;
+            if let Some(featured) = featured {
+                if !artist.to_lowercase().contains(&featured.to_lowercase()) {
+                    artist = format!("{} feat. {}", artist.trim(), featured);
+                }
+            }
+        }
+
+        CleanedTitle {
+            artist: normalize_whitespace(&artist),
+            title: normalize_whitespace(&title),
+        }
+    }
+
+    fn looks_like_label(&self, uploader: &str) -> bool {
+        let name = uploader.to_lowercase();
+
+        if self
+            .rules
+            .label_accounts
+            .iter()
+            .any(|a| a.trim().to_lowercase() == name.trim())
+        {
+            return true;
+        }
+
+        name.split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .any(|word| {
+                self.rules
+                    .label_keywords
+                    .iter()
+                    .any(|k| k.trim().eq_ignore_ascii_case(word))
+            })
+    }
+}
+
+/// Splits `Artist - Title` at the first recognised separator.
+pub fn split_artist_title(title: &str) -> Option<(String, String)> {
+    let (index, separator) = SPLIT_SEPARATORS
+        .iter()
+        .filter_map(|sep| title.find(sep).map(|i| (i, *sep)))
+        .min_by_key(|(i, _)| *i)?;
+
+    let artist = title[..index].trim();
+    let rest = title[index + separator.len()..].trim();
+
+    if artist.is_empty() || rest.is_empty() {
+        return None;
+    }
+
+    Some((artist.to_string(), rest.to_string()))
+}
+
+/// Removes a featuring credit from a title, returning the featured artists.
+///
+/// Handles both a bracketed credit anywhere in the title
+/// (`Song (feat. X) (Remix)`) and a trailing unbracketed one (`Song ft. X`).
+fn extract_featuring(title: &str) -> (String, Option<String>) {
+    if let Some(caps) = FEATURING_INLINE.captures(title) {
+        let featured = caps[1].trim().to_string();
+        let cleaned = FEATURING_INLINE.replace(title, "").into_owned();
+        return (cleaned, Some(featured));
+    }
+
+    if let Some(caps) = FEATURING.captures(title) {
+        let featured = caps[1].trim().to_string();
+        let cleaned = FEATURING.replace(title, "").into_owned();
+        return (cleaned, Some(featured));
+    }
+
+    (title.to_string(), None)
+}
+
+fn strip_uploader_tag(title: &str, uploader: &str) -> String {
+    let trimmed = title.trim_end();
+    let tag = format!("[{}]", uploader.trim());
+
+    if trimmed.len() >= tag.len()
+        && trimmed.is_char_boundary(trimmed.len() - tag.len())
+        && trimmed[trimmed.len() - tag.len()..].eq_ignore_ascii_case(&tag)
+    {
+        trimmed[..trimmed.len() - tag.len()].to_string()
+    } else {
+        title.to_string()
+    }
+}
+
+fn normalize_whitespace(value: &str) -> String {
+    MULTI_SPACE
+        .replace_all(value.trim(), " ")
+        .trim_matches(|c: char| c.is_whitespace() || c == '-' || c == '|')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply_title_rules(title: &str, uploader: &str, rules: &TitleRules) -> CleanedTitle {
+        TitleRuleEngine::new(rules.clone())
+            .unwrap()
+            .apply(title, uploader)
+    }
+
+    fn clean(title: &str, uploader: &str) -> CleanedTitle {
+        apply_title_rules(title, uploader, &TitleRules::default())
+    }
+
+    #[test]
+    fn test_label_upload_is_split() {
+        let result = clean(
+            "Artist - Title [Some Records] (Free Download)",
+            "Some Records",
+        );
+        assert_eq!(result.artist, "Artist");
+        assert_eq!(result.title, "Title");
+    }
+
+    #[test]
+    fn test_artist_upload_is_not_split_in_labels_only_mode() {
+        let result = clean("Intro - Live at Venue", "DJ Someone");
+        assert_eq!(result.artist, "DJ Someone");
+        assert_eq!(result.title, "Intro - Live at Venue");
+    }
+
+    #[test]
+    fn test_always_mode_splits_any_upload() {
+        let rules = TitleRules {
+            split_mode: SplitMode::Always,
+            ..Default::default()
+        };
+        let result = apply_title_rules("Other Artist - Track", "Reposter", &rules);
+        assert_eq!(result.artist, "Other Artist");
+        assert_eq!(result.title, "Track");
+    }
+
+    #[test]
+    fn test_never_mode_keeps_uploader() {
+        let rules = TitleRules {
+            split_mode: SplitMode::Never,
+            ..Default::default()
+        };
+        let result = apply_title_rules("Artist - Track", "Some Records", &rules);
+        assert_eq!(result.artist, "Some Records");
+        assert_eq!(result.title, "Artist - Track");
+    }
+
+    #[test]
+    fn test_label_accounts_list() {
+        let rules = TitleRules {
+            label_accounts: vec!["HATE".to_string()],
+            ..Default::default()
+        };
+        let result = apply_title_rules("Artist - Track", "hate", &rules);
+        assert_eq!(result.artist, "Artist");
+    }
+
+    #[test]
+    fn test_junk_patterns_are_removed() {
+        assert_eq!(clean("Track (Free DL)", "Artist").title, "Track");
+        assert_eq!(clean("Track [OUT NOW]", "Artist").title, "Track");
+        assert_eq!(clean("Premiere: Track", "Artist").title, "Track");
+    }
+
+    #[test]
+    fn test_remix_brackets_are_kept() {
+        let result = clean(
+            "Artist - Track (Someone Remix) [FREE DOWNLOAD]",
+            "Promo Network",
+        );
+        assert_eq!(result.artist, "Artist");
+        assert_eq!(result.title, "Track (Someone Remix)");
+    }
+
+    #[test]
+    fn test_featuring_moves_to_artist() {
+        let result = clean("Track (feat. Singer)", "Artist");
+        assert_eq!(result.artist, "Artist feat. Singer");
+        assert_eq!(result.title, "Track");
+
+        let result = clean("Track ft. Singer", "Artist");
+        assert_eq!(result.artist, "Artist feat. Singer");
+        assert_eq!(result.title, "Track");
+    }
+
+    #[test]
+    fn test_featuring_before_remix() {
+        let result = clean("Track (ft. Singer) (Someone Remix)", "Artist");
+        assert_eq!(result.artist, "Artist feat. Singer");
+        assert_eq!(result.title, "Track (Someone Remix)");
+    }
+
+    #[test]
+    fn test_featuring_not_duplicated() {
+        let result = clean("Track (feat. Singer)", "Artist feat. Singer");
+        assert_eq!(result.artist, "Artist feat. Singer");
+    }
+
+    #[test]
+    fn test_featuring_disabled() {
+        let rules = TitleRules {
+            extract_featuring: false,
+            ..Default::default()
+        };
+        let result = apply_title_rules("Track (feat. Singer)", "Artist", &rules);
+        assert_eq!(result.title, "Track (feat. Singer)");
+    }
+
+    #[test]
+    fn test_word_containing_ft_is_not_featuring() {
+        let result = clean("Drift Away", "Artist");
+        assert_eq!(result.title, "Drift Away");
+        assert_eq!(result.artist, "Artist");
+    }
+
+    #[test]
+    fn test_invalid_junk_pattern() {
+        let rules = TitleRules {
+            junk_patterns: vec!["(".to_string()],
+            ..Default::default()
+        };
+        let error = TitleRuleEngine::new(rules).err().unwrap();
+        assert!(error.contains("Invalid junk pattern '('"));
+    }
+
+    #[test]
+    fn test_title_rules_deserialize_defaults() {
+        let rules: TitleRules = serde_json::from_str(r#"{"splitMode": "always"}"#).unwrap();
+        assert_eq!(rules.split_mode, SplitMode::Always);
+        assert!(rules.extract_featuring);
+        assert!(!rules.junk_patterns.is_empty());
+    }
+}
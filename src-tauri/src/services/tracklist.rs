@@ -0,0 +1,361 @@
+//! Timestamped tracklists of DJ mixes.
+//!
+//! Mix uploads usually list their songs in the description as
+//! `00:00 Artist - Title`. When such a tracklist is found it is written as
+//! ID3 chapters (CHAP frames with a CTOC table of contents) and as a CUE
+//! sheet next to the audio file.
+
+use id3::frame::{Chapter, TableOfContents};
+use id3::{Frame, Tag, TagLike};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+use crate::services::title_rules::split_artist_title;
+
+/// Minimum number of entries for a description to count as a tracklist.
+const MIN_ENTRIES: usize = 2;
+
+/// Element ID of the table of contents frame.
+const TOC_ELEMENT_ID: &str = "toc";
+
+/// `00:00 Artist - Title`, optionally numbered (`01. 00:00 ...`), with the
+/// timestamp optionally bracketed (`[1:02:03] ...`).
+static TRACKLIST_LINE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r"^\s*(?:\d{1,3}[.)]\s+)?[\[(]?((?:\d{1,2}:)?\d{1,2}:\d{2})[\])]?\s*(?:[-–—|:.]\s*)?(.+?)\s*$",
+    )
+    .expect("tracklist regex is valid")
+});
+
+/// A song in a mix tracklist.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TracklistEntry {
+    /// Start of the song in milliseconds.
+    pub start_ms: u64,
+    pub artist: Option<String>,
+    pub title: String,
+}
+
+impl TracklistEntry {
+    /// `Artist - Title`, or just the title when there is no artist.
+    pub fn display_name(&self) -> String {
+        match &self.artist {
+            Some(artist) => format!("{} - {}", artist, self.title),
+            None => self.title.clone(),
+        }
+    }
+}
+
+/// Parses a timestamped tracklist from a track description.
+///
+/// Returns an empty list unless at least two lines carry a timestamp and
+/// the timestamps are strictly increasing, so descriptions that merely
+/// mention a time are left alone.
+pub fn parse_tracklist(description: &str) -> Vec<TracklistEntry> {
+    let entries: Vec<TracklistEntry> = description
+        .lines()
+        .filter_map(|line| {
+            let caps = TRACKLIST_LINE.captures(line)?;
+            let start_ms = parse_timestamp(&caps[1])?;
+            let text = caps[2].trim();
+
+            let (artist, title) = match split_artist_title(text) {
+                Some((artist, title)) => (Some(artist), title),
+                None => (None, text.to_string()),
+            };
+
+            Some(TracklistEntry {
+                start_ms,
+                artist,
+                title,
+            })
+        })
+        .collect();
+
+    let increasing = entries.windows(2).all(|w| w[0].start_ms < w[1].start_ms);
+    if entries.len() < MIN_ENTRIES || !increasing {
+        return vec![];
+    }
+
+    entries
+}
+
+/// Parses `m:ss`, `mm:ss` or `h:mm:ss` into milliseconds.
+fn parse_timestamp(value: &str) -> Option<u64> {
+    let parts = value
+        .split(':')
+        .map(|p| p.parse::<u64>().ok())
+        .collect::<Option<Vec<_>>>()?;
+
+    let (hours, minutes, seconds) = match parts[..] {
+        [m, s] => (0, m, s),
+        [h, m, s] if m < 60 => (h, m, s),
+        _ => return None,
+    };
+    if seconds >= 60 {
+        return None;
+    }
+
+    Some(((hours * 60 + minutes) * 60 + seconds) * 1000)
+}
+
+/// Writes the tracklist as chapters, replacing any existing ones.
+///
+/// Each chapter ends where the next one starts; the last one ends at
+/// `duration_ms` (or at its own start when the duration is unknown).
+/// Entries starting after the end of the track are dropped.
+pub fn apply_chapters(tag: &mut Tag, entries: &[TracklistEntry], duration_ms: Option<u64>) {
+    tag.remove_all_chapters();
+    tag.remove_all_tables_of_contents();
+
+    let entries: Vec<&TracklistEntry> = entries
+        .iter()
+        .filter(|e| !matches!(duration_ms, Some(d) if e.start_ms >= d))
+        .collect();
+    if entries.is_empty() {
+        return;
+    }
+
+    let mut elements = Vec::with_capacity(entries.len());
+    for (i, entry) in entries.iter().enumerate() {
+        let end_ms = entries
+            .get(i + 1)
+            .map(|next| next.start_ms)
+            .or(duration_ms)
+            .unwrap_or(entry.start_ms);
+
+        let mut frames = vec![Frame::text("TIT2", entry.display_name())];
+        if let Some(artist) = &entry.artist {
+            frames.push(Frame::text("TPE1", artist));
+        }
+
+        let element_id = format!("chp{}", i);
+        tag.add_frame(Chapter {
+            element_id: element_id.clone(),
+            start_time: to_u32(entry.start_ms),
+            end_time: to_u32(end_ms),
+            // No byte offsets: players use the times
+            start_offset: u32::MAX,
+            end_offset: u32::MAX,
+            frames,
+        });
+        elements.push(element_id);
+    }
+
+    tag.add_frame(TableOfContents {
+        element_id: TOC_ELEMENT_ID.to_string(),
+        top_level: true,
+        ordered: true,
+        elements,
+        frames: vec![],
+    });
+}
+
+fn to_u32(ms: u64) -> u32 {
+    u32::try_from(ms).unwrap_or(u32::MAX)
+}
+
+/// Renders a CUE sheet for an audio file.
+pub fn cue_sheet(
+    entries: &[TracklistEntry],
+    file_name: &str,
+    performer: &str,
+    title: &str,
+) -> String {
+    let mut cue = format!(
+        "PERFORMER \"{}\"\nTITLE \"{}\"\nFILE \"{}\" MP3\n",
+        cue_escape(performer),
+        cue_escape(title),
+        cue_escape(file_name)
+    );
+
+    for (i, entry) in entries.iter().enumerate() {
+        cue.push_str(&format!("  TRACK {:02} AUDIO\n", i + 1));
+        cue.push_str(&format!("    TITLE \"{}\"\n", cue_escape(&entry.title)));
+        if let Some(artist) = &entry.artist {
+            cue.push_str(&format!("    PERFORMER \"{}\"\n", cue_escape(artist)));
+        }
+        cue.push_str(&format!("    INDEX 01 {}\n", cue_time(entry.start_ms)));
+    }
+
+    cue
+}
+
+/// Formats milliseconds as a CUE `mm:ss:ff` time (75 frames per second).
+fn cue_time(ms: u64) -> String {
+    let total_seconds = ms / 1000;
+    let frames = (ms % 1000) * 75 / 1000;
+    format!(
+        "{:02}:{:02}:{:02}",
+        total_seconds / 60,
+        total_seconds % 60,
+        frames
+    )
+}
+
+/// CUE strings cannot contain double quotes.
+fn cue_escape(value: &str) -> String {
+    value.replace('"', "'")
+}
+
+/// Writes the CUE sheet next to the audio file, returning its path.
+pub fn write_cue_sheet(
+    audio_path: &Path,
+    entries: &[TracklistEntry],
+    performer: &str,
+    title: &str,
+) -> std::io::Result<PathBuf> {
+    let file_name = audio_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let cue_path = audio_path.with_extension("cue");
+
+    std::fs::write(&cue_path, cue_sheet(entries, &file_name, performer, title))?;
+    Ok(cue_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const DESCRIPTION: &str = "Recorded live at the club.\n\
+        \n\
+        Tracklist:\n\
+        00:00 Intro\n\
+        03:15 Artist One - First Song\n\
+        01. 1:02:03 Artist Two - Second Song (Remix)\n\
+        \n\
+        Follow me on socials";
+
+    fn entries() -> Vec<TracklistEntry> {
+        parse_tracklist(DESCRIPTION)
+    }
+
+    #[test]
+    fn test_parse_tracklist() {
+        assert_eq!(
+            entries(),
+            vec![
+                TracklistEntry {
+                    start_ms: 0,
+                    artist: None,
+                    title: "Intro".to_string(),
+                },
+                TracklistEntry {
+                    start_ms: 195_000,
+                    artist: Some("Artist One".to_string()),
+                    title: "First Song".to_string(),
+                },
+                TracklistEntry {
+                    start_ms: 3_723_000,
+                    artist: Some("Artist Two".to_string()),
+                    title: "Second Song (Remix)".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_tracklist_bracketed_timestamps() {
+        let entries = parse_tracklist("[0:00] A - B\n[4:30] C - D");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].start_ms, 270_000);
+        assert_eq!(entries[1].artist.as_deref(), Some("C"));
+    }
+
+    #[test]
+    fn test_parse_tracklist_requires_multiple_increasing_entries() {
+        assert!(parse_tracklist("Out now! 12:00 release on Friday").is_empty());
+        assert!(parse_tracklist("10:00 A - B").is_empty());
+        assert!(parse_tracklist("10:00 A - B\n05:00 C - D").is_empty());
+        assert!(parse_tracklist("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("0:05"), Some(5_000));
+        assert_eq!(parse_timestamp("75:00"), Some(4_500_000));
+        assert_eq!(parse_timestamp("1:02:03"), Some(3_723_000));
+        assert_eq!(parse_timestamp("1:75:00"), None);
+        assert_eq!(parse_timestamp("1:60"), None);
+    }
+
+    #[test]
+    fn test_apply_chapters() {
+        let mut tag = Tag::new();
+        apply_chapters(&mut tag, &entries(), Some(4_000_000));
+
+        let chapters: Vec<&Chapter> = tag.chapters().collect();
+        assert_eq!(chapters.len(), 3);
+        assert_eq!(chapters[0].element_id, "chp0");
+        assert_eq!((chapters[0].start_time, chapters[0].end_time), (0, 195_000));
+        assert_eq!(
+            (chapters[2].start_time, chapters[2].end_time),
+            (3_723_000, 4_000_000)
+        );
+        assert_eq!(
+            chapters[1].frames[0].content().text(),
+            Some("Artist One - First Song")
+        );
+        assert_eq!(chapters[1].frames[1].content().text(), Some("Artist One"));
+
+        let toc = tag.tables_of_contents().next().unwrap();
+        assert!(toc.top_level && toc.ordered);
+        assert_eq!(toc.elements, vec!["chp0", "chp1", "chp2"]);
+    }
+
+    #[test]
+    fn test_apply_chapters_replaces_existing_and_drops_late_entries() {
+        let mut tag = Tag::new();
+        apply_chapters(&mut tag, &entries(), None);
+        apply_chapters(&mut tag, &entries(), Some(1_000_000));
+
+        let chapters: Vec<&Chapter> = tag.chapters().collect();
+        assert_eq!(chapters.len(), 2);
+        assert_eq!(chapters[1].end_time, 1_000_000);
+        assert_eq!(tag.tables_of_contents().count(), 1);
+    }
+
+    #[test]
+    fn test_cue_sheet() {
+        let cue = cue_sheet(&entries(), "Mix.mp3", "DJ \"Name\"", "Mix");
+
+        assert_eq!(
+            cue,
+            "PERFORMER \"DJ 'Name'\"\n\
+             TITLE \"Mix\"\n\
+             FILE \"Mix.mp3\" MP3\n  \
+               TRACK 01 AUDIO\n    \
+                 TITLE \"Intro\"\n    \
+                 INDEX 01 00:00:00\n  \
+               TRACK 02 AUDIO\n    \
+                 TITLE \"First Song\"\n    \
+                 PERFORMER \"Artist One\"\n    \
+                 INDEX 01 03:15:00\n  \
+               TRACK 03 AUDIO\n    \
+                 TITLE \"Second Song (Remix)\"\n    \
+                 PERFORMER \"Artist Two\"\n    \
+                 INDEX 01 62:03:00\n"
+        );
+    }
+
+    #[test]
+    fn test_cue_time_frames() {
+        assert_eq!(cue_time(1_500), "00:01:37");
+    }
+
+    #[test]
+    fn test_write_cue_sheet_next_to_audio() {
+        let dir = tempdir().unwrap();
+        let audio = dir.path().join("Mix.mp3");
+
+        let cue_path = write_cue_sheet(&audio, &entries(), "DJ", "Mix").unwrap();
+        assert_eq!(cue_path, dir.path().join("Mix.cue"));
+        assert!(std::fs::read_to_string(cue_path)
+            .unwrap()
+            .contains("FILE \"Mix.mp3\" MP3"));
+    }
+}
@@ -1,8 +1,45 @@
-use crate::models::url::{UrlType, ValidationError, ValidationResult};
+use std::collections::HashSet;
+
+use once_cell::sync::Lazy;
+use regex::Regex;
 use url::Url;
 
+use crate::models::url::{
+    CollectionKind, ExtractedUrl, UrlType, ValidationError, ValidationResult,
+};
+
 const SOUNDCLOUD_HOSTS: [&str; 3] = ["soundcloud.com", "www.soundcloud.com", "on.soundcloud.com"];
 
+/// Anything that looks like a link to a SoundCloud host, with or without
+/// scheme, captured in group 1. It must start the text or follow a space or
+/// bracket, so hosts like `notsoundcloud.com` and links nested in another
+/// URL's query are not picked up. The host is checked after parsing, so
+/// `api.soundcloud.com` links are dropped.
+static URL_CANDIDATE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"(?i)(?:^|[\s<>"'()\[\]{},;:!|*])((?:https?://)?(?:[a-z0-9-]+\.)*soundcloud\.com(?:/[^\s<>"'()\[\]{}]*)?)"#,
+    )
+    .expect("URL candidate regex is valid")
+});
+
+/// Top-level SoundCloud pages that look like profiles but are not.
+const RESERVED_PATHS: [&str; 14] = [
+    "charts",
+    "discover",
+    "feed",
+    "jobs",
+    "messages",
+    "mobile",
+    "notifications",
+    "pages",
+    "people",
+    "search",
+    "settings",
+    "stream",
+    "upload",
+    "you",
+];
+
 pub fn validate_url(input: &str) -> ValidationResult {
     // Check if input is empty
     if input.trim().is_empty() {
@@ -86,6 +123,28 @@ pub fn validate_url(input: &str) -> ValidationResult {
                 error: None,
             }
         }
+        // Pages that are not user content: /discover, /you/likes, ...
+        [page, ..] if RESERVED_PATHS.contains(page) => ValidationResult {
+            valid: false,
+            url_type: None,
+            error: Some(ValidationError {
+                code: "INVALID_FORMAT".to_string(),
+                message: "This page is not a profile, playlist or track".to_string(),
+                hint: Some("Try pasting a profile, playlist or track link".to_string()),
+            }),
+        },
+        // Profile tracks tab: /user/tracks
+        [_user, "tracks"] => ValidationResult {
+            valid: true,
+            url_type: Some(UrlType::Profile),
+            error: None,
+        },
+        // Profile tab: /user/likes, /user/sets, ...
+        [_user, tab] if CollectionKind::from_path_segment(tab).is_some() => ValidationResult {
+            valid: true,
+            url_type: CollectionKind::from_path_segment(tab).map(UrlType::Collection),
+            error: None,
+        },
         // Regular track: /user/track-name (2 segments, not "sets")
         [_user, track] if *track != "sets" => ValidationResult {
             valid: true,
@@ -94,13 +153,9 @@ pub fn validate_url(input: &str) -> ValidationResult {
         },
         // Profile: /user (1 segment only)
         [_user] => ValidationResult {
-            valid: false,
-            url_type: None,
-            error: Some(ValidationError {
-                code: "PROFILE_URL".to_string(),
-                message: "This is a profile, not a playlist or track".to_string(),
-                hint: Some("Try pasting a playlist or track link".to_string()),
-            }),
+            valid: true,
+            url_type: Some(UrlType::Profile),
+            error: None,
         },
         // Other patterns
         _ => invalid_format_error(),
@@ -119,6 +174,102 @@ fn invalid_format_error() -> ValidationResult {
     }
 }
 
+/// Normalizes a SoundCloud URL: `www.`/`m.` hosts become `soundcloud.com`,
+/// and the query (tracking parameters such as `si` and `utm_*`), fragment
+/// and trailing slash are dropped. The path keeps its case, since secret
+/// tokens are case-sensitive.
+///
+/// With `expand_playlists`, a track opened from a playlist
+/// (`/user/track?in=user/sets/name`) becomes the playlist URL.
+///
+/// Returns None for URLs that are not SoundCloud pages.
+pub fn normalize_url(input: &str, expand_playlists: bool) -> Option<String> {
+    let input = input.trim();
+    let url = Url::parse(input)
+        .ok()
+        .filter(|u| u.has_host())
+        .or_else(|| Url::parse(&format!("https://{}", input)).ok())?;
+
+    let host = match url.host_str()? {
+        "soundcloud.com" | "www.soundcloud.com" | "m.soundcloud.com" => "soundcloud.com",
+        "on.soundcloud.com" => "on.soundcloud.com",
+        _ => return None,
+    };
+
+    let containing_playlist = url
+        .query_pairs()
+        .find(|(key, _)| key == "in")
+        .map(|(_, value)| value.trim_matches('/').to_string())
+        .filter(|value| value.split('/').nth(1) == Some("sets"));
+
+    let path = match containing_playlist {
+        Some(playlist) if expand_playlists && host == "soundcloud.com" => format!("/{}", playlist),
+        _ => url.path().trim_end_matches('/').to_string(),
+    };
+    if path.is_empty() {
+        return None;
+    }
+
+    Some(format!("https://{}{}", host, path))
+}
+
+/// Finds every SoundCloud link in `text` (e.g. a chat message), normalizes
+/// it with [`normalize_url`] and validates it. Links are returned once, in
+/// the order they first appear.
+pub fn extract_urls(text: &str, expand_playlists: bool) -> Vec<ExtractedUrl> {
+    let mut seen = HashSet::new();
+
+    URL_CANDIDATE
+        .captures_iter(text)
+        .filter_map(|captures| captures.get(1))
+        .map(|m| m.as_str().trim_end_matches(['.', ',', ';', ':', '!', '?']))
+        .filter_map(|candidate| normalize_url(candidate, expand_playlists))
+        .filter(|url| seen.insert(url.clone()))
+        .map(|url| ExtractedUrl {
+            result: validate_url(&url),
+            url,
+        })
+        .collect()
+}
+
+/// Adds the secret token of a private track to its URL, so it can be
+/// downloaded by someone other than its owner.
+///
+/// Page URLs get the token as last path segment (`/user/track/s-XXXX`), API
+/// URLs as `secret_token` query parameter. URLs that already carry a token
+/// are returned unchanged.
+pub fn with_secret_token(url: &str, secret_token: Option<&str>) -> String {
+    let Some(token) = secret_token.map(str::trim).filter(|t| !t.is_empty()) else {
+        return url.to_string();
+    };
+    let Ok(mut parsed) = Url::parse(url.trim()) else {
+        return url.to_string();
+    };
+
+    if parsed.host_str() == Some("api.soundcloud.com") {
+        if !parsed.query_pairs().any(|(key, _)| key == "secret_token") {
+            parsed.query_pairs_mut().append_pair("secret_token", token);
+        }
+        return parsed.to_string();
+    }
+
+    let path = parsed.path().trim_end_matches('/').to_string();
+    // Only the segment after a track (`/user/track/s-X`) or set
+    // (`/user/sets/name/s-X`) is a token; slugs may start with `s-` too
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let has_token = match segments.as_slice() {
+        [_, "sets", _, token] => token.starts_with("s-"),
+        [_, track, token] => *track != "sets" && token.starts_with("s-"),
+        _ => false,
+    };
+    if !has_token {
+        parsed.set_path(&format!("{}/{}", path, token));
+    }
+    parsed.set_query(None);
+    parsed.set_fragment(None);
+    parsed.to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -154,14 +305,18 @@ mod tests {
     }
 
     #[test]
-    fn test_profile_url_rejected() {
+    fn test_profile_url() {
         let result = validate_url("https://soundcloud.com/user");
-        assert!(!result.valid);
-        assert!(result.url_type.is_none());
-        let error = result.error.unwrap();
-        assert!(error.message.contains("profile"));
-        assert!(error.hint.is_some());
-        assert!(error.hint.unwrap().contains("playlist or track"));
+        assert!(result.valid);
+        assert_eq!(result.url_type, Some(UrlType::Profile));
+        assert!(result.error.is_none());
+    }
+
+    #[test]
+    fn test_profile_tracks_tab_url() {
+        let result = validate_url("https://soundcloud.com/user/tracks/");
+        assert!(result.valid);
+        assert_eq!(result.url_type, Some(UrlType::Profile));
     }
 
     #[test]
@@ -222,9 +377,43 @@ mod tests {
     }
 
     #[test]
-    fn test_sets_without_playlist_name_rejected() {
-        let result = validate_url("https://soundcloud.com/user/sets");
+    fn test_reserved_page_with_subpath_rejected() {
+        let result = validate_url("https://soundcloud.com/you/likes");
         assert!(!result.valid);
+        assert!(result.url_type.is_none());
+    }
+
+    #[test]
+    fn test_collection_urls() {
+        let cases = [
+            ("likes", CollectionKind::Likes),
+            ("reposts", CollectionKind::Reposts),
+            ("popular-tracks", CollectionKind::PopularTracks),
+            ("albums", CollectionKind::Albums),
+            ("sets", CollectionKind::Sets),
+        ];
+
+        for (tab, kind) in cases {
+            let result = validate_url(&format!("https://soundcloud.com/user/{}/", tab));
+            assert!(result.valid, "{} should be valid", tab);
+            assert_eq!(result.url_type, Some(UrlType::Collection(kind)));
+        }
+    }
+
+    #[test]
+    fn test_collection_url_type_serialize() {
+        let json =
+            serde_json::to_string(&UrlType::Collection(CollectionKind::PopularTracks)).unwrap();
+        assert_eq!(json, r#"{"collection":"popularTracks"}"#);
+    }
+
+    #[test]
+    fn test_collection_kind_path_segment_round_trip() {
+        for segment in ["likes", "reposts", "popular-tracks", "albums", "sets"] {
+            let kind = CollectionKind::from_path_segment(segment).unwrap();
+            assert_eq!(kind.path_segment(), segment);
+        }
+        assert!(CollectionKind::from_path_segment("following").is_none());
     }
 
     #[test]
@@ -289,4 +478,181 @@ mod tests {
         let error = result.error.unwrap();
         assert_eq!(error.code, "INVALID_FORMAT");
     }
+
+    #[test]
+    fn test_with_secret_token_appends_to_page_url() {
+        assert_eq!(
+            with_secret_token("https://soundcloud.com/artist/demo", Some("s-AbC12")),
+            "https://soundcloud.com/artist/demo/s-AbC12"
+        );
+        assert_eq!(
+            with_secret_token("https://soundcloud.com/artist/demo/?si=x", Some("s-AbC12")),
+            "https://soundcloud.com/artist/demo/s-AbC12"
+        );
+    }
+
+    #[test]
+    fn test_with_secret_token_keeps_existing_token() {
+        let url = "https://soundcloud.com/artist/demo/s-AbC12";
+        assert_eq!(with_secret_token(url, Some("s-AbC12")), url);
+        assert_eq!(
+            with_secret_token("https://soundcloud.com/artist/demo", None),
+            "https://soundcloud.com/artist/demo"
+        );
+        assert_eq!(
+            with_secret_token("https://soundcloud.com/artist/demo", Some(" ")),
+            "https://soundcloud.com/artist/demo"
+        );
+    }
+
+    #[test]
+    fn test_with_secret_token_slug_starting_with_s() {
+        assert_eq!(
+            with_secret_token("https://soundcloud.com/artist/s-express", Some("s-AbC12")),
+            "https://soundcloud.com/artist/s-express/s-AbC12"
+        );
+        assert_eq!(
+            with_secret_token(
+                "https://soundcloud.com/artist/sets/s-sides",
+                Some("s-AbC12")
+            ),
+            "https://soundcloud.com/artist/sets/s-sides/s-AbC12"
+        );
+        let url = "https://soundcloud.com/artist/sets/s-sides/s-AbC12";
+        assert_eq!(with_secret_token(url, Some("s-AbC12")), url);
+    }
+
+    #[test]
+    fn test_with_secret_token_api_url() {
+        assert_eq!(
+            with_secret_token("https://api.soundcloud.com/tracks/42", Some("s-AbC12")),
+            "https://api.soundcloud.com/tracks/42?secret_token=s-AbC12"
+        );
+        let url = "https://api.soundcloud.com/tracks/42?secret_token=s-AbC12";
+        assert_eq!(with_secret_token(url, Some("s-AbC12")), url);
+    }
+
+    #[test]
+    fn test_normalize_url_canonicalizes_host_and_strips_tracking() {
+        assert_eq!(
+            normalize_url(
+                "https://m.soundcloud.com/artist/track/?si=abc123&utm_source=clipboard&utm_medium=text",
+                false
+            ),
+            Some("https://soundcloud.com/artist/track".to_string())
+        );
+        assert_eq!(
+            normalize_url("www.SoundCloud.com/artist/sets/mix#comments", false),
+            Some("https://soundcloud.com/artist/sets/mix".to_string())
+        );
+        assert_eq!(
+            normalize_url("https://on.soundcloud.com/rtg5VHcWCnHLN2fxYl?si=x", false),
+            Some("https://on.soundcloud.com/rtg5VHcWCnHLN2fxYl".to_string())
+        );
+    }
+
+    #[test]
+    fn test_normalize_url_keeps_secret_token() {
+        assert_eq!(
+            normalize_url("https://soundcloud.com/artist/demo/s-AbC12?si=x", false),
+            Some("https://soundcloud.com/artist/demo/s-AbC12".to_string())
+        );
+    }
+
+    #[test]
+    fn test_normalize_url_rejects_other_hosts() {
+        assert_eq!(
+            normalize_url("https://api.soundcloud.com/tracks/1", false),
+            None
+        );
+        assert_eq!(
+            normalize_url("https://example.com/artist/track", false),
+            None
+        );
+        assert_eq!(normalize_url("https://soundcloud.com/", false), None);
+    }
+
+    #[test]
+    fn test_normalize_url_expands_track_in_playlist() {
+        let url = "https://soundcloud.com/artist/track?in=owner/sets/summer-mix/s-Tok3n&si=abc";
+        assert_eq!(
+            normalize_url(url, false),
+            Some("https://soundcloud.com/artist/track".to_string())
+        );
+        assert_eq!(
+            normalize_url(url, true),
+            Some("https://soundcloud.com/owner/sets/summer-mix/s-Tok3n".to_string())
+        );
+        // `in` pointing somewhere other than a playlist is ignored
+        assert_eq!(
+            normalize_url("https://soundcloud.com/artist/track?in=owner/likes", true),
+            Some("https://soundcloud.com/artist/track".to_string())
+        );
+    }
+
+    #[test]
+    fn test_extract_urls_from_message() {
+        let text = "check these out: https://soundcloud.com/artist/track?si=1, \
+            (also m.soundcloud.com/artist/track/) and the set \
+            <https://www.soundcloud.com/owner/sets/mix>. Not https://example.com/x \
+            nor https://api.soundcloud.com/tracks/1 or soundcloud.com itself!";
+
+        let urls: Vec<(String, Option<UrlType>)> = extract_urls(text, false)
+            .into_iter()
+            .map(|e| (e.url, e.result.url_type))
+            .collect();
+        assert_eq!(
+            urls,
+            vec![
+                (
+                    "https://soundcloud.com/artist/track".to_string(),
+                    Some(UrlType::Track)
+                ),
+                (
+                    "https://soundcloud.com/owner/sets/mix".to_string(),
+                    Some(UrlType::Playlist)
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extract_urls_ignores_lookalike_hosts() {
+        assert!(extract_urls("https://notsoundcloud.com/promo", false).is_empty());
+        assert!(extract_urls("example.com/?ref=soundcloud.com/artist", false).is_empty());
+        assert!(extract_urls(
+            "see https://example.com/?u=https://soundcloud.com/a/b",
+            false
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn test_extract_urls_dedupes_expanded_playlists() {
+        let text = "https://soundcloud.com/a/one?in=owner/sets/mix \
+            https://soundcloud.com/b/two?in=owner/sets/mix \
+            https://soundcloud.com/owner/sets/mix";
+
+        let extracted = extract_urls(text, true);
+        assert_eq!(extracted.len(), 1);
+        assert_eq!(extracted[0].url, "https://soundcloud.com/owner/sets/mix");
+        assert!(extracted[0].result.valid);
+    }
+
+    #[test]
+    fn test_extract_urls_reports_invalid_pages() {
+        let extracted = extract_urls("https://soundcloud.com/discover", false);
+        assert_eq!(extracted.len(), 1);
+        assert!(!extracted[0].result.valid);
+    }
+
+    #[test]
+    fn test_extracted_url_serialize() {
+        let extracted = extract_urls("soundcloud.com/artist/track", false);
+        let json = serde_json::to_string(&extracted[0]).unwrap();
+        assert_eq!(
+            json,
+            r#"{"url":"https://soundcloud.com/artist/track","valid":true,"urlType":"track","error":null}"#
+        );
+    }
 }
@@ -7,6 +7,7 @@ use tokio::sync::{watch, Mutex};
 
 use crate::models::error::YtDlpError;
 use crate::models::ErrorResponse;
+use crate::services::http;
 use crate::services::sidecar::{bytes_to_string, get_sidecar_version};
 use crate::services::storage::{load_tokens, refresh_and_store_tokens};
 
@@ -75,7 +76,7 @@ pub struct DownloadProgressEvent {
     pub error: Option<ErrorResponse>,
 }
 
-fn sanitize_filename(s: &str) -> String {
+pub fn sanitize_filename(s: &str) -> String {
     s.chars()
         .map(|c| match c {
             '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
@@ -236,6 +237,7 @@ pub async fn download_track_to_mp3<R: tauri::Runtime>(
         "title".to_string(),
         ".+".to_string(),
         escape_for_regex_replacement(&output_result.display_title),
+        "--embed-metadata".to_string(),
         "-o".to_string(),
         output_result.template,
         "--windows-filenames".to_string(),
@@ -250,6 +252,10 @@ pub async fn download_track_to_mp3<R: tauri::Runtime>(
         log::debug!("[ytdlp] Added OAuth token to yt-dlp args");
     }
 
+    // Route yt-dlp through the same proxy as API requests
+    let proxy_args = http::client().config().proxy.ytdlp_args();
+    args.splice(0..0, proxy_args);
+
     if let Some(ffmpeg_path) = get_ffmpeg_path() {
         args.insert(0, "--ffmpeg-location".to_string());
         args.insert(1, ffmpeg_path.to_string_lossy().to_string());
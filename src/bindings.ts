@@ -84,17 +84,130 @@ async signOut() : Promise<Result<null, string>> {
 async validateSoundcloudUrl(url: string) : Promise<ValidationResult> {
     return await TAURI_INVOKE("validate_soundcloud_url", { url });
 },
-async getPlaylistInfo(url: string) : Promise<Result<PlaylistInfo, string>> {
+/**
+ * Find every SoundCloud link in pasted text and normalize it.
+ * 
+ * Hosts are canonicalized and tracking parameters stripped; secret tokens
+ * are kept. With `expand_playlists`, `?in=user/sets/...` track links are
+ * replaced by the playlist they were opened from. Duplicates are removed.
+ */
+async normalizeAndExtractUrls(text: string, expandPlaylists: boolean | null) : Promise<ExtractedUrl[]> {
+    return await TAURI_INVOKE("normalize_and_extract_urls", { text, expandPlaylists });
+},
+/**
+ * Resolve an `on.soundcloud.com` share link to its canonical URL and type.
+ */
+async resolveShareLink(url: string) : Promise<Result<ResolvedLink, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("resolve_share_link", { url }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetch a playlist with all its tracks.
+ * 
+ * Large playlists are paged through; tracks are streamed via
+ * `playlist-fetch-progress` while they load. Playlists fetched recently
+ * are served from the metadata cache unless `force_refresh` is set.
+ * Share links are resolved first.
+ */
+async getPlaylistInfo(url: string, forceRefresh: boolean | null) : Promise<Result<PlaylistInfo, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_playlist_info", { url, forceRefresh }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetch a single track, from the metadata cache unless `force_refresh`
+ * is set or the cached entry expired. Share links are resolved first.
+ */
+async getTrackInfo(url: string, forceRefresh: boolean | null) : Promise<Result<TrackInfo, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_track_info", { url, forceRefresh }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetch every track uploaded by an artist from their profile URL.
+ * 
+ * The result has the same shape as a playlist, titled after the artist,
+ * so it can be fed into `start_download_queue`.
+ */
+async getProfileInfo(url: string) : Promise<Result<PlaylistInfo, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_profile_info", { url }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetch a profile tab: `/likes`, `/reposts`, `/popular-tracks`, `/albums`
+ * or `/sets`.
+ * 
+ * Albums and sets expand into one playlist each; queue them one at a time
+ * with the `playlist` subfolder naming so every set gets its own folder
+ * and track numbering.
+ */
+async getCollectionInfo(url: string) : Promise<Result<CollectionInfo, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_collection_info", { url }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetch the tracks liked by the signed-in user.
+ */
+async getMyLikes() : Promise<Result<PlaylistInfo, string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_playlist_info", { url }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_my_likes") };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
 }
 },
-async getTrackInfo(url: string) : Promise<Result<TrackInfo, string>> {
+/**
+ * Fetch the signed-in user's playlists, private ones included.
+ * 
+ * Private playlists and tracks keep their `secret_token` so they can be
+ * downloaded.
+ */
+async getMyPlaylists() : Promise<Result<PlaylistInfo[], string>> {
     try {
-    return { status: "ok", data: await TAURI_INVOKE("get_track_info", { url }) };
+    return { status: "ok", data: await TAURI_INVOKE("get_my_playlists") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Fetch the signed-in user's uploads, private tracks included.
+ */
+async getMyUploads() : Promise<Result<PlaylistInfo, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("get_my_uploads") };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Search SoundCloud for tracks, playlists or users.
+ * 
+ * Pass `next_offset` of the results as `offset` to get the next page.
+ * `filters` only apply to track searches.
+ */
+async searchSoundcloud(query: string, kind: SearchKind, limit: number, offset: number, filters: SearchFilters | null) : Promise<Result<SearchResults, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("search_soundcloud", { query, kind, limit, offset, filters }) };
 } catch (e) {
     if(e instanceof Error) throw e;
     else return { status: "error", error: e  as any };
@@ -125,13 +238,41 @@ async testFfmpeg() : Promise<Result<string, ErrorResponse>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Route API requests and yt-dlp through the configured proxy.
+ * 
+ * The setting is saved and applied again on startup; until it is first
+ * set, the system proxy environment variables are used.
+ */
+async setProxy(settings: ProxySettings) : Promise<Result<null, ErrorResponse>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("set_proxy", { settings }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Check that SoundCloud is reachable through a proxy before saving it.
+ * 
+ * Returns the round trip time in milliseconds.
+ */
+async testProxy(settings: ProxySettings) : Promise<Result<number, ErrorResponse>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("test_proxy", { settings }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 /**
  * Download and convert a track to MP3 with metadata embedding.
  * 
  * This command orchestrates the full download pipeline:
  * 1. Downloads audio using yt-dlp with OAuth authentication
  * 2. Converts to high-quality MP3 using yt-dlp native conversion
- * 3. Embeds ID3 metadata (title, artist, album, track number, artwork)
+ * 3. Embeds ID3 metadata (title, artist, album, track number, genre, date,
+ * label, ISRC, comment, source URL, BPM, artwork)
  * 4. Emits progress events throughout the process
  */
 async downloadTrackFull(request: DownloadRequest) : Promise<Result<string, ErrorResponse>> {
@@ -182,6 +323,75 @@ async respondToAuthChoice(choice: AuthChoice) : Promise<Result<null, string>> {
     else return { status: "error", error: e  as any };
 }
 },
+/**
+ * Preview the artist/title produced by the title cleanup rules.
+ * 
+ * Fails if one of the junk patterns is not a valid regular expression.
+ */
+async previewTitleRules(title: string, artist: string, rules: TitleRules) : Promise<Result<CleanedTitle, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("preview_title_rules", { title, artist, rules }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Re-tag previously downloaded files in a directory from SoundCloud metadata.
+ * 
+ * Files are matched to SoundCloud tracks by their embedded track ID or URL,
+ * or by file name via the download history. With `dryRun` set, no file is
+ * written and the result lists the proposed changes. Progress is emitted
+ * via `retag-progress`.
+ */
+async retagLibrary(dir: string, options: RetagOptions) : Promise<Result<RetagSummary, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("retag_library", { dir, options }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Read the editable tags of a downloaded file (MP3, AIFF or WAV).
+ */
+async readTags(path: string) : Promise<Result<EditableTags, ErrorResponse>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("read_tags", { path }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Apply the same tag changes to one or more files.
+ * 
+ * Use a single path to edit one file, or all files of a playlist for batch
+ * edits such as setting the album. Returns a result per file.
+ */
+async writeTags(paths: string[], patch: TagPatch) : Promise<Result<TagWriteResult[], ErrorResponse>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("write_tags", { paths, patch }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
+/**
+ * Export downloaded playlists as a Rekordbox XML or Traktor NML collection.
+ * 
+ * Several playlists can be merged into one file; each becomes a playlist
+ * node named after its title, listing its files in order. Files that can
+ * no longer be read are left out and reported in `skipped`.
+ */
+async exportDjCollection(playlists: DjPlaylist[], outputPath: string, format: DjCollectionFormat) : Promise<Result<DjExportResult, string>> {
+    try {
+    return { status: "ok", data: await TAURI_INVOKE("export_dj_collection", { playlists, outputPath, format }) };
+} catch (e) {
+    if(e instanceof Error) throw e;
+    else return { status: "error", error: e  as any };
+}
+},
 async checkWritePermission(path: string) : Promise<Result<boolean, string>> {
     try {
     return { status: "ok", data: await TAURI_INVOKE("check_write_permission", { path }) };
@@ -207,12 +417,14 @@ async validateDownloadPath(path: string) : Promise<Result<boolean, string>> {
 }
 },
 /**
- * Checks for available updates.
+ * Checks for available updates silently.
+ * 
+ * Network errors and timeouts are handled gracefully — the app continues
+ * normally without showing errors to the user (FR27 compliance).
  * 
  * # Returns
  * * `Ok(Some(UpdateInfo))` - Update available with version, notes, and date
- * * `Ok(None)` - No update available
- * * `Err(String)` - Error message if check fails
+ * * `Ok(None)` - No update available, or check failed silently
  */
 async checkForUpdates() : Promise<Result<UpdateInfo | null, string>> {
     try {
@@ -252,15 +464,469 @@ async installUpdate() : Promise<Result<null, string>> {
 
 /** user-defined types **/
 
+/**
+ * Playlist-level metadata used as album tags when downloading an album/EP set.
+ */
+export type AlbumMetadata = { 
+/**
+ * Album artist (the playlist owner).
+ */
+artist: string; 
+/**
+ * Playlist artwork, used as cover for every track.
+ */
+artwork_url: string | null; release_date: string | null; label: string | null; genre: string | null }
+/**
+ * Resolution of the artwork embedded in downloaded files.
+ */
+export type ArtworkSize = 
+/**
+ * 500x500, a good balance between quality and file size.
+ */
+{ kind: "t500X500" } | 
+/**
+ * The image as uploaded (may be several megabytes).
+ */
+{ kind: "original" } | 
+/**
+ * The original image scaled down to fit within `px` x `px`.
+ */
+{ kind: "resize"; px: number }
+/**
+ * New cover art for a file.
+ */
+export type ArtworkSource = 
+/**
+ * Image file on disk.
+ */
+{ kind: "path"; path: string } | 
+/**
+ * Image downloaded from a URL.
+ */
+{ kind: "url"; url: string } | 
+/**
+ * Remove the front cover.
+ */
+{ kind: "remove" }
+/**
+ * Embedded artwork summary.
+ */
+export type ArtworkSummary = { mimeType: string; sizeBytes: number }
 export type AuthChoice = "re_authenticated" | "continue_standard"
-export type DownloadRequest = { trackUrl: string; trackId: string; title: string; artist: string; album: string | null; trackNumber: number | null; totalTracks: number | null; artworkUrl: string | null; outputDir: string | null }
+/**
+ * Artist and title after applying the rules.
+ */
+export type CleanedTitle = { artist: string; title: string }
+/**
+ * Tracks or playlists listed on a profile tab (`/likes`, `/sets`, ...).
+ */
+export type CollectionInfo = { kind: CollectionKind; user: UserInfo; 
+/**
+ * A single playlist for track tabs, one per set for `/albums` and
+ * `/sets`. Each set is meant to be downloaded as its own queue.
+ */
+playlists: PlaylistInfo[] }
+/**
+ * Profile tabs that can be downloaded as a whole.
+ */
+export type CollectionKind = 
+/**
+ * `/likes`: tracks the user liked.
+ */
+"likes" | 
+/**
+ * `/reposts`: tracks the user reposted.
+ */
+"reposts" | 
+/**
+ * `/popular-tracks`: the user's tracks, most played first.
+ */
+"popularTracks" | 
+/**
+ * `/albums`: albums, EPs and singles, one playlist each.
+ */
+"albums" | 
+/**
+ * `/sets`: playlists, one playlist each.
+ */
+"sets"
+/**
+ * Collection file format.
+ */
+export type DjCollectionFormat = 
+/**
+ * Rekordbox XML (`File > Import > rekordbox xml`).
+ */
+"rekordbox" | 
+/**
+ * Traktor NML collection.
+ */
+"traktor"
+/**
+ * Result of an export.
+ */
+export type DjExportResult = { path: string; tracks: number; playlists: number; 
+/**
+ * Files that could not be read and were left out.
+ */
+skipped: string[] }
+/**
+ * A playlist to export: its name and the files in playlist order.
+ */
+export type DjPlaylist = { title: string; paths: string[] }
+export type DownloadRequest = { trackUrl: string; trackId: string; title: string; artist: string; album: string | null; trackNumber: number | null; totalTracks: number | null; artworkUrl: string | null; outputDir: string | null; albumArtist: string | null; genre: string | null; releaseDate: string | null; label: string | null; isrc: string | null; description: string | null; permalinkUrl: string | null; bpm: number | null; 
+/**
+ * Duration in milliseconds.
+ */
+duration: number | null; 
+/**
+ * Secret token of a private track, added to `track_url` for yt-dlp.
+ */
+secretToken: string | null; tagOptions?: TagOptions; 
+/**
+ * Title cleanup rules (None disables cleanup).
+ */
+titleRules: TitleRules | null; 
+/**
+ * Split mixes into songs after download (None keeps them whole).
+ */
+splitMix: MixSplitOptions | null }
+/**
+ * Editable tag fields of a file.
+ */
+export type EditableTags = { title: string | null; artist: string | null; album: string | null; albumArtist: string | null; genre: string | null; 
+/**
+ * Recording date (`YYYY`, `YYYY-MM` or `YYYY-MM-DD`).
+ */
+date: string | null; trackNumber: number | null; totalTracks: number | null; label: string | null; isrc: string | null; bpm: string | null; comment: string | null; url: string | null; artwork: ArtworkSummary | null }
 export type ErrorResponse = { code: string; message: string }
+/**
+ * A SoundCloud URL found in pasted text, normalized, with its validation.
+ */
+export type ExtractedUrl = ({ valid: boolean; urlType: UrlType | null; error: ValidationError | null }) & { url: string }
+/**
+ * File name used when saving the cover next to the downloaded tracks.
+ */
+export type FolderArtName = 
+/**
+ * `cover.jpg`
+ */
+"cover" | 
+/**
+ * `folder.jpg`
+ */
+"folder"
+/**
+ * Options for splitting a mix after download.
+ */
+export type MixSplitOptions = { 
+/**
+ * Fall back to silence detection when there is no tracklist.
+ */
+detectSilence: boolean; 
+/**
+ * Volume below which audio counts as silence, in dB.
+ */
+silenceThresholdDb: number; 
+/**
+ * Minimum length of a silence to split on, in seconds.
+ */
+minSilenceSecs: number; 
+/**
+ * Keep the full mix next to the pieces.
+ */
+keepOriginal: boolean }
+/**
+ * Whether a track can be downloaded in full, judged from its metadata.
+ */
+export type Playability = "playable" | 
+/**
+ * Only a 30 second preview is available (e.g. Go+ tracks).
+ */
+"previewOnly" | 
+/**
+ * The track cannot be streamed, so its download will fail.
+ */
+"blocked"
 /**
  * Playlist information from SoundCloud API.
  */
-export type PlaylistInfo = { id: number; title: string; user: UserInfo; artwork_url: string | null; track_count: number; tracks: TrackInfo[] }
-export type QueueItemRequest = { trackUrl: string; trackId: string; title: string; artist: string; artworkUrl: string | null }
-export type StartQueueRequest = { tracks: QueueItemRequest[]; albumName: string | null; outputDir: string | null }
+export type PlaylistInfo = { id: number; title: string; user: UserInfo; artwork_url: string | null; track_count: number; tracks: TrackInfo[]; set_type?: string | null; 
+/**
+ * Album metadata, present when the set is an album or EP.
+ */
+album?: AlbumMetadata | null; 
+/**
+ * Secret token (`s-...`) of a private playlist.
+ */
+secret_token?: string | null; 
+/**
+ * Playlist page, used to fetch the full track list of search results.
+ */
+permalink_url?: string | null; 
+/**
+ * Tracks listed in the playlist that could not be fetched.
+ */
+unavailable?: UnavailableTrack[] }
+/**
+ * How requests reach the internet.
+ */
+export type ProxyMode = 
+/**
+ * Use `HTTP_PROXY`, `HTTPS_PROXY` and `ALL_PROXY` when set.
+ */
+"system" | 
+/**
+ * Connect directly, ignoring proxy environment variables.
+ */
+"direct" | "http" | "https" | "socks5"
+/**
+ * Proxy configured in the settings.
+ */
+export type ProxySettings = { mode: ProxyMode; 
+/**
+ * Proxy host name or IP, for the explicit modes.
+ */
+host: string; 
+/**
+ * Defaults to 8080 for HTTP, 443 for HTTPS and 1080 for SOCKS5.
+ */
+port: number | null; username: string | null; password: string | null }
+export type QueueItemRequest = { trackUrl: string; trackId: string; title: string; artist: string; artworkUrl: string | null; genre: string | null; releaseDate: string | null; label: string | null; isrc: string | null; description: string | null; permalinkUrl: string | null; bpm: number | null; 
+/**
+ * Duration in milliseconds.
+ */
+duration: number | null; 
+/**
+ * Position in the source playlist, used as track number. Defaults to
+ * the position in the queue.
+ */
+trackNumber: number | null; 
+/**
+ * Secret token of a private track, added to `track_url` for yt-dlp.
+ */
+secretToken: string | null }
+/**
+ * A share link resolved to its canonical page.
+ */
+export type ResolvedLink = { 
+/**
+ * Canonical `https://soundcloud.com/...` URL.
+ */
+url: string; urlType: UrlType | null }
+/**
+ * Result for a single file.
+ */
+export type RetagFileResult = { path: string; trackId: string | null; status: RetagStatus; changes: TagChange[]; error: string | null }
+/**
+ * Options for a re-tag run.
+ */
+export type RetagOptions = { 
+/**
+ * Only report proposed changes, don't write anything.
+ */
+dryRun: boolean; 
+/**
+ * Only convert existing tags to `tag_options.profile`, without looking
+ * up SoundCloud metadata.
+ */
+convertOnly: boolean; tagOptions: TagOptions; titleRules: TitleRules | null }
+/**
+ * Outcome for a single file.
+ */
+export type RetagStatus = "updated" | 
+/**
+ * Dry run: the file would be updated.
+ */
+"would_update" | "unchanged" | 
+/**
+ * No SoundCloud track could be matched to the file.
+ */
+"unidentified" | "failed"
+/**
+ * Summary of a re-tag run.
+ */
+export type RetagSummary = { dryRun: boolean; total: number; updated: number; unchanged: number; unidentified: number; failed: number; results: RetagFileResult[] }
+/**
+ * Filters for track searches; ignored when searching playlists or users.
+ */
+export type SearchFilters = { 
+/**
+ * Shortest track to return, in milliseconds.
+ */
+minDuration: number | null; 
+/**
+ * Longest track to return, in milliseconds.
+ */
+maxDuration: number | null; genre: string | null; 
+/**
+ * Only tracks whose uploader enabled downloads.
+ */
+downloadableOnly: boolean }
+/**
+ * Items of a search results page, depending on the kind searched.
+ */
+export type SearchItems = { kind: "tracks"; items: TrackInfo[] } | 
+/**
+ * Playlists with the tracks listed by the API; fetch `permalink_url`
+ * with `get_playlist_info` when `track_count` is larger.
+ */
+{ kind: "playlists"; items: PlaylistInfo[] } | { kind: "users"; items: UserSummary[] }
+/**
+ * What to search SoundCloud for.
+ */
+export type SearchKind = "tracks" | "playlists" | "users"
+/**
+ * One page of search results.
+ */
+export type SearchResults = { results: SearchItems; 
+/**
+ * Offset of the next page, or None on the last page.
+ */
+next_offset: number | null }
+/**
+ * When an `Artist - Title` upload title is split into artist and title.
+ */
+export type SplitMode = 
+/**
+ * Never split; keep the uploader as artist.
+ */
+"never" | 
+/**
+ * Split only when the uploader looks like a label or promo channel.
+ */
+"labelsOnly" | 
+/**
+ * Split every title containing a separator.
+ */
+"always"
+export type StartQueueRequest = { tracks: QueueItemRequest[]; albumName: string | null; outputDir: string | null; 
+/**
+ * Album artist written to every track (the playlist owner).
+ */
+albumArtist: string | null; 
+/**
+ * Album mode: playlist-level metadata for album/EP sets.
+ */
+album: AlbumMetadata | null; tagOptions?: TagOptions; 
+/**
+ * Save the cover as `cover.jpg`/`folder.jpg` in the output directory.
+ */
+folderArt: FolderArtName | null; 
+/**
+ * Title cleanup rules (None disables cleanup).
+ */
+titleRules: TitleRules | null; 
+/**
+ * Split mixes into songs after download (None keeps them whole).
+ */
+splitMix: MixSplitOptions | null; 
+/**
+ * Save tracks to an artist subfolder (None saves to the output directory).
+ */
+subfolder: SubfolderNaming | null; 
+/**
+ * Tracks in the source playlist including unavailable ones
+ * (`PlaylistInfo.track_count`). Defaults to the number of queued tracks.
+ */
+totalTracks: number | null }
+/**
+ * Subfolder of the output directory the tracks are saved to.
+ */
+export type SubfolderNaming = 
+/**
+ * One folder for the queue, named after the album artist (the playlist
+ * or profile owner).
+ */
+"albumArtist" | 
+/**
+ * One folder per track artist.
+ */
+"trackArtist" | 
+/**
+ * One folder for the queue, named after the album (the playlist
+ * title), so each set of a collection gets its own folder.
+ */
+"playlist"
+/**
+ * A single tag field that differs between the file and SoundCloud.
+ */
+export type TagChange = { field: string; old: string | null; new: string | null }
+/**
+ * User-configurable tagging options sent with download requests.
+ */
+export type TagOptions = { 
+/**
+ * Resolution of the embedded cover art.
+ */
+artworkSize: ArtworkSize; writeMode: TagWriteMode; 
+/**
+ * ID3 versions written to the file.
+ */
+profile: TagProfile }
+/**
+ * Changes to apply to one or more files.
+ * 
+ * A missing field is left untouched, `null` (or an empty string) removes
+ * the frame, and a value replaces it.
+ */
+export type TagPatch = { title?: string | null; artist?: string | null; album?: string | null; albumArtist?: string | null; genre?: string | null; date?: string | null; trackNumber?: number | null; totalTracks?: number | null; label?: string | null; isrc?: string | null; bpm?: string | null; comment?: string | null; url?: string | null; artwork?: ArtworkSource | null }
+/**
+ * Which ID3 versions are written to a file.
+ */
+export type TagProfile = 
+/**
+ * ID3v2.3 with UTF-16 text.
+ */
+"id3V23" | 
+/**
+ * ID3v2.4 with UTF-8 text.
+ */
+"id3V24" | 
+/**
+ * ID3v2.3 plus an ID3v1 footer (MP3 only).
+ */
+"id3V23WithV1" | 
+/**
+ * ID3v2.4 plus an ID3v1 footer (MP3 only).
+ */
+"id3V24WithV1"
+/**
+ * How our tags are combined with the tag already in the file.
+ */
+export type TagWriteMode = 
+/**
+ * Keep frames written by yt-dlp/ffmpeg and overwrite only the fields we set.
+ */
+"merge" | 
+/**
+ * Discard the existing tag and write ours from scratch.
+ */
+"replace"
+/**
+ * Result of writing a patch to one file.
+ */
+export type TagWriteResult = { path: string; error: string | null }
+/**
+ * User-configurable title cleanup rules.
+ */
+export type TitleRules = { splitMode: SplitMode; 
+/**
+ * Words that mark an uploader name as a label (matched case-insensitively).
+ */
+labelKeywords: string[]; 
+/**
+ * Uploader names always treated as labels.
+ */
+labelAccounts: string[]; 
+/**
+ * Regular expressions removed from titles (matched case-insensitively).
+ */
+junkPatterns: string[]; 
+/**
+ * Move `feat.`/`ft.` credits from the title into the artist field.
+ */
+extractFeaturing: boolean }
 /**
  * Track information from SoundCloud API.
  */
@@ -268,16 +934,86 @@ export type TrackInfo = { id: number; title: string; user: UserInfo; artwork_url
 /**
  * Duration in milliseconds.
  */
-duration: number }
+duration: number; genre?: string | null; 
+/**
+ * Release date, falling back to the upload date.
+ */
+release_date?: string | null; label?: string | null; isrc?: string | null; description?: string | null; permalink_url?: string | null; bpm?: number | null; 
+/**
+ * Secret token (`s-...`) of a private track, needed to download it.
+ */
+secret_token?: string | null; downloadable?: boolean | null; streamable?: boolean | null; 
+/**
+ * `ALLOW`, `MONETIZE`, `SNIP` or `BLOCK`.
+ */
+policy?: string | null; 
+/**
+ * `playable`, `preview` or `blocked`.
+ */
+access?: string | null; 
+/**
+ * Summary of `streamable`, `policy` and `access`, so tracks that will
+ * fail or only download as a preview can be left out of the queue.
+ */
+playability?: Playability; 
+/**
+ * 1-based position in the playlist the track was loaded from. Tracks
+ * that could not be fetched keep their slot, so it can skip numbers.
+ */
+playlist_position?: number | null }
+/**
+ * Why a playlist track could not be fetched.
+ */
+export type UnavailableReason = 
+/**
+ * Not available in the user's country (HTTP 403).
+ */
+"geoBlocked" | 
+/**
+ * Deleted by the uploader or taken down (HTTP 404).
+ */
+"removed" | 
+/**
+ * Made private after being added to the playlist (HTTP 401).
+ */
+"private" | 
+/**
+ * Left out by the API for another reason, e.g. a blocked uploader.
+ */
+"apiFiltered" | 
+/**
+ * The request for the track kept failing (server or network error).
+ */
+"unknown"
+/**
+ * A playlist track that could not be fetched.
+ */
+export type UnavailableTrack = { id: number; 
+/**
+ * 1-based position in the playlist.
+ */
+position: number; reason: UnavailableReason }
 /**
  * Information about an available update.
  */
 export type UpdateInfo = { version: string; body: string | null; date: string | null }
-export type UrlType = "playlist" | "track"
+export type UrlType = "playlist" | "track" | 
+/**
+ * An artist profile; downloads every track of the user.
+ */
+"profile" | 
+/**
+ * A tab of a profile listing tracks or playlists.
+ */
+{ collection: CollectionKind }
 /**
  * User information from SoundCloud API (public).
  */
 export type UserInfo = { username: string }
+/**
+ * A user found by search; download their tracks with `get_profile_info`.
+ */
+export type UserSummary = { id: number; username: string; avatar_url?: string | null; permalink_url?: string | null; track_count?: number | null; followers_count?: number | null }
 export type ValidationError = { code: string; message: string; hint: string | null }
 export type ValidationResult = { valid: boolean; urlType: UrlType | null; error: ValidationError | null }
 
@@ -30,6 +30,18 @@ const createDownloadRequest = (
   totalTracks: null,
   artworkUrl: null,
   outputDir: null,
+  albumArtist: null,
+  genre: null,
+  releaseDate: null,
+  label: null,
+  isrc: null,
+  description: null,
+  permalinkUrl: null,
+  bpm: null,
+  duration: null,
+  secretToken: null,
+  titleRules: null,
+  splitMix: null,
   ...partial,
 });
 
@@ -38,6 +50,32 @@ const createQueueItem = (
   partial: Partial<QueueItemRequest> & Pick<QueueItemRequest, 'trackUrl' | 'trackId' | 'title' | 'artist'>
 ): QueueItemRequest => ({
   artworkUrl: null,
+  genre: null,
+  releaseDate: null,
+  label: null,
+  isrc: null,
+  description: null,
+  permalinkUrl: null,
+  bpm: null,
+  duration: null,
+  trackNumber: null,
+  secretToken: null,
+  ...partial,
+});
+
+// Helper to create a complete StartQueueRequest
+const createQueueRequest = (
+  partial: Partial<StartQueueRequest> & Pick<StartQueueRequest, 'tracks'>
+): StartQueueRequest => ({
+  albumName: null,
+  outputDir: null,
+  albumArtist: null,
+  album: null,
+  folderArt: null,
+  titleRules: null,
+  splitMix: null,
+  subfolder: null,
+  totalTracks: null,
   ...partial,
 });
 
@@ -134,7 +172,7 @@ describe('startDownloadQueue', () => {
   });
 
   it('should invoke startDownloadQueue with request object', async () => {
-    const request: StartQueueRequest = {
+    const request = createQueueRequest({
       tracks: [
         createQueueItem({
           trackUrl: 'https://soundcloud.com/artist/track1',
@@ -151,8 +189,7 @@ describe('startDownloadQueue', () => {
         }),
       ],
       albumName: 'Playlist Name',
-      outputDir: null,
-    };
+    });
 
     mockStartDownloadQueue.mockResolvedValue({ status: 'ok', data: null });
 
@@ -162,7 +199,7 @@ describe('startDownloadQueue', () => {
   });
 
   it('should work without album name', async () => {
-    const request: StartQueueRequest = {
+    const request = createQueueRequest({
       tracks: [
         createQueueItem({
           trackUrl: 'https://soundcloud.com/artist/track',
@@ -171,9 +208,7 @@ describe('startDownloadQueue', () => {
           artist: 'Artist',
         }),
       ],
-      albumName: null,
-      outputDir: null,
-    };
+    });
 
     mockStartDownloadQueue.mockResolvedValue({ status: 'ok', data: null });
 
@@ -183,11 +218,7 @@ describe('startDownloadQueue', () => {
   });
 
   it('should handle empty tracks array', async () => {
-    const request: StartQueueRequest = {
-      tracks: [],
-      albumName: null,
-      outputDir: null,
-    };
+    const request = createQueueRequest({ tracks: [] });
 
     mockStartDownloadQueue.mockResolvedValue({ status: 'ok', data: null });
 
@@ -197,7 +228,7 @@ describe('startDownloadQueue', () => {
   });
 
   it('should propagate errors from the backend', async () => {
-    const request: StartQueueRequest = {
+    const request = createQueueRequest({
       tracks: [
         createQueueItem({
           trackUrl: 'https://soundcloud.com/artist/track',
@@ -206,9 +237,7 @@ describe('startDownloadQueue', () => {
           artist: 'Artist',
         }),
       ],
-      albumName: null,
-      outputDir: null,
-    };
+    });
     const errorMessage = 'Failed to start queue';
 
     mockStartDownloadQueue.mockResolvedValue({ status: 'error', error: errorMessage });
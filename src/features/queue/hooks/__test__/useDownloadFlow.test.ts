@@ -294,26 +294,28 @@ describe('useDownloadFlow', () => {
         await result.current.handleDownload();
       });
 
-      expect(mockStartDownloadQueue).toHaveBeenCalledWith({
-        tracks: [
-          {
-            trackUrl: 'https://api.soundcloud.com/tracks/1',
-            trackId: '1',
-            title: 'Track 1',
-            artist: 'Artist1',
-            artworkUrl: 'https://example.com/art1.jpg',
-          },
-          {
-            trackUrl: 'https://api.soundcloud.com/tracks/2',
-            trackId: '2',
-            title: 'Track 2',
-            artist: 'Artist2',
-            artworkUrl: null,
-          },
-        ],
-        albumName: null,
-        outputDir: null,
-      });
+      expect(mockStartDownloadQueue).toHaveBeenCalledWith(
+        expect.objectContaining({
+          tracks: [
+            expect.objectContaining({
+              trackUrl: 'https://api.soundcloud.com/tracks/1',
+              trackId: '1',
+              title: 'Track 1',
+              artist: 'Artist1',
+              artworkUrl: 'https://example.com/art1.jpg',
+            }),
+            expect.objectContaining({
+              trackUrl: 'https://api.soundcloud.com/tracks/2',
+              trackId: '2',
+              title: 'Track 2',
+              artist: 'Artist2',
+              artworkUrl: null,
+            }),
+          ],
+          albumName: null,
+          outputDir: null,
+        })
+      );
     });
 
     it('should include album name when media is a playlist', async () => {
@@ -339,6 +341,29 @@ describe('useDownloadFlow', () => {
       );
     });
 
+    it('should number tracks against the playlist track count', async () => {
+      mockUseMediaFetch.mockReturnValue({
+        data: mockPlaylist,
+        isLoading: false,
+        error: null,
+      });
+      mockUseQueueStoreGetState.mockReturnValue(
+        createMockQueueState(mockQueueTracksData)
+      );
+
+      const { result } = renderHook(() => useDownloadFlow());
+
+      await act(async () => {
+        await result.current.handleDownload();
+      });
+
+      expect(mockStartDownloadQueue).toHaveBeenCalledWith(
+        expect.objectContaining({
+          totalTracks: mockPlaylist.track_count,
+        })
+      );
+    });
+
     it('should not include album name when media is a single track', async () => {
       mockUseMediaFetch.mockReturnValue({
         data: mockTrack,
@@ -382,15 +407,17 @@ describe('useDownloadFlow', () => {
         await result.current.handleDownload();
       });
 
-      expect(mockStartDownloadQueue).toHaveBeenCalledWith({
-        tracks: [
-          expect.objectContaining({
-            artworkUrl: null,
-          }),
-        ],
-        albumName: null,
-        outputDir: null,
-      });
+      expect(mockStartDownloadQueue).toHaveBeenCalledWith(
+        expect.objectContaining({
+          tracks: [
+            expect.objectContaining({
+              artworkUrl: null,
+            }),
+          ],
+          albumName: null,
+          outputDir: null,
+        })
+      );
     });
 
     it('should set isPending to true when download starts', async () => {
@@ -128,26 +128,28 @@ describe('useRetryTracks', () => {
 
       expect(mockState.setRetrying).toHaveBeenCalledWith(true);
       expect(mockState.setInitializing).toHaveBeenCalledWith(true);
-      expect(startDownloadQueue).toHaveBeenCalledWith({
-        tracks: [
-          {
-            trackUrl: 'https://api.soundcloud.com/tracks/1',
-            trackId: '1',
-            title: 'Track 1',
-            artist: 'Artist 1',
-            artworkUrl: null,
-          },
-          {
-            trackUrl: 'https://api.soundcloud.com/tracks/2',
-            trackId: '2',
-            title: 'Track 2',
-            artist: 'Artist 2',
-            artworkUrl: 'http://art.jpg',
-          },
-        ],
-        albumName: null,
-        outputDir: '/test/downloads',
-      });
+      expect(startDownloadQueue).toHaveBeenCalledWith(
+        expect.objectContaining({
+          tracks: [
+            expect.objectContaining({
+              trackUrl: 'https://api.soundcloud.com/tracks/1',
+              trackId: '1',
+              title: 'Track 1',
+              artist: 'Artist 1',
+              artworkUrl: null,
+            }),
+            expect.objectContaining({
+              trackUrl: 'https://api.soundcloud.com/tracks/2',
+              trackId: '2',
+              title: 'Track 2',
+              artist: 'Artist 2',
+              artworkUrl: 'http://art.jpg',
+            }),
+          ],
+          albumName: null,
+          outputDir: '/test/downloads',
+        })
+      );
     });
 
     it('should reset retrying state on error', async () => {
@@ -225,19 +227,21 @@ describe('useRetryTracks', () => {
 
       expect(mockState.setRetrying).toHaveBeenCalledWith(true);
       expect(mockState.setInitializing).toHaveBeenCalledWith(true);
-      expect(startDownloadQueue).toHaveBeenCalledWith({
-        tracks: [
-          {
-            trackUrl: 'https://api.soundcloud.com/tracks/1',
-            trackId: '1',
-            title: 'Track 1',
-            artist: 'Artist 1',
-            artworkUrl: null,
-          },
-        ],
-        albumName: null,
-        outputDir: '/test/downloads',
-      });
+      expect(startDownloadQueue).toHaveBeenCalledWith(
+        expect.objectContaining({
+          tracks: [
+            expect.objectContaining({
+              trackUrl: 'https://api.soundcloud.com/tracks/1',
+              trackId: '1',
+              title: 'Track 1',
+              artist: 'Artist 1',
+              artworkUrl: null,
+            }),
+          ],
+          albumName: null,
+          outputDir: '/test/downloads',
+        })
+      );
     });
 
     it('should reset retrying state on error', async () => {
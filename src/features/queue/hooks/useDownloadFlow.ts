@@ -73,6 +73,13 @@ export function useDownloadFlow(): UseDownloadFlowReturn {
         tracks: queueTracks.map(queueTrackToDownloadRequest),
         albumName: albumName ?? null,
         outputDir: outputDir ?? null,
+        albumArtist: null,
+        album: isPlaylist(media) ? media.album ?? null : null,
+        folderArt: null,
+        titleRules: null,
+        splitMix: null,
+        subfolder: null,
+        totalTracks: isPlaylist(media) ? media.track_count : null,
       });
     } catch (error) {
       logger.error(`[useDownloadFlow] Download failed: ${error}`);
@@ -24,6 +24,13 @@ async function executeRetry(tracks: Track[], logMessage: string): Promise<void>
       tracks: tracks.map(queueTrackToDownloadRequest),
       albumName: null,
       outputDir: outputDir ?? null,
+      albumArtist: null,
+      album: null,
+      folderArt: null,
+      titleRules: null,
+      splitMix: null,
+      subfolder: null,
+      totalTracks: null,
     });
   } catch (error) {
     logger.error(`[useRetryTracks] Retry failed: ${error}`);
@@ -1,3 +1,4 @@
+import type { TrackInfo } from '@/bindings';
 import type { AppError } from './errors';
 
 export type TrackStatus =
@@ -17,6 +18,8 @@ export interface Track {
   error?: AppError;
   downloadedBytes?: number;
   totalBytes?: number;
+  /** Metadata the track was loaded with, passed on for tagging */
+  info?: TrackInfo;
 }
 
 /** Context for playlist track numbering (used by yt-dlp) */
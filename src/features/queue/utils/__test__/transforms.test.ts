@@ -33,6 +33,7 @@ describe('trackInfoToQueueTrack', () => {
       artist: 'TestArtist',
       artworkUrl: 'https://example.com/art.jpg',
       status: 'pending',
+      info: mockTrack,
     });
   });
 
@@ -108,6 +109,37 @@ describe('queueTrackToDownloadRequest', () => {
       title: 'Test Track',
       artist: 'TestArtist',
       artworkUrl: 'https://example.com/art.jpg',
+      genre: null,
+      releaseDate: null,
+      label: null,
+      isrc: null,
+      description: null,
+      permalinkUrl: null,
+      bpm: null,
+      duration: null,
+      trackNumber: null,
+      secretToken: null,
+    });
+  });
+
+  it('should forward the metadata the track was loaded with', () => {
+    const result = queueTrackToDownloadRequest({
+      ...mockQueueTrack,
+      info: {
+        ...mockTrack,
+        genre: 'Techno',
+        permalink_url: 'https://soundcloud.com/testartist/test-track',
+        secret_token: 's-AbC12',
+        playlist_position: 4,
+      },
+    });
+
+    expect(result).toMatchObject({
+      genre: 'Techno',
+      permalinkUrl: 'https://soundcloud.com/testartist/test-track',
+      duration: 180000,
+      trackNumber: 4,
+      secretToken: 's-AbC12',
     });
   });
 
@@ -12,6 +12,7 @@ export function trackInfoToQueueTrack(track: TrackInfo): Track {
     artist: track.user.username,
     artworkUrl: track.artwork_url,
     status: 'pending',
+    info: track,
   };
 }
 
@@ -26,11 +27,22 @@ export function playlistTracksToQueueTracks(tracks: TrackInfo[]): Track[] {
  * Convert a queue Track to a download request item
  */
 export function queueTrackToDownloadRequest(track: Track): QueueItemRequest {
+  const info = track.info;
   return {
     trackUrl: `https://api.soundcloud.com/tracks/${track.id}`,
     trackId: track.id,
     title: track.title,
     artist: track.artist,
     artworkUrl: track.artworkUrl ?? null,
+    genre: info?.genre ?? null,
+    releaseDate: info?.release_date ?? null,
+    label: info?.label ?? null,
+    isrc: info?.isrc ?? null,
+    description: info?.description ?? null,
+    permalinkUrl: info?.permalink_url ?? null,
+    bpm: info?.bpm ?? null,
+    duration: info?.duration ?? null,
+    trackNumber: info?.playlist_position ?? null,
+    secretToken: info?.secret_token ?? null,
   };
 }
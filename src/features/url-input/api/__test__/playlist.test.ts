@@ -1,5 +1,5 @@
 import { describe, it, expect, vi, beforeEach } from 'vitest';
-import { fetchPlaylistInfo } from '../playlist';
+import { fetchPlaylistInfo, fetchCollectionInfo } from '../playlist';
 
 vi.mock('@tauri-apps/api/core', () => ({
   invoke: vi.fn(),
@@ -27,6 +27,7 @@ describe('fetchPlaylistInfo', () => {
 
     expect(invoke).toHaveBeenCalledWith('get_playlist_info', {
       url: 'https://soundcloud.com/user/sets/playlist',
+      forceRefresh: false,
     });
   });
 
@@ -101,3 +102,45 @@ describe('fetchPlaylistInfo', () => {
     );
   });
 });
+
+describe('fetchCollectionInfo', () => {
+  const playlist = (id: number) => ({
+    id,
+    title: `Set ${id}`,
+    user: { username: 'testuser' },
+    artwork_url: null,
+    track_count: 0,
+    tracks: [],
+  });
+
+  beforeEach(() => {
+    vi.clearAllMocks();
+  });
+
+  it('should return the single playlist of a track tab', async () => {
+    vi.mocked(invoke).mockResolvedValueOnce({
+      kind: 'likes',
+      user: { username: 'testuser' },
+      playlists: [playlist(1)],
+    });
+
+    const result = await fetchCollectionInfo('https://soundcloud.com/testuser/likes');
+
+    expect(invoke).toHaveBeenCalledWith('get_collection_info', {
+      url: 'https://soundcloud.com/testuser/likes',
+    });
+    expect(result.id).toBe(1);
+  });
+
+  it('should reject a tab holding several sets', async () => {
+    vi.mocked(invoke).mockResolvedValueOnce({
+      kind: 'sets',
+      user: { username: 'testuser' },
+      playlists: [playlist(1), playlist(2)],
+    });
+
+    await expect(
+      fetchCollectionInfo('https://soundcloud.com/testuser/sets')
+    ).rejects.toThrow('MultipleSets');
+  });
+});
@@ -26,3 +26,36 @@ export async function fetchTrackInfo(url: string) {
   logger.info(`[playlist.ts] Invoking get_track_info with url: ${url}`);
   return api.getTrackInfo(url);
 }
+
+/**
+ * Fetches every track uploaded by an artist from their profile URL.
+ *
+ * @param url - The SoundCloud profile URL
+ * @returns The uploads, shaped like a playlist titled after the artist
+ * @throws Error if the fetch fails
+ */
+export async function fetchProfileInfo(url: string) {
+  logger.info(`[playlist.ts] Invoking get_profile_info with url: ${url}`);
+  return api.getProfileInfo(url);
+}
+
+/**
+ * Fetches a profile tab (likes, reposts, popular tracks, albums or sets).
+ *
+ * Track tabs come back as a single playlist. Album and set tabs hold one
+ * playlist per set, which are downloaded one at a time, so only a tab with
+ * a single set can be opened here.
+ *
+ * @param url - The SoundCloud profile tab URL
+ * @returns The tab's tracks as a playlist
+ * @throws Error if the fetch fails or the tab holds several sets
+ */
+export async function fetchCollectionInfo(url: string) {
+  logger.info(`[playlist.ts] Invoking get_collection_info with url: ${url}`);
+  const collection = await api.getCollectionInfo(url);
+  const [playlist, ...rest] = collection.playlists;
+  if (!playlist || rest.length > 0) {
+    throw new Error(`MultipleSets: ${collection.playlists.length} sets in this tab`);
+  }
+  return playlist;
+}
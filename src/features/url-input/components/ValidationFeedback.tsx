@@ -1,6 +1,6 @@
 import { useTranslation } from 'react-i18next';
 import { AlertCircle, CheckCircle2 } from 'lucide-react';
-import type { ValidationResult } from '@/features/url-input/types/url';
+import { isCollectionUrl, type ValidationResult } from '@/features/url-input/types/url';
 
 interface ValidationFeedbackProps {
   result: ValidationResult | null;
@@ -66,6 +66,8 @@ export function ValidationFeedback({ result, isValidating, hideWhenMediaLoaded }
     const getSuccessMessage = () => {
       if (result.urlType === 'playlist') return t('download.validPlaylist');
       if (result.urlType === 'track') return t('download.validTrack');
+      if (result.urlType === 'profile') return t('download.validProfile');
+      if (isCollectionUrl(result.urlType)) return t('download.validCollection');
       return t('download.validShortLink'); // Short links have no type yet
     };
 
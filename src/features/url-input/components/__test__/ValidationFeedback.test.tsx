@@ -11,6 +11,8 @@ vi.mock('react-i18next', () => ({
         'download.validPlaylist': 'Valid playlist URL',
         'download.validTrack': 'Valid track URL',
         'download.validShortLink': 'Valid SoundCloud link',
+        'download.validProfile': 'Valid artist profile URL',
+        'download.validCollection': 'Valid profile tab URL',
         'download.validating': 'Checking URL...',
         'errors.invalidUrl': 'Not a SoundCloud URL',
         'errors.invalidUrlFormat': 'Invalid URL format',
@@ -103,6 +105,30 @@ describe('ValidationFeedback', () => {
     });
   });
 
+  describe('when validation succeeds with profile or profile tab', () => {
+    it('should show success message for profile', () => {
+      render(
+        <ValidationFeedback
+          result={{ valid: true, urlType: 'profile', error: null }}
+          isValidating={false}
+        />
+      );
+
+      expect(screen.getByText('Valid artist profile URL')).toBeInTheDocument();
+    });
+
+    it('should show success message for profile tab', () => {
+      render(
+        <ValidationFeedback
+          result={{ valid: true, urlType: { collection: 'likes' }, error: null }}
+          isValidating={false}
+        />
+      );
+
+      expect(screen.getByText('Valid profile tab URL')).toBeInTheDocument();
+    });
+  });
+
   describe('when validation succeeds with short link', () => {
     const validShortLinkResult: ValidationResult = {
       valid: true,
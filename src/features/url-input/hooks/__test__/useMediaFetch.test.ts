@@ -7,6 +7,8 @@ import type { ValidationResult } from '@/features/url-input/types/url';
 vi.mock('@/features/url-input/api/playlist', () => ({
   fetchPlaylistInfo: vi.fn(),
   fetchTrackInfo: vi.fn(),
+  fetchProfileInfo: vi.fn(),
+  fetchCollectionInfo: vi.fn(),
 }));
 
 vi.mock('react-i18next', () => ({
@@ -15,10 +17,17 @@ vi.mock('react-i18next', () => ({
   }),
 }));
 
-import { fetchPlaylistInfo, fetchTrackInfo } from '@/features/url-input/api/playlist';
+import {
+  fetchPlaylistInfo,
+  fetchTrackInfo,
+  fetchProfileInfo,
+  fetchCollectionInfo,
+} from '@/features/url-input/api/playlist';
 
 const mockFetchPlaylistInfo = vi.mocked(fetchPlaylistInfo);
 const mockFetchTrackInfo = vi.mocked(fetchTrackInfo);
+const mockFetchProfileInfo = vi.mocked(fetchProfileInfo);
+const mockFetchCollectionInfo = vi.mocked(fetchCollectionInfo);
 
 const mockPlaylist = {
   id: 123,
@@ -47,8 +56,12 @@ describe('useMediaFetch', () => {
   beforeEach(() => {
     mockFetchPlaylistInfo.mockReset();
     mockFetchTrackInfo.mockReset();
+    mockFetchProfileInfo.mockReset();
+    mockFetchCollectionInfo.mockReset();
     mockFetchPlaylistInfo.mockResolvedValue(mockPlaylist);
     mockFetchTrackInfo.mockResolvedValue(mockTrack);
+    mockFetchProfileInfo.mockResolvedValue(mockPlaylist);
+    mockFetchCollectionInfo.mockResolvedValue(mockPlaylist);
   });
 
   describe('when validation is null', () => {
@@ -142,6 +155,56 @@ describe('useMediaFetch', () => {
     });
   });
 
+  describe('when validation is valid for a profile or profile tab', () => {
+    it('should fetch profile info', async () => {
+      const { result } = renderHook(
+        ({ url, validation }) => useMediaFetch(url, validation),
+        {
+          initialProps: {
+            url: 'https://soundcloud.com/artist',
+            validation: { valid: true, urlType: 'profile', error: null } as ValidationResult,
+          },
+          wrapper: createQueryWrapper(),
+        }
+      );
+
+      await waitFor(() => {
+        expect(result.current.isLoading).toBe(false);
+      });
+
+      expect(result.current.data).toEqual(mockPlaylist);
+      expect(mockFetchProfileInfo).toHaveBeenCalledWith('https://soundcloud.com/artist');
+      expect(mockFetchTrackInfo).not.toHaveBeenCalled();
+    });
+
+    it('should fetch collection info', async () => {
+      const { result } = renderHook(
+        ({ url, validation }) => useMediaFetch(url, validation),
+        {
+          initialProps: {
+            url: 'https://soundcloud.com/artist/likes',
+            validation: {
+              valid: true,
+              urlType: { collection: 'likes' },
+              error: null,
+            } as ValidationResult,
+          },
+          wrapper: createQueryWrapper(),
+        }
+      );
+
+      await waitFor(() => {
+        expect(result.current.isLoading).toBe(false);
+      });
+
+      expect(result.current.data).toEqual(mockPlaylist);
+      expect(mockFetchCollectionInfo).toHaveBeenCalledWith(
+        'https://soundcloud.com/artist/likes'
+      );
+      expect(mockFetchTrackInfo).not.toHaveBeenCalled();
+    });
+  });
+
   describe('error handling', () => {
     it('should map "not found" error to INVALID_URL', async () => {
       mockFetchTrackInfo.mockRejectedValue(new Error('Track not found'));
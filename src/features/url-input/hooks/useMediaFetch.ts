@@ -1,9 +1,14 @@
 import { useQuery } from '@tanstack/react-query';
 import { useTranslation } from 'react-i18next';
-import { fetchPlaylistInfo, fetchTrackInfo } from '@/features/url-input/api/playlist';
+import {
+  fetchPlaylistInfo,
+  fetchTrackInfo,
+  fetchProfileInfo,
+  fetchCollectionInfo,
+} from '@/features/url-input/api/playlist';
 import { parseMediaError, type FetchError } from '@/features/url-input/utils/parseMediaError';
 import type { PlaylistInfo, TrackInfo } from '@/features/url-input/types/playlist';
-import type { ValidationResult } from '@/features/url-input/types/url';
+import { isCollectionUrl, type ValidationResult } from '@/features/url-input/types/url';
 
 export type { FetchError };
 
@@ -28,6 +33,12 @@ export function useMediaFetch(
       if (urlType === 'playlist') {
         return fetchPlaylistInfo(url);
       }
+      if (urlType === 'profile') {
+        return fetchProfileInfo(url);
+      }
+      if (isCollectionUrl(urlType)) {
+        return fetchCollectionInfo(url);
+      }
       return fetchTrackInfo(url);
     },
     enabled: isEnabled,
@@ -2,7 +2,12 @@ export * from './hooks';
 export * from './types/url';
 export * from './types/playlist';
 export { validateUrl } from './api/validation';
-export { fetchPlaylistInfo, fetchTrackInfo } from './api/playlist';
+export {
+  fetchPlaylistInfo,
+  fetchTrackInfo,
+  fetchProfileInfo,
+  fetchCollectionInfo,
+} from './api/playlist';
 export { UrlInput } from './components/UrlInput';
 export { ValidationFeedback } from './components/ValidationFeedback';
 export { PlaylistPreview } from './components/PlaylistPreview';
@@ -1,2 +1,11 @@
 // Re-export types from generated bindings
 export type { UrlType, ValidationError, ValidationResult } from '@/bindings';
+
+import type { CollectionKind, UrlType } from '@/bindings';
+
+/** Profile tabs (likes, sets, ...) are the only object-shaped URL type. */
+export function isCollectionUrl(
+  urlType: UrlType | null
+): urlType is { collection: CollectionKind } {
+  return typeof urlType === 'object' && urlType !== null;
+}
@@ -39,6 +39,14 @@ export function parseMediaError(err: unknown, t: TFunction): FetchError {
     };
   }
 
+  if (message.includes('MultipleSets')) {
+    return {
+      code: 'MULTIPLE_SETS',
+      message: t('errors.multipleSets'),
+      hint: t('errors.multipleSetsHint'),
+    };
+  }
+
   if (message.includes('TokenExpired')) {
     return {
       code: 'TOKEN_EXPIRED',
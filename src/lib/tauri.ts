@@ -5,6 +5,7 @@ import {
   type StartQueueRequest,
   type PlaylistInfo,
   type TrackInfo,
+  type CollectionInfo,
   type ValidationResult,
   type ErrorResponse,
 } from '@/bindings';
@@ -41,11 +42,17 @@ export const api = {
     commands.signOut().then(unwrap).then(() => undefined),
 
   // Media info
-  getPlaylistInfo: (url: string): Promise<PlaylistInfo> =>
-    commands.getPlaylistInfo(url).then(unwrap),
+  getPlaylistInfo: (url: string, forceRefresh = false): Promise<PlaylistInfo> =>
+    commands.getPlaylistInfo(url, forceRefresh).then(unwrap),
 
-  getTrackInfo: (url: string): Promise<TrackInfo> =>
-    commands.getTrackInfo(url).then(unwrap),
+  getTrackInfo: (url: string, forceRefresh = false): Promise<TrackInfo> =>
+    commands.getTrackInfo(url, forceRefresh).then(unwrap),
+
+  getProfileInfo: (url: string): Promise<PlaylistInfo> =>
+    commands.getProfileInfo(url).then(unwrap),
+
+  getCollectionInfo: (url: string): Promise<CollectionInfo> =>
+    commands.getCollectionInfo(url).then(unwrap),
 
   // Validation (no Result wrapper - returns ValidationResult directly)
   validateSoundcloudUrl: (url: string): Promise<ValidationResult> =>